@@ -0,0 +1,96 @@
+use crate::cluster::Cluster;
+use anchor_lang::{AccountDeserialize, Discriminator, Owners};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use dynamic_amm::state::{Config, LockEscrow, Pool};
+use dynamic_vault::state::{Strategy, Vault};
+use solana_sdk::pubkey::Pubkey;
+
+/// Decoded dynamic amm, dynamic vault, or SPL token account
+#[derive(Debug, Clone)]
+pub enum DecodedAccount {
+    Pool(Box<Pool>),
+    LockEscrow(LockEscrow),
+    Config(Config),
+    Vault(Box<Vault>),
+    Strategy(Strategy),
+    /// SPL token, or token 2022 account. Extensions are not decoded.
+    TokenAccount(TokenAccount),
+    /// SPL token, or token 2022 mint. Extensions are not decoded.
+    Mint(Mint),
+    /// Account not known by the decoder
+    Unknown {
+        owner: Pubkey,
+        data: Vec<u8>,
+    },
+}
+
+impl DecodedAccount {
+    /// Name of the account variant
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pool(_) => "Pool",
+            Self::LockEscrow(_) => "LockEscrow",
+            Self::Config(_) => "Config",
+            Self::Vault(_) => "Vault",
+            Self::Strategy(_) => "Strategy",
+            Self::TokenAccount(_) => "TokenAccount",
+            Self::Mint(_) => "Mint",
+            Self::Unknown { .. } => "Unknown",
+        }
+    }
+}
+
+fn deserialize<T: AccountDeserialize>(data: &[u8]) -> anyhow::Result<T> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+/// Decode the account by its owner, and discriminator.
+/// Account with unknown owner, or discriminator is decoded as `DecodedAccount::Unknown`.
+pub fn decode_account(
+    cluster: &Cluster,
+    owner: &Pubkey,
+    data: &[u8],
+) -> anyhow::Result<DecodedAccount> {
+    let discriminator = data.get(..8).unwrap_or_default();
+
+    let account = if *owner == cluster.amm_program_id {
+        if discriminator == Pool::DISCRIMINATOR {
+            DecodedAccount::Pool(Box::new(deserialize(data)?))
+        } else if discriminator == LockEscrow::DISCRIMINATOR {
+            DecodedAccount::LockEscrow(deserialize(data)?)
+        } else if discriminator == Config::DISCRIMINATOR {
+            DecodedAccount::Config(deserialize(data)?)
+        } else {
+            unknown_account(owner, data)
+        }
+    } else if *owner == cluster.vault_program_id {
+        if discriminator == Vault::DISCRIMINATOR {
+            DecodedAccount::Vault(Box::new(deserialize(data)?))
+        } else if discriminator == Strategy::DISCRIMINATOR {
+            DecodedAccount::Strategy(deserialize(data)?)
+        } else {
+            unknown_account(owner, data)
+        }
+    } else if TokenAccount::owners().contains(owner) {
+        // Token account, and mint are distinguished by the account length, or the account type of token 2022 extension
+        if let Ok(token_account) = deserialize::<TokenAccount>(data) {
+            DecodedAccount::TokenAccount(token_account)
+        } else if let Ok(mint) = deserialize::<Mint>(data) {
+            DecodedAccount::Mint(mint)
+        } else {
+            unknown_account(owner, data)
+        }
+    } else {
+        unknown_account(owner, data)
+    };
+
+    Ok(account)
+}
+
+fn unknown_account(owner: &Pubkey, data: &[u8]) -> DecodedAccount {
+    DecodedAccount::Unknown {
+        owner: *owner,
+        data: data.to_vec(),
+    }
+}
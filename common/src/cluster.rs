@@ -0,0 +1,137 @@
+use anyhow::bail;
+use dynamic_amm::constants::activation::{SLOT_BUFFER, TIME_BUFFER};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+pub const MAINNET_AMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+pub const STAGING_AMM_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ammbh4CQztZ6txJ8AaQgPsWjd6o7GhmvopS2JAo5bCB");
+pub const VAULT_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+pub const METAPLEX_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Activation buffers of the program, which differ between the deployed, and test-bpf build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivationBuffers {
+    pub slot_buffer: u64,
+    pub time_buffer: u64,
+    pub max_activation_slot_duration: u64,
+    pub max_activation_time_duration: u64,
+    pub five_minutes_slot_buffer: u64,
+    pub five_minutes_time_buffer: u64,
+}
+
+impl ActivationBuffers {
+    /// Buffers of the deployed program
+    pub const fn deployed() -> Self {
+        let slot_buffer = SLOT_BUFFER;
+        let time_buffer = TIME_BUFFER;
+        Self {
+            slot_buffer,
+            time_buffer,
+            max_activation_slot_duration: slot_buffer * 24 * 31, // 31 days
+            max_activation_time_duration: time_buffer * 24 * 31, // 31 days
+            five_minutes_slot_buffer: slot_buffer / 12,
+            five_minutes_time_buffer: time_buffer / 12,
+        }
+    }
+
+    /// Buffers of the program built with test-bpf, and localnet features
+    pub const fn localnet() -> Self {
+        Self {
+            slot_buffer: 5,
+            time_buffer: 5,
+            max_activation_slot_duration: 30,
+            max_activation_time_duration: 30,
+            five_minutes_slot_buffer: 5,
+            five_minutes_time_buffer: 5,
+        }
+    }
+}
+
+/// Network of the cluster, which select the accounts created only on that network, such as the non PDA based lp mints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Devnet,
+}
+
+/// Program ids, and constants of the cluster which the client talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cluster {
+    pub amm_program_id: Pubkey,
+    pub vault_program_id: Pubkey,
+    pub metadata_program_id: Pubkey,
+    pub activation_buffers: ActivationBuffers,
+    pub network: Network,
+}
+
+impl Cluster {
+    pub const fn mainnet() -> Self {
+        Self {
+            amm_program_id: MAINNET_AMM_PROGRAM_ID,
+            vault_program_id: VAULT_PROGRAM_ID,
+            metadata_program_id: METAPLEX_PROGRAM_ID,
+            activation_buffers: ActivationBuffers::deployed(),
+            network: Network::Mainnet,
+        }
+    }
+
+    /// Mainnet, with the staging amm program
+    pub const fn staging() -> Self {
+        Self {
+            amm_program_id: STAGING_AMM_PROGRAM_ID,
+            ..Self::mainnet()
+        }
+    }
+
+    /// Devnet programs are deployed with the same program ids as mainnet
+    pub const fn devnet() -> Self {
+        Self {
+            network: Network::Devnet,
+            ..Self::mainnet()
+        }
+    }
+
+    /// Local validator loading the programs at the mainnet program ids, built with test-bpf, and localnet features
+    pub const fn localnet() -> Self {
+        Self {
+            activation_buffers: ActivationBuffers::localnet(),
+            ..Self::mainnet()
+        }
+    }
+
+    /// Use redeployed amm program
+    pub fn with_amm_program_id(mut self, amm_program_id: Pubkey) -> Self {
+        self.amm_program_id = amm_program_id;
+        self
+    }
+
+    /// Use redeployed vault program
+    pub fn with_vault_program_id(mut self, vault_program_id: Pubkey) -> Self {
+        self.vault_program_id = vault_program_id;
+        self
+    }
+}
+
+impl Default for Cluster {
+    fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Self::mainnet()),
+            "staging" => Ok(Self::staging()),
+            "devnet" => Ok(Self::devnet()),
+            "localnet" | "localhost" => Ok(Self::localnet()),
+            _ => bail!("Unknown cluster {s}. Expected mainnet, staging, devnet, or localnet"),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::config::{get_max_activation_duration, validate_activation_point};
+use crate::cluster::Cluster;
+use anyhow::{bail, ensure};
+use dynamic_amm::constants::fee::{
+    FEE_DENOMINATOR, MAX_BASIS_POINT, MAX_FEE_BPS, MAX_PARTNER_FEE_NUMERATOR,
+};
+use dynamic_amm::constants::stable_curve::MAX_AMP;
+use dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+use dynamic_amm::instructions::ConfigParameters;
+use dynamic_amm::state::{ActivationType, CurveType, Pool, PoolFees};
+
+/// Validate the fees of set_pool_fees, or create_config. Protocol trade fee is a portion of the trade fee.
+pub fn validate_pool_fees(pool_fees: &PoolFees, partner_fee_numerator: u64) -> anyhow::Result<()> {
+    ensure!(
+        pool_fees.trade_fee_denominator > 0 && pool_fees.protocol_trade_fee_denominator > 0,
+        "Fee denominator must be non zero"
+    );
+
+    // Compare without rounding the trade fee to bps
+    ensure!(
+        u128::from(pool_fees.trade_fee_numerator) * u128::from(MAX_BASIS_POINT)
+            <= u128::from(MAX_FEE_BPS) * u128::from(pool_fees.trade_fee_denominator),
+        "Trade fee {} over {} > max fee {} bps",
+        pool_fees.trade_fee_numerator,
+        pool_fees.trade_fee_denominator,
+        MAX_FEE_BPS
+    );
+    ensure!(
+        pool_fees.protocol_trade_fee_numerator <= pool_fees.protocol_trade_fee_denominator,
+        "Protocol trade fee numerator {} > denominator {}",
+        pool_fees.protocol_trade_fee_numerator,
+        pool_fees.protocol_trade_fee_denominator
+    );
+    ensure!(
+        partner_fee_numerator <= MAX_PARTNER_FEE_NUMERATOR,
+        "Partner fee numerator {} > {} over {}",
+        partner_fee_numerator,
+        MAX_PARTNER_FEE_NUMERATOR,
+        FEE_DENOMINATOR
+    );
+
+    Ok(())
+}
+
+/// Validate the config parameters of create_config
+pub fn validate_config_parameters(
+    cluster: &Cluster,
+    params: &ConfigParameters,
+) -> anyhow::Result<()> {
+    validate_pool_fees(
+        &PoolFees {
+            trade_fee_numerator: params.trade_fee_numerator,
+            trade_fee_denominator: FEE_DENOMINATOR,
+            protocol_trade_fee_numerator: params.protocol_trade_fee_numerator,
+            protocol_trade_fee_denominator: FEE_DENOMINATOR,
+        },
+        params.partner_fee_numerator,
+    )?;
+
+    let Ok(activation_type) = ActivationType::try_from(params.activation_type) else {
+        bail!("Invalid activation type {}", params.activation_type);
+    };
+    let max_activation_duration = get_max_activation_duration(cluster, activation_type);
+    ensure!(
+        params.activation_duration <= max_activation_duration,
+        "Activation duration {} > {}",
+        params.activation_duration,
+        max_activation_duration
+    );
+
+    Ok(())
+}
+
+/// Stable curve of the pool with the amp overridden. The other attributes are ignored by override_curve_param.
+/// Amp can only be changed `MIN_CHANGE_AMP_DURATION` seconds after the last change.
+pub fn get_override_curve_type(
+    curve_type: CurveType,
+    amp: u64,
+    current_timestamp: u64,
+) -> anyhow::Result<CurveType> {
+    let CurveType::Stable {
+        token_multiplier,
+        depeg,
+        last_amp_updated_timestamp,
+        ..
+    } = curve_type
+    else {
+        bail!("Only stable pool curve can be overridden");
+    };
+
+    ensure!(
+        amp > 0 && amp <= MAX_AMP,
+        "Amp must be between 1 and {}",
+        MAX_AMP
+    );
+
+    let next_amp_update_timestamp =
+        last_amp_updated_timestamp.saturating_add(MIN_CHANGE_AMP_DURATION);
+    ensure!(
+        current_timestamp >= next_amp_update_timestamp,
+        "Amp was updated at {}, it can only be changed after {}",
+        last_amp_updated_timestamp,
+        next_amp_update_timestamp
+    );
+
+    Ok(CurveType::Stable {
+        amp,
+        token_multiplier,
+        depeg,
+        last_amp_updated_timestamp,
+    })
+}
+
+/// Validate the new activation point of the pool, which is not activated yet.
+/// `current_point` is the current slot, or timestamp matching the pool activation type.
+pub fn validate_update_activation_point(
+    cluster: &Cluster,
+    pool: &Pool,
+    new_activation_point: u64,
+    current_point: u64,
+) -> anyhow::Result<()> {
+    let activation_type = pool.bootstrapping.activation_type;
+    let Ok(activation_type) = ActivationType::try_from(activation_type) else {
+        bail!("Invalid pool activation type {}", activation_type);
+    };
+
+    ensure!(
+        current_point < pool.bootstrapping.activation_point,
+        "Pool was activated at {}",
+        pool.bootstrapping.activation_point
+    );
+    validate_activation_point(
+        cluster,
+        activation_type,
+        new_activation_point,
+        current_point,
+    )
+}
@@ -1,8 +1,12 @@
-use std::collections::HashMap;
-
+use super::pda::derive_lp_mint_key;
+use crate::cluster::{Cluster, Network};
+use crate::lp_mint_registry::{verify_lp_mints, LpMintMismatch, LpMintRegistry};
+use dynamic_amm::state::Pool;
 use lazy_static::lazy_static;
+use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 
 lazy_static! {
     pub static ref POOL_WITH_NON_PDA_BASED_LP_MINT: HashMap<Pubkey, Pubkey> = HashMap::from_iter([
@@ -93,44 +97,75 @@ lazy_static! {
     ]);
 }
 
-#[cfg(feature = "devnet")]
 lazy_static! {
-    static ref POOL_WITH_NON_PDA_BASED_LP_MINT: HashMap<Pubkey, Pubkey> = HashMap::from_iter([
-        (
-            pubkey!("GXy2cEDWFodXuXpEZZizVzcyiqF2QZCiMqfZX9BGx1vz"),
-            pubkey!("2nqgDcgfTzXJSckrVdqZGFpSfAAUY7NJKhCeikioaP5m")
-        ),
-        (
-            pubkey!("2GPECnGQbXgBBPmmLdw8daxu6A1VJUyuHGWPyJH6U56h"),
-            pubkey!("FMnK5dTHUDR9iLvcshaaxnnEtYN1Ly5Gv8e3vccQ8k9K")
-        ),
-        (
-            pubkey!("3PSrJVm8CYJ9R1eJUT9iMikjWD6b8xHWp8VGUnMdr5yF"),
-            pubkey!("cu45VVBLEjpwufw4A4FRLmfyckJNwwxmNrf86burdvy")
-        ),
-        (
-            pubkey!("FZgdEqq6rwsWnsZ83Ez2pyJqPdPGfDvzYhrbvcboTPtf"),
-            pubkey!("2qWv8R6EBibqTsBCfyiqKaDh3HU8TxwD26cr5zGkFMJs")
-        ),
-        (
-            pubkey!("AyRTAzaXPamTMTRG8dny9jqG3EGWte8FrY5g9Ds3Gtr2"),
-            pubkey!("8J65QEAV5c6CREDBpNBYhrKn7zGdoodMj2Nk1mSeUpPz")
-        ),
-        (
-            pubkey!("HK7b7P3goViFkSLQ7rTKRemsGa8LKNKt2t9D4buzbxq2"),
-            pubkey!("BJUsjgYod77LrvTpTATwPQwjuk7hHPyNGKYUSVRJmgvN")
-        ),
-        (
-            pubkey!("BAHscmu1NncGS7t4rc5gSBPv1UFEMkvLaon1Ahdd5rHi"),
-            pubkey!("3A2DuLdNFyeVFVsumFEVWKFoLaeTryZ4PQSJowD38Le7")
-        ),
-        (
-            pubkey!("2rkn2yM4wJcHPV57T8fPWeBksrfSpiNZoEjRgjtxNDEQ"),
-            pubkey!("ENoFQvqrk6LnxRUmPX1cJzHyrsicJCrbZ2WEtGMY9y6N")
-        ),
-        (
-            pubkey!("Bgf1Sy5kfeDgib4go4NgzHuZwek8wE8NZus56z6uizzi"),
-            pubkey!("2xSpdNRwDjkx2BJAtdu2zArWybzeEHqPqP1m63tFakNU")
-        ),
-    ]);
+    pub static ref DEVNET_POOL_WITH_NON_PDA_BASED_LP_MINT: HashMap<Pubkey, Pubkey> =
+        HashMap::from_iter([
+            (
+                pubkey!("GXy2cEDWFodXuXpEZZizVzcyiqF2QZCiMqfZX9BGx1vz"),
+                pubkey!("2nqgDcgfTzXJSckrVdqZGFpSfAAUY7NJKhCeikioaP5m")
+            ),
+            (
+                pubkey!("2GPECnGQbXgBBPmmLdw8daxu6A1VJUyuHGWPyJH6U56h"),
+                pubkey!("FMnK5dTHUDR9iLvcshaaxnnEtYN1Ly5Gv8e3vccQ8k9K")
+            ),
+            (
+                pubkey!("3PSrJVm8CYJ9R1eJUT9iMikjWD6b8xHWp8VGUnMdr5yF"),
+                pubkey!("cu45VVBLEjpwufw4A4FRLmfyckJNwwxmNrf86burdvy")
+            ),
+            (
+                pubkey!("FZgdEqq6rwsWnsZ83Ez2pyJqPdPGfDvzYhrbvcboTPtf"),
+                pubkey!("2qWv8R6EBibqTsBCfyiqKaDh3HU8TxwD26cr5zGkFMJs")
+            ),
+            (
+                pubkey!("AyRTAzaXPamTMTRG8dny9jqG3EGWte8FrY5g9Ds3Gtr2"),
+                pubkey!("8J65QEAV5c6CREDBpNBYhrKn7zGdoodMj2Nk1mSeUpPz")
+            ),
+            (
+                pubkey!("HK7b7P3goViFkSLQ7rTKRemsGa8LKNKt2t9D4buzbxq2"),
+                pubkey!("BJUsjgYod77LrvTpTATwPQwjuk7hHPyNGKYUSVRJmgvN")
+            ),
+            (
+                pubkey!("BAHscmu1NncGS7t4rc5gSBPv1UFEMkvLaon1Ahdd5rHi"),
+                pubkey!("3A2DuLdNFyeVFVsumFEVWKFoLaeTryZ4PQSJowD38Le7")
+            ),
+            (
+                pubkey!("2rkn2yM4wJcHPV57T8fPWeBksrfSpiNZoEjRgjtxNDEQ"),
+                pubkey!("ENoFQvqrk6LnxRUmPX1cJzHyrsicJCrbZ2WEtGMY9y6N")
+            ),
+            (
+                pubkey!("Bgf1Sy5kfeDgib4go4NgzHuZwek8wE8NZus56z6uizzi"),
+                pubkey!("2xSpdNRwDjkx2BJAtdu2zArWybzeEHqPqP1m63tFakNU")
+            ),
+        ]);
+}
+
+lazy_static! {
+    /// Built-in mainnet pools with non PDA based lp mint, extendable at runtime
+    pub static ref POOL_LP_MINT_REGISTRY: LpMintRegistry =
+        LpMintRegistry::new(POOL_WITH_NON_PDA_BASED_LP_MINT.clone());
+    /// Built-in devnet pools with non PDA based lp mint, extendable at runtime
+    pub static ref DEVNET_POOL_LP_MINT_REGISTRY: LpMintRegistry =
+        LpMintRegistry::new(DEVNET_POOL_WITH_NON_PDA_BASED_LP_MINT.clone());
+}
+
+/// Lp mint registry of the pools on the network of the cluster
+pub fn get_pool_lp_mint_registry(cluster: &Cluster) -> &'static LpMintRegistry {
+    match cluster.network {
+        Network::Mainnet => &POOL_LP_MINT_REGISTRY,
+        Network::Devnet => &DEVNET_POOL_LP_MINT_REGISTRY,
+    }
+}
+
+/// Fetch the pools, and report those which lp mint differ from `derive_lp_mint_key`
+pub fn verify_pool_lp_mints(
+    cluster: &Cluster,
+    rpc_client: &RpcClient,
+    pools: &[Pubkey],
+) -> anyhow::Result<Vec<LpMintMismatch>> {
+    verify_lp_mints(
+        rpc_client,
+        pools,
+        |state: &Pool| state.lp_mint,
+        |key| derive_lp_mint_key(cluster, key),
+    )
 }
@@ -0,0 +1,211 @@
+use crate::cluster::Cluster;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{bail, ensure};
+use dynamic_amm::constants::fee::MAX_BASIS_POINT;
+use dynamic_amm::state::{ActivationType, Config, PoolFees};
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::pubkey::Pubkey;
+
+/// Offset of `Config::pool_creator_authority`, including the discriminator
+const POOL_CREATOR_AUTHORITY_OFFSET: usize = 8 + 72;
+/// Offset of `Config::activation_type`, including the discriminator
+const ACTIVATION_TYPE_OFFSET: usize = POOL_CREATOR_AUTHORITY_OFFSET + 32;
+
+/// Filter of config accounts. None matches any value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigFilter {
+    /// Only the creator authority can create pool with the config. Pubkey::default() for config which anyone can use.
+    pub pool_creator_authority: Option<Pubkey>,
+    pub activation_type: Option<ActivationType>,
+    pub trade_fee_bps: Option<u64>,
+    pub protocol_trade_fee_bps: Option<u64>,
+}
+
+impl ConfigFilter {
+    /// Filters evaluated by the RPC node. Fee is compared after fetching as the fee denominator is not fixed.
+    pub fn to_rpc_filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &Config::discriminator(),
+        ))];
+
+        if let Some(pool_creator_authority) = self.pool_creator_authority {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                POOL_CREATOR_AUTHORITY_OFFSET,
+                pool_creator_authority.as_ref(),
+            )));
+        }
+
+        if let Some(activation_type) = self.activation_type {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                ACTIVATION_TYPE_OFFSET,
+                &[activation_type as u8],
+            )));
+        }
+
+        filters
+    }
+
+    pub fn matches(&self, config: &Config) -> bool {
+        if let Some(pool_creator_authority) = self.pool_creator_authority {
+            if pool_creator_authority != config.pool_creator_authority {
+                return false;
+            }
+        }
+
+        if let Some(activation_type) = self.activation_type {
+            if activation_type as u8 != config.activation_type {
+                return false;
+            }
+        }
+
+        if let Some(trade_fee_bps) = self.trade_fee_bps {
+            if get_trade_fee_bps(&config.pool_fees) != Some(trade_fee_bps) {
+                return false;
+            }
+        }
+
+        if let Some(protocol_trade_fee_bps) = self.protocol_trade_fee_bps {
+            if get_protocol_trade_fee_bps(&config.pool_fees) != Some(protocol_trade_fee_bps) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn to_bps(numerator: u64, denominator: u64) -> Option<u64> {
+    u128::from(numerator)
+        .checked_mul(MAX_BASIS_POINT.into())?
+        .checked_div(denominator.into())?
+        .try_into()
+        .ok()
+}
+
+pub fn get_trade_fee_bps(pool_fees: &PoolFees) -> Option<u64> {
+    to_bps(
+        pool_fees.trade_fee_numerator,
+        pool_fees.trade_fee_denominator,
+    )
+}
+
+pub fn get_protocol_trade_fee_bps(pool_fees: &PoolFees) -> Option<u64> {
+    to_bps(
+        pool_fees.protocol_trade_fee_numerator,
+        pool_fees.protocol_trade_fee_denominator,
+    )
+}
+
+/// Fetch, and decode all config accounts matching the filter, sorted by the config key
+pub fn get_configs(
+    cluster: &Cluster,
+    rpc_client: &RpcClient,
+    filter: &ConfigFilter,
+) -> anyhow::Result<Vec<(Pubkey, Config)>> {
+    let accounts: Vec<(Pubkey, Account)> = rpc_client.get_program_accounts_with_config(
+        &cluster.amm_program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(filter.to_rpc_filters()),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut configs = vec![];
+    for (key, account) in accounts {
+        let config = Config::try_deserialize(&mut account.data.as_ref())?;
+        if filter.matches(&config) {
+            configs.push((key, config));
+        }
+    }
+    configs.sort_by_key(|(key, _)| *key);
+
+    Ok(configs)
+}
+
+/// Current slot, or timestamp of the clock depending on the activation type
+pub fn get_current_point(clock: &Clock, activation_type: ActivationType) -> anyhow::Result<u64> {
+    Ok(match activation_type {
+        ActivationType::Slot => clock.slot,
+        ActivationType::Timestamp => clock.unix_timestamp.try_into()?,
+    })
+}
+
+/// Max duration between the pool creation, and activation of the activation type
+pub fn get_max_activation_duration(cluster: &Cluster, activation_type: ActivationType) -> u64 {
+    match activation_type {
+        ActivationType::Slot => cluster.activation_buffers.max_activation_slot_duration,
+        ActivationType::Timestamp => cluster.activation_buffers.max_activation_time_duration,
+    }
+}
+
+/// Validate the activation point is between the current point, and the current point + max activation duration
+pub fn validate_activation_point(
+    cluster: &Cluster,
+    activation_type: ActivationType,
+    activation_point: u64,
+    current_point: u64,
+) -> anyhow::Result<()> {
+    let max_activation_duration = get_max_activation_duration(cluster, activation_type);
+    ensure!(
+        activation_point >= current_point,
+        "Activation point {} < current point {}",
+        activation_point,
+        current_point
+    );
+    ensure!(
+        activation_point - current_point <= max_activation_duration,
+        "Activation point {} > current point {} + {}",
+        activation_point,
+        current_point,
+        max_activation_duration
+    );
+    Ok(())
+}
+
+/// Validate the pool creation by the payer with the config, and return the activation point of the pool.
+/// `current_point` is the current slot, or timestamp matching the config activation type.
+/// Activation point default to the current point + activation duration of the config.
+pub fn get_activation_point_from_config(
+    cluster: &Cluster,
+    config: &Config,
+    payer: Pubkey,
+    activation_point: Option<u64>,
+    current_point: u64,
+) -> anyhow::Result<u64> {
+    ensure!(
+        config.pool_creator_authority == Pubkey::default()
+            || config.pool_creator_authority == payer,
+        "Only {} can create pool with the config",
+        config.pool_creator_authority
+    );
+
+    let Ok(activation_type) = ActivationType::try_from(config.activation_type) else {
+        bail!("Invalid config activation type {}", config.activation_type);
+    };
+
+    let max_activation_duration = get_max_activation_duration(cluster, activation_type);
+    ensure!(
+        config.activation_duration <= max_activation_duration,
+        "Config activation duration {} > {}",
+        config.activation_duration,
+        max_activation_duration
+    );
+
+    match activation_point {
+        Some(activation_point) => {
+            validate_activation_point(cluster, activation_type, activation_point, current_point)?;
+            Ok(activation_point)
+        }
+        None => Ok(current_point.saturating_add(config.activation_duration)),
+    }
+}
@@ -0,0 +1,74 @@
+use super::config::validate_activation_point;
+use crate::cluster::Cluster;
+use anyhow::{bail, ensure};
+use dynamic_amm::constants::fee::{
+    FEE_DENOMINATOR, MAX_BASIS_POINT, MEME_MAX_FEE_NUMERATOR, MEME_MIN_FEE_NUMERATOR,
+};
+use dynamic_amm::constants::QUOTE_MINTS;
+use dynamic_amm::instructions::CustomizableParams;
+use dynamic_amm::state::ActivationType;
+use solana_sdk::pubkey::Pubkey;
+
+/// Customizable permissionless constant product pool parameters, before validation
+#[derive(Debug, Clone, Copy)]
+pub struct CustomizablePoolParams {
+    pub trade_fee_bps: u64,
+    pub activation_type: ActivationType,
+    /// None to activate the pool on creation
+    pub activation_point: Option<u64>,
+    pub has_alpha_vault: bool,
+}
+
+impl CustomizablePoolParams {
+    /// Trade fee numerator over `FEE_DENOMINATOR`
+    pub fn trade_fee_numerator(&self) -> Option<u64> {
+        self.trade_fee_bps
+            .checked_mul(FEE_DENOMINATOR)?
+            .checked_div(MAX_BASIS_POINT)
+    }
+
+    /// Validate the parameters for the token pair, and build the instruction params.
+    /// `current_point` is the current slot, or timestamp matching the activation type.
+    pub fn to_customizable_params(
+        &self,
+        cluster: &Cluster,
+        token_a_mint: Pubkey,
+        token_b_mint: Pubkey,
+        current_point: u64,
+    ) -> anyhow::Result<CustomizableParams> {
+        ensure!(
+            QUOTE_MINTS.contains(&token_a_mint) || QUOTE_MINTS.contains(&token_b_mint),
+            "One of the token mint must be a quote mint {:?}",
+            QUOTE_MINTS
+        );
+
+        let trade_fee_numerator = self.trade_fee_numerator().filter(|numerator| {
+            (MEME_MIN_FEE_NUMERATOR..=MEME_MAX_FEE_NUMERATOR).contains(numerator)
+        });
+        let Some(trade_fee_numerator) = trade_fee_numerator else {
+            bail!(
+                "Trade fee numerator must be between {} and {} over {}",
+                MEME_MIN_FEE_NUMERATOR,
+                MEME_MAX_FEE_NUMERATOR,
+                FEE_DENOMINATOR
+            );
+        };
+
+        if let Some(activation_point) = self.activation_point {
+            validate_activation_point(
+                cluster,
+                self.activation_type,
+                activation_point,
+                current_point,
+            )?;
+        }
+
+        Ok(CustomizableParams {
+            trade_fee_numerator: trade_fee_numerator.try_into()?,
+            activation_point: self.activation_point,
+            has_alpha_vault: self.has_alpha_vault,
+            activation_type: self.activation_type as u8,
+            padding: [0u8; 90],
+        })
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_lang::__private::base64;
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{ensure, Context};
+use dynamic_amm::event;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+macro_rules! dynamic_amm_events {
+    ($($event:ident),* $(,)?) => {
+        /// Decoded dynamic amm event
+        #[derive(Debug, Clone)]
+        pub enum DynamicAmmEvent {
+            $($event(event::$event),)*
+            /// Event with discriminator not known by the decoder
+            Unknown { discriminator: [u8; 8], data: Vec<u8> },
+        }
+
+        impl DynamicAmmEvent {
+            /// Name of the event variant
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                    Self::Unknown { .. } => "Unknown",
+                }
+            }
+        }
+
+        /// Decode event data, which is prefixed by the event discriminator
+        pub fn decode_event(data: &[u8]) -> anyhow::Result<DynamicAmmEvent> {
+            ensure!(data.len() >= 8, "Event data is shorter than discriminator");
+            let (discriminator, mut event_data) = data.split_at(8);
+
+            $(
+                if discriminator == event::$event::DISCRIMINATOR {
+                    let event = event::$event::deserialize(&mut event_data)
+                        .context(concat!("Fail to decode ", stringify!($event), " event"))?;
+                    return Ok(DynamicAmmEvent::$event(event));
+                }
+            )*
+
+            Ok(DynamicAmmEvent::Unknown {
+                discriminator: discriminator.try_into()?,
+                data: data.to_vec(),
+            })
+        }
+    };
+}
+
+dynamic_amm_events! {
+    AddLiquidity,
+    RemoveLiquidity,
+    BootstrapLiquidity,
+    Swap,
+    SetPoolFees,
+    PoolInfo,
+    TransferAdmin,
+    OverrideCurveParam,
+    PoolCreated,
+    PoolEnabled,
+    MigrateFeeAccount,
+    CreateLockEscrow,
+    Lock,
+    ClaimFee,
+    CreateConfig,
+    CloseConfig,
+    WithdrawProtocolFees,
+    PartnerClaimFees,
+}
+
+/// Event emitted by the program, with where it was emitted
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    /// Index of the transaction instruction which emitted the event, directly or through CPI
+    pub instruction_index: usize,
+    /// Invoke depth of the program when it emitted the event. 1 for transaction instruction, greater than 1 when invoked through CPI.
+    pub invoke_depth: usize,
+    /// Number of inner instructions invoked by the transaction instruction before the event was emitted.
+    /// Event CPI is counted from its own inner instruction, so logged, and CPI events can be merged in execution order.
+    pub inner_instruction_position: usize,
+    /// Program which emitted the event
+    pub program_id: Pubkey,
+    pub event: DynamicAmmEvent,
+}
+
+/// Decode event emitted through self CPI (anchor `emit_cpi!`) from the inner instruction data.
+/// Return None if the instruction is not an event CPI.
+pub fn decode_event_cpi(data: &[u8]) -> Option<anyhow::Result<DynamicAmmEvent>> {
+    let event_data = data.strip_prefix(&EVENT_IX_TAG_LE)?;
+    Some(decode_event(event_data))
+}
+
+/// Decode every event emitted by `program_id` through `Program data:` logs, in order, including events emitted by CPI.
+/// Instruction index is counted from the top level program invocations of the logs, and inner instruction position from the CPI invocations.
+/// Log which fail to decode is skipped, so it doesn't drop the other events of the transaction.
+pub fn parse_event_logs(logs: &[String], program_id: Pubkey) -> Vec<DecodedEvent> {
+    let mut events = vec![];
+    let mut execution_stack: Vec<Pubkey> = vec![];
+    let mut instruction_count = 0usize;
+    let mut inner_instruction_count = 0usize;
+
+    for log in logs {
+        let Some(log) = log.strip_prefix("Program ") else {
+            continue;
+        };
+
+        if let Some(data) = log.strip_prefix("data: ") {
+            if execution_stack.last() != Some(&program_id) {
+                continue;
+            }
+            // Multiple data slices are separated by space. Anchor event is logged as a single slice.
+            let Some(data) = data.split_whitespace().next() else {
+                continue;
+            };
+            let Ok(data) = base64::decode(data) else {
+                continue;
+            };
+            let Ok(event) = decode_event(&data) else {
+                continue;
+            };
+            events.push(DecodedEvent {
+                instruction_index: instruction_count.saturating_sub(1),
+                invoke_depth: execution_stack.len(),
+                inner_instruction_position: inner_instruction_count,
+                program_id,
+                event,
+            });
+            continue;
+        }
+
+        let mut parts = log.split_whitespace();
+        let (Some(program), Some(status)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(program) = Pubkey::from_str(program) else {
+            continue;
+        };
+
+        match status {
+            "invoke" => {
+                if execution_stack.is_empty() {
+                    instruction_count += 1;
+                    inner_instruction_count = 0;
+                } else {
+                    inner_instruction_count += 1;
+                }
+                execution_stack.push(program);
+            }
+            "success" | "failed:" => {
+                execution_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
@@ -0,0 +1,23 @@
+use anyhow::{ensure, Context};
+
+/// Validate the trade fee bps is one of the allowed fee tiers. Default to the lowest allowed trade fee bps.
+pub fn select_trade_fee_bps(
+    allowed_trade_fee_bps: &[u64],
+    trade_fee_bps: Option<u64>,
+) -> anyhow::Result<u64> {
+    match trade_fee_bps {
+        Some(trade_fee_bps) => {
+            ensure!(
+                allowed_trade_fee_bps.contains(&trade_fee_bps),
+                "Trade fee bps must be one of {:?}",
+                allowed_trade_fee_bps
+            );
+            Ok(trade_fee_bps)
+        }
+        None => allowed_trade_fee_bps
+            .iter()
+            .min()
+            .copied()
+            .context("No allowed trade fee bps"),
+    }
+}
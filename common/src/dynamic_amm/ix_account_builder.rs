@@ -1,23 +1,21 @@
+use crate::cluster::Cluster;
+use crate::dynamic_vault::VaultBases;
 use crate::{dynamic_amm::pda::*, dynamic_vault::pda::*};
 use anchor_spl::associated_token::get_associated_token_address;
-use dynamic_amm::state::{Depeg, TokenMultiplier};
 use solana_sdk::pubkey::Pubkey;
 
+use super::StableCurve;
+
 pub enum CurveTypeIx {
     ConstantProduct,
-    Stable,
+    Stable(StableCurve),
 }
 
 impl From<CurveTypeIx> for dynamic_amm::state::CurveType {
     fn from(value: CurveTypeIx) -> Self {
         match value {
             CurveTypeIx::ConstantProduct => dynamic_amm::state::CurveType::ConstantProduct,
-            CurveTypeIx::Stable => dynamic_amm::state::CurveType::Stable {
-                amp: 0,
-                token_multiplier: TokenMultiplier::default(),
-                depeg: Depeg::default(),
-                last_amp_updated_timestamp: 0,
-            },
+            CurveTypeIx::Stable(stable_curve) => stable_curve.curve_type(),
         }
     }
 }
@@ -41,29 +39,31 @@ pub struct InitPoolRelatedKeys {
 }
 
 pub fn get_or_derive_initialize_pool_related_keys(
+    cluster: &Cluster,
     pool_key: Pubkey,
     token_a_mint: Pubkey,
     token_b_mint: Pubkey,
     payer: Pubkey,
+    vault_bases: VaultBases,
 ) -> InitPoolRelatedKeys {
-    let vault_a_key = derive_vault_key(token_a_mint);
-    let vault_b_key = derive_vault_key(token_b_mint);
+    let vault_a_key = derive_vault_key_with_base(cluster, token_a_mint, vault_bases.token_a);
+    let vault_b_key = derive_vault_key_with_base(cluster, token_b_mint, vault_bases.token_b);
 
-    let vault_a_token_vault = derive_token_vault_key(vault_a_key);
-    let vault_b_token_vault = derive_token_vault_key(vault_b_key);
+    let vault_a_token_vault = derive_token_vault_key(cluster, vault_a_key);
+    let vault_b_token_vault = derive_token_vault_key(cluster, vault_b_key);
 
-    let vault_a_lp_mint = crate::dynamic_vault::pda::derive_lp_mint_key(vault_a_key);
-    let vault_b_lp_mint = crate::dynamic_vault::pda::derive_lp_mint_key(vault_b_key);
+    let vault_a_lp_mint = crate::dynamic_vault::pda::derive_lp_mint_key(cluster, vault_a_key);
+    let vault_b_lp_mint = crate::dynamic_vault::pda::derive_lp_mint_key(cluster, vault_b_key);
 
-    let lp_mint = crate::dynamic_amm::pda::derive_lp_mint_key(pool_key);
+    let lp_mint = crate::dynamic_amm::pda::derive_lp_mint_key(cluster, pool_key);
 
-    let protocol_token_a_fee = derive_protocol_fee_key(token_a_mint, pool_key);
-    let protocol_token_b_fee = derive_protocol_fee_key(token_b_mint, pool_key);
+    let protocol_token_a_fee = derive_protocol_fee_key(cluster, token_a_mint, pool_key);
+    let protocol_token_b_fee = derive_protocol_fee_key(cluster, token_b_mint, pool_key);
 
-    let vault_a_lp_key = derive_vault_lp_key(vault_a_key, pool_key);
-    let vault_b_lp_key = derive_vault_lp_key(vault_b_key, pool_key);
+    let vault_a_lp_key = derive_vault_lp_key(cluster, vault_a_key, pool_key);
+    let vault_b_lp_key = derive_vault_lp_key(cluster, vault_b_key, pool_key);
 
-    let mint_metadata = derive_metadata_key(lp_mint);
+    let mint_metadata = derive_metadata_key(cluster, lp_mint);
 
     let payer_token_a = get_associated_token_address(&payer, &token_a_mint);
     let payer_token_b = get_associated_token_address(&payer, &token_b_mint);
@@ -92,15 +92,18 @@ pub struct IxAccountBuilder;
 
 impl IxAccountBuilder {
     pub fn initialize_permissionless_pool_with_fee_tier_accounts(
+        cluster: &Cluster,
         curve_type_ix: CurveTypeIx,
         trade_fee_bps: u64,
         token_a_mint: Pubkey,
         token_b_mint: Pubkey,
         payer: Pubkey,
+        vault_bases: VaultBases,
     ) -> dynamic_amm::accounts::InitializePermissionlessPoolWithFeeTier {
         let curve_type = curve_type_ix.into();
 
         let pool_key = derive_permissionless_pool_key_with_fee_tier(
+            cluster,
             curve_type,
             token_a_mint,
             token_b_mint,
@@ -123,7 +126,14 @@ impl IxAccountBuilder {
             payer_token_a,
             payer_pool_lp,
             payer_token_b,
-        } = get_or_derive_initialize_pool_related_keys(pool_key, token_a_mint, token_b_mint, payer);
+        } = get_or_derive_initialize_pool_related_keys(
+            cluster,
+            pool_key,
+            token_a_mint,
+            token_b_mint,
+            payer,
+            vault_bases,
+        );
 
         let accounts = dynamic_amm::accounts::InitializePermissionlessPoolWithFeeTier {
             pool: pool_key,
@@ -147,8 +157,8 @@ impl IxAccountBuilder {
             payer,
             // Deprecated field
             fee_owner: payer,
-            vault_program: dynamic_vault::ID,
-            metadata_program: mpl_token_metadata::ID,
+            vault_program: cluster.vault_program_id,
+            metadata_program: cluster.metadata_program_id,
             rent: solana_sdk::sysvar::rent::ID,
             associated_token_program: anchor_spl::associated_token::ID,
             system_program: solana_sdk::system_program::ID,
@@ -159,13 +169,16 @@ impl IxAccountBuilder {
     }
 
     pub fn initialize_permissionless_pool_accounts(
+        cluster: &Cluster,
         curve_type_ix: CurveTypeIx,
         token_a_mint: Pubkey,
         token_b_mint: Pubkey,
         payer: Pubkey,
+        vault_bases: VaultBases,
     ) -> dynamic_amm::accounts::InitializePermissionlessPool {
         let curve_type = curve_type_ix.into();
-        let pool_key = derive_permissionless_pool_key(curve_type, token_a_mint, token_b_mint);
+        let pool_key =
+            derive_permissionless_pool_key(cluster, curve_type, token_a_mint, token_b_mint);
 
         let InitPoolRelatedKeys {
             vault_a,
@@ -183,7 +196,14 @@ impl IxAccountBuilder {
             payer_token_a,
             payer_pool_lp,
             payer_token_b,
-        } = get_or_derive_initialize_pool_related_keys(pool_key, token_a_mint, token_b_mint, payer);
+        } = get_or_derive_initialize_pool_related_keys(
+            cluster,
+            pool_key,
+            token_a_mint,
+            token_b_mint,
+            payer,
+            vault_bases,
+        );
 
         let accounts = dynamic_amm::accounts::InitializePermissionlessPool {
             pool: pool_key,
@@ -207,8 +227,8 @@ impl IxAccountBuilder {
             payer,
             // Deprecated field
             fee_owner: payer,
-            vault_program: dynamic_vault::ID,
-            metadata_program: mpl_token_metadata::ID,
+            vault_program: cluster.vault_program_id,
+            metadata_program: cluster.metadata_program_id,
             rent: solana_sdk::sysvar::rent::ID,
             associated_token_program: anchor_spl::associated_token::ID,
             system_program: solana_sdk::system_program::ID,
@@ -219,12 +239,15 @@ impl IxAccountBuilder {
     }
 
     pub fn initialize_permissionless_constant_product_pool_with_config_accounts(
+        cluster: &Cluster,
         token_a_mint: Pubkey,
         token_b_mint: Pubkey,
         config: Pubkey,
         payer: Pubkey,
+        vault_bases: VaultBases,
     ) -> dynamic_amm::accounts::InitializePermissionlessConstantProductPoolWithConfig {
         let pool_key = derive_permissionless_constant_product_pool_with_config_key(
+            cluster,
             token_a_mint,
             token_b_mint,
             config,
@@ -246,7 +269,14 @@ impl IxAccountBuilder {
             payer_token_a,
             payer_pool_lp,
             payer_token_b,
-        } = get_or_derive_initialize_pool_related_keys(pool_key, token_a_mint, token_b_mint, payer);
+        } = get_or_derive_initialize_pool_related_keys(
+            cluster,
+            pool_key,
+            token_a_mint,
+            token_b_mint,
+            payer,
+            vault_bases,
+        );
 
         let accounts =
             dynamic_amm::accounts::InitializePermissionlessConstantProductPoolWithConfig {
@@ -270,8 +300,8 @@ impl IxAccountBuilder {
                 payer_token_b,
                 payer,
                 config,
-                vault_program: dynamic_vault::ID,
-                metadata_program: mpl_token_metadata::ID,
+                vault_program: cluster.vault_program_id,
+                metadata_program: cluster.metadata_program_id,
                 rent: solana_sdk::sysvar::rent::ID,
                 associated_token_program: anchor_spl::associated_token::ID,
                 system_program: solana_sdk::system_program::ID,
@@ -282,11 +312,14 @@ impl IxAccountBuilder {
     }
 
     pub fn initialize_customizable_permissionless_constant_product_pool(
+        cluster: &Cluster,
         token_a_mint: Pubkey,
         token_b_mint: Pubkey,
         payer: Pubkey,
+        vault_bases: VaultBases,
     ) -> dynamic_amm::accounts::InitializeCustomizablePermissionlessConstantProductPool {
         let pool_key = derive_customizable_permissionless_constant_product_pool_key(
+            cluster,
             token_a_mint,
             token_b_mint,
         );
@@ -307,7 +340,14 @@ impl IxAccountBuilder {
             payer_token_a,
             payer_pool_lp,
             payer_token_b,
-        } = get_or_derive_initialize_pool_related_keys(pool_key, token_a_mint, token_b_mint, payer);
+        } = get_or_derive_initialize_pool_related_keys(
+            cluster,
+            pool_key,
+            token_a_mint,
+            token_b_mint,
+            payer,
+            vault_bases,
+        );
 
         let accounts =
             dynamic_amm::accounts::InitializeCustomizablePermissionlessConstantProductPool {
@@ -330,8 +370,8 @@ impl IxAccountBuilder {
                 payer_pool_lp,
                 payer_token_b,
                 payer,
-                vault_program: dynamic_vault::ID,
-                metadata_program: mpl_token_metadata::ID,
+                vault_program: cluster.vault_program_id,
+                metadata_program: cluster.metadata_program_id,
                 rent: solana_sdk::sysvar::rent::ID,
                 // Deprecated field
                 associated_token_program: anchor_spl::associated_token::ID,
@@ -341,4 +381,67 @@ impl IxAccountBuilder {
 
         accounts
     }
+
+    pub fn initialize_permissioned_pool_accounts(
+        cluster: &Cluster,
+        pool_key: Pubkey,
+        token_a_mint: Pubkey,
+        token_b_mint: Pubkey,
+        admin: Pubkey,
+        vault_bases: VaultBases,
+    ) -> dynamic_amm::accounts::InitializePermissionedPool {
+        let InitPoolRelatedKeys {
+            vault_a,
+            vault_a_lp_mint,
+            vault_a_lp,
+            vault_b,
+            vault_b_lp_mint,
+            vault_b_lp,
+            lp_mint,
+            protocol_token_a_fee,
+            protocol_token_b_fee,
+            mint_metadata,
+            payer_token_a,
+            payer_pool_lp,
+            payer_token_b,
+            ..
+        } = get_or_derive_initialize_pool_related_keys(
+            cluster,
+            pool_key,
+            token_a_mint,
+            token_b_mint,
+            admin,
+            vault_bases,
+        );
+
+        let accounts = dynamic_amm::accounts::InitializePermissionedPool {
+            pool: pool_key,
+            token_a_mint,
+            token_b_mint,
+            lp_mint,
+            a_vault: vault_a,
+            a_vault_lp: vault_a_lp,
+            a_vault_lp_mint: vault_a_lp_mint,
+            b_vault: vault_b,
+            b_vault_lp: vault_b_lp,
+            b_vault_lp_mint: vault_b_lp_mint,
+            protocol_token_a_fee,
+            protocol_token_b_fee,
+            mint_metadata,
+            admin_token_a: payer_token_a,
+            admin_pool_lp: payer_pool_lp,
+            admin_token_b: payer_token_b,
+            admin,
+            // Deprecated field
+            fee_owner: admin,
+            vault_program: cluster.vault_program_id,
+            metadata_program: cluster.metadata_program_id,
+            rent: solana_sdk::sysvar::rent::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: solana_sdk::system_program::ID,
+            token_program: anchor_spl::token::ID,
+        };
+
+        accounts
+    }
 }
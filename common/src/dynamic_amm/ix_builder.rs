@@ -0,0 +1,398 @@
+use crate::cluster::Cluster;
+use crate::dynamic_amm::pda::derive_lock_escrow_key;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use dynamic_amm::state::Pool;
+use dynamic_vault::state::Vault;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::get_remaining_accounts;
+
+/// Build dynamic amm instructions from the fetched pool, and its vaults.
+/// User token accounts are the associated token accounts of the user.
+pub struct PoolIxBuilder<'a> {
+    pub cluster: &'a Cluster,
+    pub pool_key: Pubkey,
+    pub pool: &'a Pool,
+    pub vault_a: &'a Vault,
+    pub vault_b: &'a Vault,
+}
+
+impl<'a> PoolIxBuilder<'a> {
+    pub fn new(
+        cluster: &'a Cluster,
+        pool_key: Pubkey,
+        pool: &'a Pool,
+        vault_a: &'a Vault,
+        vault_b: &'a Vault,
+    ) -> Self {
+        Self {
+            cluster,
+            pool_key,
+            pool,
+            vault_a,
+            vault_b,
+        }
+    }
+
+    fn build(
+        &self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let mut accounts = accounts.to_account_metas(None);
+        accounts.extend(remaining_accounts);
+
+        Instruction {
+            program_id: self.cluster.amm_program_id,
+            accounts,
+            data: data.data(),
+        }
+    }
+
+    /// Escrow vault, which hold the locked lp of the lock escrow
+    pub fn get_escrow_vault(&self, lock_escrow: Pubkey) -> Pubkey {
+        get_associated_token_address(&lock_escrow, &self.pool.lp_mint)
+    }
+
+    /// Swap `in_amount` of `in_token_mint`
+    pub fn swap(
+        &self,
+        user: Pubkey,
+        in_token_mint: Pubkey,
+        in_amount: u64,
+        minimum_out_amount: u64,
+    ) -> Instruction {
+        let (out_token_mint, protocol_token_fee) = if in_token_mint == self.pool.token_a_mint {
+            (self.pool.token_b_mint, self.pool.protocol_token_a_fee)
+        } else {
+            (self.pool.token_a_mint, self.pool.protocol_token_b_fee)
+        };
+
+        self.build(
+            dynamic_amm::accounts::Swap {
+                pool: self.pool_key,
+                user_source_token: get_associated_token_address(&user, &in_token_mint),
+                user_destination_token: get_associated_token_address(&user, &out_token_mint),
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_token_vault: self.vault_a.token_vault,
+                b_token_vault: self.vault_b.token_vault,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                protocol_token_fee,
+                user,
+                vault_program: self.cluster.vault_program_id,
+                token_program: anchor_spl::token::ID,
+            },
+            dynamic_amm::instruction::Swap {
+                in_amount,
+                minimum_out_amount,
+            },
+            get_remaining_accounts(self.pool),
+        )
+    }
+
+    fn add_or_remove_balance_liquidity_accounts(
+        &self,
+        user: Pubkey,
+    ) -> dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
+        dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
+            pool: self.pool_key,
+            lp_mint: self.pool.lp_mint,
+            user_pool_lp: get_associated_token_address(&user, &self.pool.lp_mint),
+            a_vault_lp: self.pool.a_vault_lp,
+            b_vault_lp: self.pool.b_vault_lp,
+            a_vault: self.pool.a_vault,
+            b_vault: self.pool.b_vault,
+            a_vault_lp_mint: self.vault_a.lp_mint,
+            b_vault_lp_mint: self.vault_b.lp_mint,
+            a_token_vault: self.vault_a.token_vault,
+            b_token_vault: self.vault_b.token_vault,
+            user_a_token: get_associated_token_address(&user, &self.pool.token_a_mint),
+            user_b_token: get_associated_token_address(&user, &self.pool.token_b_mint),
+            user,
+            vault_program: self.cluster.vault_program_id,
+            token_program: anchor_spl::token::ID,
+        }
+    }
+
+    /// Deposit both tokens in pool ratio, for `pool_token_amount` lp
+    pub fn add_balance_liquidity(
+        &self,
+        user: Pubkey,
+        pool_token_amount: u64,
+        maximum_token_a_amount: u64,
+        maximum_token_b_amount: u64,
+    ) -> Instruction {
+        self.build(
+            self.add_or_remove_balance_liquidity_accounts(user),
+            dynamic_amm::instruction::AddBalanceLiquidity {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            },
+            get_remaining_accounts(self.pool),
+        )
+    }
+
+    /// Withdraw both tokens in pool ratio, by burning `pool_token_amount` lp
+    pub fn remove_balance_liquidity(
+        &self,
+        user: Pubkey,
+        pool_token_amount: u64,
+        minimum_a_token_out: u64,
+        minimum_b_token_out: u64,
+    ) -> Instruction {
+        self.build(
+            self.add_or_remove_balance_liquidity_accounts(user),
+            dynamic_amm::instruction::RemoveBalanceLiquidity {
+                pool_token_amount,
+                minimum_a_token_out,
+                minimum_b_token_out,
+            },
+            get_remaining_accounts(self.pool),
+        )
+    }
+
+    /// Deposit tokens in any ratio. Only for stable swap pool.
+    pub fn add_imbalance_liquidity(
+        &self,
+        user: Pubkey,
+        minimum_pool_token_amount: u64,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        self.build(
+            self.add_or_remove_balance_liquidity_accounts(user),
+            dynamic_amm::instruction::AddImbalanceLiquidity {
+                minimum_pool_token_amount,
+                token_a_amount,
+                token_b_amount,
+            },
+            get_remaining_accounts(self.pool),
+        )
+    }
+
+    /// Withdraw only `out_token_mint`, by burning `pool_token_amount` lp. Only for stable swap pool.
+    pub fn remove_liquidity_single_side(
+        &self,
+        user: Pubkey,
+        out_token_mint: Pubkey,
+        pool_token_amount: u64,
+        minimum_out_amount: u64,
+    ) -> Instruction {
+        self.build(
+            dynamic_amm::accounts::RemoveLiquiditySingleSide {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                user_pool_lp: get_associated_token_address(&user, &self.pool.lp_mint),
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+                a_token_vault: self.vault_a.token_vault,
+                b_token_vault: self.vault_b.token_vault,
+                user_destination_token: get_associated_token_address(&user, &out_token_mint),
+                user,
+                vault_program: self.cluster.vault_program_id,
+                token_program: anchor_spl::token::ID,
+            },
+            dynamic_amm::instruction::RemoveLiquiditySingleSide {
+                pool_token_amount,
+                minimum_out_amount,
+            },
+            get_remaining_accounts(self.pool),
+        )
+    }
+
+    /// Bootstrap the pool when liquidity is depleted
+    pub fn bootstrap_liquidity(
+        &self,
+        user: Pubkey,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        self.build(
+            dynamic_amm::accounts::BootstrapLiquidity {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                user_pool_lp: get_associated_token_address(&user, &self.pool.lp_mint),
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+                a_token_vault: self.vault_a.token_vault,
+                b_token_vault: self.vault_b.token_vault,
+                user_a_token: get_associated_token_address(&user, &self.pool.token_a_mint),
+                user_b_token: get_associated_token_address(&user, &self.pool.token_b_mint),
+                user,
+                vault_program: self.cluster.vault_program_id,
+                token_program: anchor_spl::token::ID,
+            },
+            dynamic_amm::instruction::BootstrapLiquidity {
+                token_a_amount,
+                token_b_amount,
+            },
+            vec![],
+        )
+    }
+
+    /// Create lock escrow of `owner`
+    pub fn create_lock_escrow(&self, payer: Pubkey, owner: Pubkey) -> Instruction {
+        self.build(
+            dynamic_amm::accounts::CreateLockEscrow {
+                pool: self.pool_key,
+                lock_escrow: derive_lock_escrow_key(self.cluster, self.pool_key, owner),
+                owner,
+                lp_mint: self.pool.lp_mint,
+                payer,
+                system_program: solana_sdk::system_program::ID,
+            },
+            dynamic_amm::instruction::CreateLockEscrow {},
+            vec![],
+        )
+    }
+
+    /// Lock `amount` lp of `owner` to the lock escrow of `escrow_owner`
+    pub fn lock(&self, owner: Pubkey, escrow_owner: Pubkey, amount: u64) -> Instruction {
+        let lock_escrow = derive_lock_escrow_key(self.cluster, self.pool_key, escrow_owner);
+
+        self.build(
+            dynamic_amm::accounts::Lock {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                lock_escrow,
+                owner,
+                source_tokens: get_associated_token_address(&owner, &self.pool.lp_mint),
+                escrow_vault: self.get_escrow_vault(lock_escrow),
+                token_program: anchor_spl::token::ID,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+            },
+            dynamic_amm::instruction::Lock { amount },
+            vec![],
+        )
+    }
+
+    /// Create the lock escrow of `escrow_owner`, and its escrow vault, then lock `amount` lp of `owner` to it
+    pub fn create_lock_escrow_and_lock(
+        &self,
+        payer: Pubkey,
+        owner: Pubkey,
+        escrow_owner: Pubkey,
+        amount: u64,
+    ) -> Vec<Instruction> {
+        let lock_escrow = derive_lock_escrow_key(self.cluster, self.pool_key, escrow_owner);
+
+        vec![
+            self.create_lock_escrow(payer, escrow_owner),
+            create_associated_token_account_idempotent(
+                &payer,
+                &lock_escrow,
+                &self.pool.lp_mint,
+                &anchor_spl::token::ID,
+            ),
+            self.lock(owner, escrow_owner, amount),
+        ]
+    }
+
+    /// Claim up to `max_amount` fee lp from the lock escrow of `owner`, withdrawn as token A, and B
+    pub fn claim_fee(&self, owner: Pubkey, max_amount: u64) -> Instruction {
+        let lock_escrow = derive_lock_escrow_key(self.cluster, self.pool_key, owner);
+
+        self.build(
+            dynamic_amm::accounts::ClaimFee {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                lock_escrow,
+                owner,
+                source_tokens: get_associated_token_address(&owner, &self.pool.lp_mint),
+                escrow_vault: self.get_escrow_vault(lock_escrow),
+                token_program: anchor_spl::token::ID,
+                a_token_vault: self.vault_a.token_vault,
+                b_token_vault: self.vault_b.token_vault,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+                user_a_token: get_associated_token_address(&owner, &self.pool.token_a_mint),
+                user_b_token: get_associated_token_address(&owner, &self.pool.token_b_mint),
+                vault_program: self.cluster.vault_program_id,
+            },
+            dynamic_amm::instruction::ClaimFee { max_amount },
+            vec![],
+        )
+    }
+
+    /// Move up to `max_amount` locked lp from the lock escrow of `owner` to the lock escrow of `to_owner`
+    pub fn move_locked_lp(&self, owner: Pubkey, to_owner: Pubkey, max_amount: u64) -> Instruction {
+        let from_lock_escrow = derive_lock_escrow_key(self.cluster, self.pool_key, owner);
+        let to_lock_escrow = derive_lock_escrow_key(self.cluster, self.pool_key, to_owner);
+
+        self.build(
+            dynamic_amm::accounts::MoveLockedLp {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                from_lock_escrow,
+                to_lock_escrow,
+                owner,
+                from_escrow_vault: self.get_escrow_vault(from_lock_escrow),
+                to_escrow_vault: self.get_escrow_vault(to_lock_escrow),
+                token_program: anchor_spl::token::ID,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault_lp_mint: self.vault_a.lp_mint,
+                b_vault_lp_mint: self.vault_b.lp_mint,
+            },
+            dynamic_amm::instruction::MoveLockedLp { max_amount },
+            vec![],
+        )
+    }
+
+    /// Claim up to `max_amount_a`, and `max_amount_b` partner fee to the partner authority
+    pub fn partner_claim_fee(&self, max_amount_a: u64, max_amount_b: u64) -> Instruction {
+        let partner_authority = self.pool.partner_info.partner_authority;
+
+        self.build(
+            dynamic_amm::accounts::PartnerClaimFees {
+                pool: self.pool_key,
+                a_vault_lp: self.pool.a_vault_lp,
+                protocol_token_a_fee: self.pool.protocol_token_a_fee,
+                protocol_token_b_fee: self.pool.protocol_token_b_fee,
+                partner_token_a: get_associated_token_address(
+                    &partner_authority,
+                    &self.pool.token_a_mint,
+                ),
+                partner_token_b: get_associated_token_address(
+                    &partner_authority,
+                    &self.pool.token_b_mint,
+                ),
+                token_program: anchor_spl::token::ID,
+                partner_authority,
+            },
+            dynamic_amm::instruction::PartnerClaimFee {
+                max_amount_a,
+                max_amount_b,
+            },
+            vec![],
+        )
+    }
+}
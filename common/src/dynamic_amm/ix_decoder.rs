@@ -0,0 +1,295 @@
+use crate::cluster::Cluster;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{ensure, Context};
+use dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+use dynamic_amm::state::{CurveType, PoolFees};
+use solana_sdk::instruction::{CompiledInstruction, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+macro_rules! dynamic_amm_instructions {
+    ($(
+        $(#[$doc:meta])*
+        $variant:ident {
+            args: { $($arg:ident: $arg_ty:ty),* $(,)? },
+            accounts: { $($account:ident),* $(,)? } $(,)?
+        }
+    ),* $(,)?) => {
+        /// Decoded dynamic amm instruction. Accounts are in the order of the program instruction accounts.
+        /// Accounts passed after the instruction accounts, such as depeg pool stake accounts, are in `remaining_accounts`.
+        #[derive(Debug, Clone)]
+        pub enum DynamicAmmInstruction {
+            $(
+                $(#[$doc])*
+                $variant {
+                    $($arg: $arg_ty,)*
+                    $($account: Pubkey,)*
+                    remaining_accounts: Vec<Pubkey>,
+                },
+            )*
+            /// Instruction with discriminator not known by the decoder
+            Unknown {
+                discriminator: [u8; 8],
+                data: Vec<u8>,
+                accounts: Vec<Pubkey>,
+            },
+        }
+
+        impl DynamicAmmInstruction {
+            /// Name of the instruction variant
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => stringify!($variant),)*
+                    Self::Unknown { .. } => "Unknown",
+                }
+            }
+        }
+
+        fn decode(data: &[u8], accounts: &[Pubkey]) -> anyhow::Result<DynamicAmmInstruction> {
+            ensure!(data.len() >= 8, "Instruction data is shorter than discriminator");
+            let (discriminator, mut args) = data.split_at(8);
+
+            $(
+                if discriminator == dynamic_amm::instruction::$variant::DISCRIMINATOR {
+                    $(
+                        let $arg = <$arg_ty as AnchorDeserialize>::deserialize(&mut args)
+                            .context(concat!("Fail to decode ", stringify!($variant), " ", stringify!($arg)))?;
+                    )*
+                    let mut accounts = accounts.iter().copied();
+                    $(
+                        let $account = accounts
+                            .next()
+                            .context(concat!("Missing ", stringify!($variant), " account ", stringify!($account)))?;
+                    )*
+                    return Ok(DynamicAmmInstruction::$variant {
+                        $($arg,)*
+                        $($account,)*
+                        remaining_accounts: accounts.collect(),
+                    });
+                }
+            )*
+
+            Ok(DynamicAmmInstruction::Unknown {
+                discriminator: discriminator.try_into()?,
+                data: data.to_vec(),
+                accounts: accounts.to_vec(),
+            })
+        }
+    };
+}
+
+dynamic_amm_instructions! {
+    InitializePermissionlessPool {
+        args: { curve_type: CurveType, token_a_amount: u64, token_b_amount: u64 },
+        accounts: {
+            pool, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault, b_token_vault,
+            a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a, payer_token_b,
+            payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, fee_owner, rent,
+            mint_metadata, metadata_program, vault_program, token_program, associated_token_program,
+            system_program,
+        },
+    },
+    InitializeCustomizablePermissionlessConstantProductPool {
+        args: { token_a_amount: u64, token_b_amount: u64, params: CustomizableParams },
+        accounts: {
+            pool, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault, b_token_vault,
+            a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a, payer_token_b,
+            payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, rent, mint_metadata,
+            metadata_program, vault_program, token_program, associated_token_program, system_program,
+        },
+    },
+    InitializePermissionlessPoolWithFeeTier {
+        args: { curve_type: CurveType, trade_fee_bps: u64, token_a_amount: u64, token_b_amount: u64 },
+        accounts: {
+            pool, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault, b_token_vault,
+            a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a, payer_token_b,
+            payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, fee_owner, rent,
+            mint_metadata, metadata_program, vault_program, token_program, associated_token_program,
+            system_program,
+        },
+    },
+    PartnerClaimFee {
+        args: { max_amount_a: u64, max_amount_b: u64 },
+        accounts: {
+            pool, a_vault_lp, protocol_token_a_fee, protocol_token_b_fee, partner_token_a,
+            partner_token_b, token_program, partner_authority,
+        },
+    },
+    RemoveLiquiditySingleSide {
+        args: { pool_token_amount: u64, minimum_out_amount: u64 },
+        accounts: {
+            pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_token_vault, b_token_vault, user_destination_token, user,
+            vault_program, token_program,
+        },
+    },
+    AddImbalanceLiquidity {
+        args: { minimum_pool_token_amount: u64, token_a_amount: u64, token_b_amount: u64 },
+        accounts: {
+            pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_token_vault, b_token_vault, user_a_token, user_b_token, user,
+            vault_program, token_program,
+        },
+    },
+    Swap {
+        args: { in_amount: u64, minimum_out_amount: u64 },
+        accounts: {
+            pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault,
+            b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp,
+            protocol_token_fee, user, vault_program, token_program,
+        },
+    },
+    RemoveBalanceLiquidity {
+        args: { pool_token_amount: u64, minimum_a_token_out: u64, minimum_b_token_out: u64 },
+        accounts: {
+            pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_token_vault, b_token_vault, user_a_token, user_b_token, user,
+            vault_program, token_program,
+        },
+    },
+    AddBalanceLiquidity {
+        args: { pool_token_amount: u64, maximum_token_a_amount: u64, maximum_token_b_amount: u64 },
+        accounts: {
+            pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_token_vault, b_token_vault, user_a_token, user_b_token, user,
+            vault_program, token_program,
+        },
+    },
+    GetPoolInfo {
+        args: {},
+        accounts: {
+            pool, lp_mint, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint, b_vault_lp_mint,
+        },
+    },
+    BootstrapLiquidity {
+        args: { token_a_amount: u64, token_b_amount: u64 },
+        accounts: {
+            pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_token_vault, b_token_vault, user_a_token, user_b_token, user,
+            vault_program, token_program,
+        },
+    },
+    CreateLockEscrow {
+        args: {},
+        accounts: { pool, lock_escrow, owner, lp_mint, payer, system_program },
+    },
+    Lock {
+        args: { amount: u64 },
+        accounts: {
+            pool, lp_mint, lock_escrow, owner, source_tokens, escrow_vault, token_program, a_vault,
+            b_vault, a_vault_lp, b_vault_lp, a_vault_lp_mint, b_vault_lp_mint,
+        },
+    },
+    ClaimFee {
+        args: { max_amount: u64 },
+        accounts: {
+            pool, lp_mint, lock_escrow, owner, source_tokens, escrow_vault, token_program,
+            a_token_vault, b_token_vault, a_vault, b_vault, a_vault_lp, b_vault_lp, a_vault_lp_mint,
+            b_vault_lp_mint, user_a_token, user_b_token, vault_program,
+        },
+    },
+    InitializePermissionedPool {
+        args: { curve_type: CurveType },
+        accounts: {
+            pool, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_vault_lp_mint,
+            b_vault_lp_mint, a_vault_lp, b_vault_lp, admin_token_a, admin_token_b, admin_pool_lp,
+            protocol_token_a_fee, protocol_token_b_fee, admin, fee_owner, rent, mint_metadata,
+            metadata_program, vault_program, token_program, associated_token_program, system_program,
+        },
+    },
+    EnableOrDisablePool {
+        args: { enable: bool },
+        accounts: { pool, admin },
+    },
+    SetPoolFees {
+        args: { fees: PoolFees, new_partner_fee_numerator: u64 },
+        accounts: { pool, fee_operator },
+    },
+    OverrideCurveParam {
+        args: { curve_type: CurveType },
+        accounts: { pool, admin },
+    },
+    CreateMintMetadata {
+        args: {},
+        accounts: { pool, lp_mint, a_vault_lp, mint_metadata, metadata_program, system_program, payer },
+    },
+    CreateConfig {
+        args: { config_parameters: ConfigParameters },
+        accounts: { config, admin, system_program },
+    },
+    CloseConfig {
+        args: {},
+        accounts: { config, admin, rent_receiver },
+    },
+    UpdateActivationPoint {
+        args: { new_activation_point: u64 },
+        accounts: { pool, admin },
+    },
+    InitializePermissionlessConstantProductPoolWithConfig {
+        args: { token_a_amount: u64, token_b_amount: u64 },
+        accounts: {
+            pool, config, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault,
+            b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a,
+            payer_token_b, payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, rent,
+            mint_metadata, metadata_program, vault_program, token_program, associated_token_program,
+            system_program,
+        },
+    },
+    InitializePermissionlessConstantProductPoolWithConfig2 {
+        args: { token_a_amount: u64, token_b_amount: u64, activation_point: Option<u64> },
+        accounts: {
+            pool, config, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault,
+            b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a,
+            payer_token_b, payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, rent,
+            mint_metadata, metadata_program, vault_program, token_program, associated_token_program,
+            system_program,
+        },
+    },
+    MoveLockedLp {
+        args: { max_amount: u64 },
+        accounts: {
+            pool, lp_mint, from_lock_escrow, to_lock_escrow, owner, from_escrow_vault,
+            to_escrow_vault, token_program, a_vault, b_vault, a_vault_lp, b_vault_lp,
+            a_vault_lp_mint, b_vault_lp_mint,
+        },
+    },
+}
+
+/// Decode dynamic amm instruction. Unknown discriminator is decoded as `DynamicAmmInstruction::Unknown`.
+pub fn decode_instruction(
+    cluster: &Cluster,
+    instruction: &Instruction,
+) -> anyhow::Result<DynamicAmmInstruction> {
+    ensure!(
+        instruction.program_id == cluster.amm_program_id,
+        "Not a dynamic amm instruction"
+    );
+
+    let accounts: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+    decode(&instruction.data, &accounts)
+}
+
+/// Decode dynamic amm instruction of a transaction message.
+/// `account_keys` must include the addresses loaded from address lookup tables for versioned transaction.
+pub fn decode_compiled_instruction(
+    cluster: &Cluster,
+    instruction: &CompiledInstruction,
+    account_keys: &[Pubkey],
+) -> anyhow::Result<DynamicAmmInstruction> {
+    let program_id = account_keys
+        .get(usize::from(instruction.program_id_index))
+        .context("Program id index out of range")?;
+
+    ensure!(
+        *program_id == cluster.amm_program_id,
+        "Not a dynamic amm instruction"
+    );
+
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| account_keys.get(usize::from(*index)).copied())
+        .collect::<Option<Vec<Pubkey>>>()
+        .context("Account index out of range")?;
+
+    decode(&instruction.data, &accounts)
+}
@@ -0,0 +1,64 @@
+use anyhow::{bail, ensure, Context};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Share of the locked LP allocated to the lock escrow of the owner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockAllocation {
+    pub owner: Pubkey,
+    /// Relative to the sum of weight of all allocations
+    pub weight: u64,
+}
+
+impl FromStr for LockAllocation {
+    type Err = anyhow::Error;
+
+    /// Parse `<owner>:<weight>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((owner, weight)) = s.split_once(':') else {
+            bail!("Lock allocation must be <owner>:<weight>, got {}", s);
+        };
+
+        Ok(Self {
+            owner: Pubkey::from_str(owner)?,
+            weight: weight.parse()?,
+        })
+    }
+}
+
+/// Split the LP amount among the owners by their weight, rounded down. The last owner get the remaining LP.
+pub fn get_lock_amounts(
+    lp_amount: u64,
+    allocations: &[LockAllocation],
+) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+    let (last, rest) = allocations.split_last().context("No lock allocation")?;
+
+    let mut owners = HashSet::with_capacity(allocations.len());
+    for allocation in allocations {
+        ensure!(
+            owners.insert(allocation.owner),
+            "Duplicated owner {}",
+            allocation.owner
+        );
+    }
+
+    let sum_weight = allocations
+        .iter()
+        .try_fold(0u64, |sum, allocation| sum.checked_add(allocation.weight))
+        .context("Sum of weight overflow")?;
+    ensure!(sum_weight > 0, "Sum of weight is zero");
+
+    let mut lock_amounts = Vec::with_capacity(allocations.len());
+    let mut sum_amount = 0u64;
+    for allocation in rest {
+        let amount: u64 = (u128::from(lp_amount) * u128::from(allocation.weight)
+            / u128::from(sum_weight))
+        .try_into()?;
+        sum_amount += amount;
+        lock_amounts.push((allocation.owner, amount));
+    }
+    lock_amounts.push((last.owner, lp_amount - sum_amount));
+
+    Ok(lock_amounts)
+}
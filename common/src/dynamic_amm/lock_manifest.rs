@@ -0,0 +1,166 @@
+use super::lock_allocation::{get_lock_amounts, LockAllocation};
+use anyhow::{bail, ensure, Context};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+
+/// LP to lock for the recipient of the lock manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockManifestAmount {
+    /// Fixed LP amount
+    Amount(u64),
+    /// Share of the LP remaining after the fixed amounts, relative to the sum of shares
+    Share(u64),
+}
+
+/// Recipient of the lock manifest, which receive the locked LP in its lock escrow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockManifestEntry {
+    pub owner: Pubkey,
+    pub amount: LockManifestAmount,
+}
+
+impl LockManifestEntry {
+    fn new(owner: &str, amount: Option<u64>, share: Option<u64>) -> anyhow::Result<Self> {
+        let owner = Pubkey::from_str(owner).with_context(|| format!("Invalid owner {}", owner))?;
+        let amount = match (amount, share) {
+            (Some(amount), None) => LockManifestAmount::Amount(amount),
+            (None, Some(share)) => LockManifestAmount::Share(share),
+            _ => bail!("Owner {} must have either amount, or share", owner),
+        };
+        Ok(Self { owner, amount })
+    }
+}
+
+/// Parse CSV manifest with `owner`, and `amount` and/or `share` header. Each row set either amount, or share.
+pub fn parse_lock_manifest_csv(csv: &str) -> anyhow::Result<Vec<LockManifestEntry>> {
+    let mut lines = csv
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+
+    let (_, header) = lines.next().context("Empty lock manifest")?;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let column = |name: &str| columns.iter().position(|column| *column == name);
+    let owner_column = column("owner").context("Lock manifest must have owner column")?;
+    let amount_column = column("amount");
+    let share_column = column("share");
+    ensure!(
+        amount_column.is_some() || share_column.is_some(),
+        "Lock manifest must have amount, or share column"
+    );
+
+    let mut entries = vec![];
+    for (i, line) in lines {
+        let values: Vec<&str> = line.split(',').map(str::trim).collect();
+        let parse = |column: Option<usize>| -> anyhow::Result<Option<u64>> {
+            match column.and_then(|column| values.get(column)) {
+                Some(value) if !value.is_empty() => Ok(Some(value.parse()?)),
+                _ => Ok(None),
+            }
+        };
+        let owner = values.get(owner_column).copied().unwrap_or_default();
+        let entry = LockManifestEntry::new(owner, parse(amount_column)?, parse(share_column)?)
+            .with_context(|| format!("Invalid lock manifest line {}", i + 1))?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Parse JSON manifest, an array of `{ "owner": "<base58>", "amount": <u64> }`, or `{ "owner": "<base58>", "share": <u64> }`
+pub fn parse_lock_manifest_json(json: &str) -> anyhow::Result<Vec<LockManifestEntry>> {
+    let values: Vec<Value> = serde_json::from_str(json)?;
+
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let owner = value
+                .get("owner")
+                .and_then(Value::as_str)
+                .with_context(|| format!("Lock manifest entry {} must have owner", i))?;
+            let parse = |key: &str| match value.get(key) {
+                Some(value) => value
+                    .as_u64()
+                    .map(Some)
+                    .with_context(|| format!("Lock manifest entry {} {} must be u64", i, key)),
+                None => Ok(None),
+            };
+            LockManifestEntry::new(owner, parse("amount")?, parse("share")?)
+                .with_context(|| format!("Invalid lock manifest entry {}", i))
+        })
+        .collect()
+}
+
+/// Load the manifest. JSON when the file extension is `json`, otherwise CSV.
+pub fn load_lock_manifest_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<LockManifestEntry>> {
+    let path = path.as_ref();
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => parse_lock_manifest_json(&data),
+        _ => parse_lock_manifest_csv(&data),
+    }
+}
+
+/// LP amount to lock for each recipient, in manifest order. Fixed amounts are taken from `lp_amount` first, the remaining LP is split among the shares.
+/// When there is no share, the remaining LP is not locked.
+pub fn get_manifest_lock_amounts(
+    lp_amount: u64,
+    entries: &[LockManifestEntry],
+) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+    ensure!(!entries.is_empty(), "Empty lock manifest");
+
+    let mut owners = HashSet::with_capacity(entries.len());
+    for entry in entries {
+        ensure!(
+            owners.insert(entry.owner),
+            "Duplicated owner {}",
+            entry.owner
+        );
+    }
+
+    let fixed_amount = entries
+        .iter()
+        .try_fold(0u64, |sum, entry| match entry.amount {
+            LockManifestAmount::Amount(amount) => sum.checked_add(amount),
+            LockManifestAmount::Share(_) => Some(sum),
+        })
+        .context("Sum of amount overflow")?;
+    let remaining_amount = lp_amount.checked_sub(fixed_amount).with_context(|| {
+        format!(
+            "Sum of amount {} exceed the LP amount {}",
+            fixed_amount, lp_amount
+        )
+    })?;
+
+    let allocations: Vec<LockAllocation> = entries
+        .iter()
+        .filter_map(|entry| match entry.amount {
+            LockManifestAmount::Share(weight) => Some(LockAllocation {
+                owner: entry.owner,
+                weight,
+            }),
+            LockManifestAmount::Amount(_) => None,
+        })
+        .collect();
+    let mut share_amounts = if allocations.is_empty() {
+        vec![]
+    } else {
+        get_lock_amounts(remaining_amount, &allocations)?
+    }
+    .into_iter();
+
+    Ok(entries
+        .iter()
+        .map(|entry| match entry.amount {
+            LockManifestAmount::Amount(amount) => (entry.owner, amount),
+            // Unwrap is safe, share amounts are in the same order as the share entries
+            LockManifestAmount::Share(_) => share_amounts.next().unwrap(),
+        })
+        .collect())
+}
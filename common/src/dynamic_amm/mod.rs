@@ -1,5 +1,17 @@
+pub mod admin;
 pub mod aux_lp_mint;
+pub mod config;
+pub mod customizable_params;
+pub mod event_decoder;
+pub mod fee_tier;
 pub mod ix_account_builder;
+pub mod ix_builder;
+pub mod ix_decoder;
+pub mod lock_allocation;
+pub mod lock_manifest;
+pub mod partner;
 pub mod pda;
+pub mod stable_curve;
 
 pub use aux_lp_mint::*;
+pub use stable_curve::*;
@@ -0,0 +1,54 @@
+use crate::cluster::Cluster;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use dynamic_amm::state::Pool;
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+/// Offset of `Pool::partner_info::partner_authority`, including the discriminator
+pub const PARTNER_AUTHORITY_OFFSET: usize = 8 + 476;
+
+/// Fetch, and decode all pools of the partner authority, sorted by the pool key
+pub fn get_partner_pools(
+    cluster: &Cluster,
+    rpc_client: &RpcClient,
+    partner_authority: Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Pool)>> {
+    let accounts: Vec<(Pubkey, Account)> = rpc_client.get_program_accounts_with_config(
+        &cluster.amm_program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &Pool::discriminator())),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    PARTNER_AUTHORITY_OFFSET,
+                    partner_authority.as_ref(),
+                )),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut pools = vec![];
+    for (key, account) in accounts {
+        let pool = Pool::try_deserialize(&mut account.data.as_ref())?;
+        // Guard against layout change, as the memcmp filter is offset based
+        if pool.partner_info.partner_authority == partner_authority {
+            pools.push((key, pool));
+        }
+    }
+    pools.sort_by_key(|(key, _)| *key);
+
+    Ok(pools)
+}
+
+/// Pool has pending partner fee to claim
+pub fn has_pending_partner_fee(pool: &Pool) -> bool {
+    pool.partner_info.pending_fee_a > 0 || pool.partner_info.pending_fee_b > 0
+}
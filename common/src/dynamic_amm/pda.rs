@@ -1,13 +1,15 @@
 use dynamic_amm::{
-    constants::fee::MAX_BASIS_POINT,
-    state::{CurveType, PoolFees},
+    constants::{fee::MAX_BASIS_POINT, seeds::CONFIG_PREFIX},
+    state::{CurveType, Pool, PoolFees},
 };
 use solana_sdk::pubkey::Pubkey;
 
-use super::POOL_WITH_NON_PDA_BASED_LP_MINT;
+use crate::cluster::Cluster;
+use crate::pda_cache::find_program_address;
 
-pub const METAPLEX_PROGRAM_ID: Pubkey =
-    solana_sdk::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+use super::get_pool_lp_mint_registry;
+
+pub use crate::cluster::METAPLEX_PROGRAM_ID;
 
 /// get first key, this is same as max(key1, key2)
 fn get_first_key(key1: Pubkey, key2: Pubkey) -> Pubkey {
@@ -60,103 +62,250 @@ fn to_bps(numerator: u128, denominator: u128) -> Option<u64> {
     bps.try_into().ok()
 }
 
+fn get_permissionless_pool_seeds(
+    curve_type: CurveType,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+) -> Vec<Vec<u8>> {
+    vec![
+        get_curve_type(curve_type).to_le_bytes().to_vec(),
+        get_first_key(token_a_mint, token_b_mint)
+            .to_bytes()
+            .to_vec(),
+        get_second_key(token_a_mint, token_b_mint)
+            .to_bytes()
+            .to_vec(),
+    ]
+}
+
+fn get_permissionless_pool_with_fee_tier_seeds(
+    curve_type: CurveType,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    trade_fee_bps: u64,
+) -> Vec<Vec<u8>> {
+    let mut seeds = get_permissionless_pool_seeds(curve_type, token_a_mint, token_b_mint);
+    seeds.push(get_trade_fee_bps_bytes(trade_fee_bps));
+    seeds
+}
+
+fn get_customizable_permissionless_constant_product_pool_seeds(
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+) -> Vec<Vec<u8>> {
+    vec![
+        b"pool".to_vec(),
+        get_first_key(mint_a, mint_b).to_bytes().to_vec(),
+        get_second_key(mint_a, mint_b).to_bytes().to_vec(),
+    ]
+}
+
+fn get_permissionless_constant_product_pool_with_config_seeds(
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    config: Pubkey,
+) -> Vec<Vec<u8>> {
+    vec![
+        get_first_key(mint_a, mint_b).to_bytes().to_vec(),
+        get_second_key(mint_a, mint_b).to_bytes().to_vec(),
+        config.to_bytes().to_vec(),
+    ]
+}
+
+fn find_pool_address(cluster: &Cluster, seeds: &[Vec<u8>]) -> (Pubkey, u8) {
+    let seeds: Vec<&[u8]> = seeds.iter().map(|seed| seed.as_slice()).collect();
+    find_program_address(&seeds, &cluster.amm_program_id)
+}
+
 pub fn derive_permissionless_pool_key(
+    cluster: &Cluster,
     curve_type: CurveType,
     token_a_mint: Pubkey,
     token_b_mint: Pubkey,
 ) -> Pubkey {
-    let (pool, _bump) = Pubkey::find_program_address(
-        &[
-            &get_curve_type(curve_type).to_le_bytes(),
-            get_first_key(token_a_mint, token_b_mint).as_ref(),
-            get_second_key(token_a_mint, token_b_mint).as_ref(),
-        ],
-        &dynamic_amm::id(),
+    let (pool, _bump) = find_pool_address(
+        cluster,
+        &get_permissionless_pool_seeds(curve_type, token_a_mint, token_b_mint),
     );
     pool
 }
 
 pub fn derive_customizable_permissionless_constant_product_pool_key(
+    cluster: &Cluster,
     mint_a: Pubkey,
     mint_b: Pubkey,
 ) -> Pubkey {
-    Pubkey::find_program_address(
-        &[
-            b"pool",
-            get_first_key(mint_a, mint_b).as_ref(),
-            get_second_key(mint_a, mint_b).as_ref(),
-        ],
-        &dynamic_amm::ID,
+    find_pool_address(
+        cluster,
+        &get_customizable_permissionless_constant_product_pool_seeds(mint_a, mint_b),
     )
     .0
 }
 
-pub fn derive_protocol_fee_key(mint_key: Pubkey, pool_key: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(
+pub fn derive_protocol_fee_key(cluster: &Cluster, mint_key: Pubkey, pool_key: Pubkey) -> Pubkey {
+    find_program_address(
         &[b"fee", mint_key.as_ref(), pool_key.as_ref()],
-        &dynamic_amm::ID,
+        &cluster.amm_program_id,
     )
     .0
 }
 
-pub fn derive_metadata_key(lp_mint: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(
-        &[b"metadata", METAPLEX_PROGRAM_ID.as_ref(), lp_mint.as_ref()],
-        &METAPLEX_PROGRAM_ID,
+pub fn derive_metadata_key(cluster: &Cluster, lp_mint: Pubkey) -> Pubkey {
+    find_program_address(
+        &[
+            b"metadata",
+            cluster.metadata_program_id.as_ref(),
+            lp_mint.as_ref(),
+        ],
+        &cluster.metadata_program_id,
     )
     .0
 }
 
-pub fn derive_vault_lp_key(vault_key: Pubkey, pool_key: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(&[vault_key.as_ref(), pool_key.as_ref()], &dynamic_amm::ID).0
+pub fn derive_vault_lp_key(cluster: &Cluster, vault_key: Pubkey, pool_key: Pubkey) -> Pubkey {
+    find_program_address(
+        &[vault_key.as_ref(), pool_key.as_ref()],
+        &cluster.amm_program_id,
+    )
+    .0
 }
 
-pub fn derive_lp_mint_key(pool_key: Pubkey) -> Pubkey {
-    if let Some(lp_mint) = POOL_WITH_NON_PDA_BASED_LP_MINT.get(&pool_key) {
-        *lp_mint
+pub fn derive_lp_mint_key(cluster: &Cluster, pool_key: Pubkey) -> Pubkey {
+    if let Some(lp_mint) = get_pool_lp_mint_registry(cluster).get(&pool_key) {
+        lp_mint
     } else {
-        Pubkey::find_program_address(&[b"lp_mint", pool_key.as_ref()], &dynamic_amm::ID).0
+        find_program_address(&[b"lp_mint", pool_key.as_ref()], &cluster.amm_program_id).0
     }
 }
 
-pub fn derive_lock_escrow_key(pool_key: Pubkey, owner_key: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(
+pub fn derive_lock_escrow_key(cluster: &Cluster, pool_key: Pubkey, owner_key: Pubkey) -> Pubkey {
+    find_program_address(
         &[b"lock_escrow", pool_key.as_ref(), owner_key.as_ref()],
-        &dynamic_amm::ID,
+        &cluster.amm_program_id,
     )
     .0
 }
 
 pub fn derive_permissionless_constant_product_pool_with_config_key(
+    cluster: &Cluster,
     mint_a: Pubkey,
     mint_b: Pubkey,
     config: Pubkey,
 ) -> Pubkey {
-    Pubkey::find_program_address(
-        &[
-            get_first_key(mint_a, mint_b).as_ref(),
-            get_second_key(mint_a, mint_b).as_ref(),
-            config.as_ref(),
-        ],
-        &dynamic_amm::ID,
+    find_pool_address(
+        cluster,
+        &get_permissionless_constant_product_pool_with_config_seeds(mint_a, mint_b, config),
     )
     .0
 }
 
 pub fn derive_permissionless_pool_key_with_fee_tier(
+    cluster: &Cluster,
     curve_type: CurveType,
     token_a_mint: Pubkey,
     token_b_mint: Pubkey,
     trade_fee_bps: u64,
 ) -> Pubkey {
-    let (pool, _bump) = Pubkey::find_program_address(
-        &[
-            &get_curve_type(curve_type).to_le_bytes(),
-            get_first_key(token_a_mint, token_b_mint).as_ref(),
-            get_second_key(token_a_mint, token_b_mint).as_ref(),
-            get_trade_fee_bps_bytes(trade_fee_bps).as_ref(),
-        ],
-        &dynamic_amm::id(),
+    let (pool, _bump) = find_pool_address(
+        cluster,
+        &get_permissionless_pool_with_fee_tier_seeds(
+            curve_type,
+            token_a_mint,
+            token_b_mint,
+            trade_fee_bps,
+        ),
     );
     pool
 }
+
+pub fn derive_config_key(cluster: &Cluster, index: u64) -> Pubkey {
+    find_program_address(
+        &[CONFIG_PREFIX, index.to_le_bytes().as_ref()],
+        &cluster.amm_program_id,
+    )
+    .0
+}
+
+/// Derivation which created the pool address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolKeyDerivation {
+    /// `derive_permissionless_pool_key`
+    Permissionless,
+    /// `derive_permissionless_pool_key_with_fee_tier`
+    PermissionlessWithFeeTier { trade_fee_bps: u64 },
+    /// `derive_permissionless_constant_product_pool_with_config_key`
+    ConstantProductWithConfig { config: Pubkey },
+    /// `derive_customizable_permissionless_constant_product_pool_key`
+    CustomizableConstantProduct,
+}
+
+/// Matching derivation of the pool address, with its seeds, and bump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolKeyClassification {
+    pub derivation: PoolKeyDerivation,
+    pub seeds: Vec<Vec<u8>>,
+    pub bump: u8,
+}
+
+/// Identify which derivation created the pool address from the decoded pool.
+/// Curve type, and trade fee bps are read from the pool, and `config_keys` are the candidate configs of config based pool.
+/// Return None for non PDA pool, such as permissioned pool, or fee tier pool with the trade fee updated after creation.
+pub fn classify_pool_key(
+    cluster: &Cluster,
+    pool_key: Pubkey,
+    pool: &Pool,
+    config_keys: &[Pubkey],
+) -> Option<PoolKeyClassification> {
+    let token_a_mint = pool.token_a_mint;
+    let token_b_mint = pool.token_b_mint;
+
+    let mut candidates = vec![(
+        PoolKeyDerivation::Permissionless,
+        get_permissionless_pool_seeds(pool.curve_type, token_a_mint, token_b_mint),
+    )];
+
+    // Fee tier with default trade fee bps share the same seeds as permissionless pool
+    if let Some(trade_fee_bps) = to_bps(
+        pool.fees.trade_fee_numerator.into(),
+        pool.fees.trade_fee_denominator.into(),
+    ) {
+        if !get_trade_fee_bps_bytes(trade_fee_bps).is_empty() {
+            candidates.push((
+                PoolKeyDerivation::PermissionlessWithFeeTier { trade_fee_bps },
+                get_permissionless_pool_with_fee_tier_seeds(
+                    pool.curve_type,
+                    token_a_mint,
+                    token_b_mint,
+                    trade_fee_bps,
+                ),
+            ));
+        }
+    }
+
+    if let CurveType::ConstantProduct = pool.curve_type {
+        candidates.push((
+            PoolKeyDerivation::CustomizableConstantProduct,
+            get_customizable_permissionless_constant_product_pool_seeds(token_a_mint, token_b_mint),
+        ));
+
+        for config in config_keys {
+            candidates.push((
+                PoolKeyDerivation::ConstantProductWithConfig { config: *config },
+                get_permissionless_constant_product_pool_with_config_seeds(
+                    token_a_mint,
+                    token_b_mint,
+                    *config,
+                ),
+            ));
+        }
+    }
+
+    candidates.into_iter().find_map(|(derivation, seeds)| {
+        let (key, bump) = find_pool_address(cluster, &seeds);
+        (key == pool_key).then_some(PoolKeyClassification {
+            derivation,
+            seeds,
+            bump,
+        })
+    })
+}
@@ -0,0 +1,175 @@
+use anyhow::{bail, ensure, Context};
+use dynamic_amm::constants::stable_curve::MAX_AMP;
+use dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, TokenMultiplier};
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+
+/// Marinade state account, used by marinade depeg pool to fetch mSOL virtual price
+pub const MARINADE_STATE: Pubkey =
+    solana_sdk::pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC");
+/// Solido state account, used by lido depeg pool to fetch stSOL virtual price
+pub const SOLIDO_STATE: Pubkey =
+    solana_sdk::pubkey!("49Yi1TKkNyYjPAFdR9LBvoHcUjuPX4Df5T5yv39w2XTn");
+
+/// Depeg type from its index. 0 - None, 1 - Marinade, 2 - Lido, 3 - SplStake
+pub fn get_depeg_type(depeg_type: u8) -> anyhow::Result<DepegType> {
+    Ok(match depeg_type {
+        0 => DepegType::None,
+        1 => DepegType::Marinade,
+        2 => DepegType::Lido,
+        3 => DepegType::SplStake,
+        _ => bail!("Invalid depeg type {}", depeg_type),
+    })
+}
+
+/// Validate no depeg type, or stake account is given for constant product pool
+pub fn validate_constant_product_depeg(
+    depeg_type: DepegType,
+    stake: Option<Pubkey>,
+) -> anyhow::Result<()> {
+    ensure!(
+        depeg_type == DepegType::None && stake.is_none(),
+        "Depeg type, and stake pool are only for stable pool"
+    );
+    Ok(())
+}
+
+/// Remaining accounts required by depeg pool to fetch the virtual price of the staking token
+pub fn get_remaining_accounts(pool: &Pool) -> Vec<AccountMeta> {
+    let mut accounts = vec![];
+
+    if let CurveType::Stable { depeg, .. } = pool.curve_type {
+        match depeg.depeg_type {
+            DepegType::Marinade => accounts.push(AccountMeta::new_readonly(MARINADE_STATE, false)),
+            DepegType::Lido => accounts.push(AccountMeta::new_readonly(SOLIDO_STATE, false)),
+            _ => {}
+        }
+    }
+
+    if pool.stake != Pubkey::default() {
+        accounts.push(AccountMeta::new_readonly(pool.stake, false));
+    }
+
+    accounts
+}
+
+/// Compute token multiplier, which upscale both token amount to the highest decimal of the pool
+pub fn compute_token_multiplier(
+    token_a_decimals: u8,
+    token_b_decimals: u8,
+) -> Option<TokenMultiplier> {
+    let precision_factor = std::cmp::max(token_a_decimals, token_b_decimals);
+    let token_a_multiplier = 10u64.checked_pow((precision_factor - token_a_decimals).into())?;
+    let token_b_multiplier = 10u64.checked_pow((precision_factor - token_b_decimals).into())?;
+
+    Some(TokenMultiplier {
+        token_a_multiplier,
+        token_b_multiplier,
+        precision_factor,
+    })
+}
+
+/// Validated parameters of stable swap curve
+#[derive(Clone, Copy, Debug)]
+pub struct StableCurve {
+    amp: u64,
+    token_multiplier: TokenMultiplier,
+    depeg_type: DepegType,
+    stake: Option<Pubkey>,
+}
+
+impl StableCurve {
+    /// Build stable swap curve parameters from the token decimals.
+    /// Stake account is the SPL stake pool for SplStake depeg pool. Marinade, and Lido depeg pool use their state account.
+    pub fn new(
+        token_a_decimals: u8,
+        token_b_decimals: u8,
+        amp: u64,
+        depeg_type: DepegType,
+        stake: Option<Pubkey>,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            amp > 0 && amp <= MAX_AMP,
+            "Amp must be in range 1..={MAX_AMP}"
+        );
+
+        let token_multiplier = compute_token_multiplier(token_a_decimals, token_b_decimals)
+            .context("Token decimals difference is too large")?;
+
+        let stake = match depeg_type {
+            DepegType::None => {
+                ensure!(stake.is_none(), "Stake account is only for depeg pool");
+                None
+            }
+            DepegType::Marinade => {
+                ensure!(
+                    matches!(stake, None | Some(MARINADE_STATE)),
+                    "Marinade depeg pool must use marinade state account"
+                );
+                Some(MARINADE_STATE)
+            }
+            DepegType::Lido => {
+                ensure!(
+                    matches!(stake, None | Some(SOLIDO_STATE)),
+                    "Lido depeg pool must use solido state account"
+                );
+                Some(SOLIDO_STATE)
+            }
+            DepegType::SplStake => {
+                Some(stake.context("SPL stake depeg pool require stake pool account")?)
+            }
+        };
+
+        Ok(Self {
+            amp,
+            token_multiplier,
+            depeg_type,
+            stake,
+        })
+    }
+
+    pub fn amp(&self) -> u64 {
+        self.amp
+    }
+
+    pub fn token_multiplier(&self) -> TokenMultiplier {
+        self.token_multiplier
+    }
+
+    pub fn depeg_type(&self) -> DepegType {
+        self.depeg_type
+    }
+
+    /// Stake account of the depeg pool. None for non depeg pool.
+    pub fn stake(&self) -> Option<Pubkey> {
+        self.stake
+    }
+
+    /// Remaining accounts required to initialize the depeg pool virtual price
+    pub fn remaining_accounts(&self) -> Vec<AccountMeta> {
+        self.stake
+            .map(|stake| AccountMeta::new_readonly(stake, false))
+            .into_iter()
+            .collect()
+    }
+
+    pub fn curve_type(&self) -> CurveType {
+        CurveType::Stable {
+            amp: self.amp,
+            token_multiplier: self.token_multiplier,
+            depeg: Depeg {
+                // Will be initialized by the program
+                base_virtual_price: 0,
+                base_cache_updated: 0,
+                depeg_type: self.depeg_type,
+            },
+            last_amp_updated_timestamp: 0,
+        }
+    }
+}
+
+impl From<StableCurve> for CurveType {
+    fn from(value: StableCurve) -> Self {
+        value.curve_type()
+    }
+}
@@ -1,4 +1,9 @@
+use super::pda::derive_lp_mint_key;
+use crate::cluster::{Cluster, Network};
+use crate::lp_mint_registry::{verify_lp_mints, LpMintMismatch, LpMintRegistry};
+use dynamic_vault::state::Vault;
 use lazy_static::lazy_static;
+use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
@@ -73,56 +78,87 @@ lazy_static! {
     ]);
 }
 
-#[cfg(feature = "devnet")]
 lazy_static! {
-    static ref VAULT_WITH_NON_PDA_BASED_LP_MINT: HashMap<Pubkey, Pubkey> = HashMap::from_iter([
-        (
-            pubkey!("2u9ycJ7KEiWeR9vUhaHnohi5RdP2uLwuS1o8LynxhNBa"),
-            pubkey!("DDrvEcscZagpLE361HqpaiTiwyTtyNnWPhE8xKuqgXKY")
-        ),
-        (
-            pubkey!("sr5nfQgnAmn2bTkxmpPSQS1iEDGN4Bnk48xxcEAqUsi"),
-            pubkey!("3UhvDzg4dYtgE69QzjPaH94CoTJbLkczmYJWhq1P3MqC")
-        ),
-        (
-            pubkey!("G5qooe1TGxzsNCefw1xycto4SNy7H4Ad2AiPTCUJnM8W"),
-            pubkey!("C1XV8Wd4zdDAy3VTGd6GBJn3KYkSE8MwNyFrPQUEW9py")
-        ),
-        (
-            pubkey!("2FiYEM3EVtUNj6soptXZJdxjBjNWHtUUUKh79QaywYRg"),
-            pubkey!("Dq6j7SuMPhHh4eajA8WS1Nby9sbNynJfxyM3p7vxes9f")
-        ),
-        (
-            pubkey!("ATeQUJkKFRiWUfV76k5P5TfAyXwjWgBdck54z2sGvuNK"),
-            pubkey!("BgPb3pzLMmSwECCPjTHoKLYQR3iirXBw3bVgF8ZaR7sc")
-        ),
-        (
-            pubkey!("FERjPVNEa7Udq8CEv68h6tPL46Tq7ieE49HrE2wea3XT"),
-            pubkey!("BvoAjwEDhpLzs3jtu4H72j96ShKT5rvZE9RP1vgpfSM")
-        ),
-        (
-            pubkey!("8p1VKP45hhqq5iZG5fNGoi7ucme8nFLeChoDWNy7rWFm"),
-            pubkey!("8YE7s4oCbsEUzH71hVwe9DBCyemprwAjyDzksZ8d9bPz")
-        ),
-        (
-            pubkey!("4cX1amsBFy9by77uPuTbhN9Qw3oEaMu4J3pAyPa2gmku"),
-            pubkey!("2iGUnZPUPgjpjG6rT5Fi4VEeMoFw9DAwMJ8UFjXDpVs1")
-        ),
-        (
-            pubkey!("9Fze2yguDHYvX1KVfj1rgA9Q5moboWQFkw67wLGc61Z8"),
-            pubkey!("CNJoMWip1hX5mq2zHQ88LeC5gGMrVbGdQ9ZP6jB3qvkn")
-        ),
-        (
-            pubkey!("BPNKnFRAi9jfbD4xNAUavZmCbkn9DxGc1FCy4cYWHTXf"),
-            pubkey!("tewho86AFqTGmMvtKEvnNegHZfce4tTzDYENa58TLCq")
-        ),
-        (
-            pubkey!("DZwqzesnbNhoP5iPaxQkPG37JfDuqpZBfmsBw2wCpwQ1"),
-            pubkey!("GDK7uxgtQYYnwHwSXE83T6pxiJbKAV2jDMAa3bmc3Qzm")
-        ),
-        (
-            pubkey!("CyAd2PPVUCytnCiMztYFqu7v56Df3KiXdrx94rCyWeJz"),
-            pubkey!("HQU6SZNTTReKLXGyyPp9tt9tcBRo75yV9PgPMZavzXRG")
-        ),
-    ]);
+    pub static ref DEVNET_VAULT_WITH_NON_PDA_BASED_LP_MINT: HashMap<Pubkey, Pubkey> =
+        HashMap::from_iter([
+            (
+                pubkey!("2u9ycJ7KEiWeR9vUhaHnohi5RdP2uLwuS1o8LynxhNBa"),
+                pubkey!("DDrvEcscZagpLE361HqpaiTiwyTtyNnWPhE8xKuqgXKY")
+            ),
+            (
+                pubkey!("sr5nfQgnAmn2bTkxmpPSQS1iEDGN4Bnk48xxcEAqUsi"),
+                pubkey!("3UhvDzg4dYtgE69QzjPaH94CoTJbLkczmYJWhq1P3MqC")
+            ),
+            (
+                pubkey!("G5qooe1TGxzsNCefw1xycto4SNy7H4Ad2AiPTCUJnM8W"),
+                pubkey!("C1XV8Wd4zdDAy3VTGd6GBJn3KYkSE8MwNyFrPQUEW9py")
+            ),
+            (
+                pubkey!("2FiYEM3EVtUNj6soptXZJdxjBjNWHtUUUKh79QaywYRg"),
+                pubkey!("Dq6j7SuMPhHh4eajA8WS1Nby9sbNynJfxyM3p7vxes9f")
+            ),
+            (
+                pubkey!("ATeQUJkKFRiWUfV76k5P5TfAyXwjWgBdck54z2sGvuNK"),
+                pubkey!("BgPb3pzLMmSwECCPjTHoKLYQR3iirXBw3bVgF8ZaR7sc")
+            ),
+            (
+                pubkey!("FERjPVNEa7Udq8CEv68h6tPL46Tq7ieE49HrE2wea3XT"),
+                pubkey!("BvoAjwEDhpLzs3jtu4H72j96ShKT5rvZE9RP1vgpfSM")
+            ),
+            (
+                pubkey!("8p1VKP45hhqq5iZG5fNGoi7ucme8nFLeChoDWNy7rWFm"),
+                pubkey!("8YE7s4oCbsEUzH71hVwe9DBCyemprwAjyDzksZ8d9bPz")
+            ),
+            (
+                pubkey!("4cX1amsBFy9by77uPuTbhN9Qw3oEaMu4J3pAyPa2gmku"),
+                pubkey!("2iGUnZPUPgjpjG6rT5Fi4VEeMoFw9DAwMJ8UFjXDpVs1")
+            ),
+            (
+                pubkey!("9Fze2yguDHYvX1KVfj1rgA9Q5moboWQFkw67wLGc61Z8"),
+                pubkey!("CNJoMWip1hX5mq2zHQ88LeC5gGMrVbGdQ9ZP6jB3qvkn")
+            ),
+            (
+                pubkey!("BPNKnFRAi9jfbD4xNAUavZmCbkn9DxGc1FCy4cYWHTXf"),
+                pubkey!("tewho86AFqTGmMvtKEvnNegHZfce4tTzDYENa58TLCq")
+            ),
+            (
+                pubkey!("DZwqzesnbNhoP5iPaxQkPG37JfDuqpZBfmsBw2wCpwQ1"),
+                pubkey!("GDK7uxgtQYYnwHwSXE83T6pxiJbKAV2jDMAa3bmc3Qzm")
+            ),
+            (
+                pubkey!("CyAd2PPVUCytnCiMztYFqu7v56Df3KiXdrx94rCyWeJz"),
+                pubkey!("HQU6SZNTTReKLXGyyPp9tt9tcBRo75yV9PgPMZavzXRG")
+            ),
+        ]);
+}
+
+lazy_static! {
+    /// Built-in mainnet vaults with non PDA based lp mint, extendable at runtime
+    pub static ref VAULT_LP_MINT_REGISTRY: LpMintRegistry =
+        LpMintRegistry::new(VAULT_WITH_NON_PDA_BASED_LP_MINT.clone());
+    /// Built-in devnet vaults with non PDA based lp mint, extendable at runtime
+    pub static ref DEVNET_VAULT_LP_MINT_REGISTRY: LpMintRegistry =
+        LpMintRegistry::new(DEVNET_VAULT_WITH_NON_PDA_BASED_LP_MINT.clone());
+}
+
+/// Lp mint registry of the vaults on the network of the cluster
+pub fn get_vault_lp_mint_registry(cluster: &Cluster) -> &'static LpMintRegistry {
+    match cluster.network {
+        Network::Mainnet => &VAULT_LP_MINT_REGISTRY,
+        Network::Devnet => &DEVNET_VAULT_LP_MINT_REGISTRY,
+    }
+}
+
+/// Fetch the vaults, and report those which lp mint differ from `derive_lp_mint_key`
+pub fn verify_vault_lp_mints(
+    cluster: &Cluster,
+    rpc_client: &RpcClient,
+    vaults: &[Pubkey],
+) -> anyhow::Result<Vec<LpMintMismatch>> {
+    verify_lp_mints(
+        rpc_client,
+        vaults,
+        |state: &Vault| state.lp_mint,
+        |key| derive_lp_mint_key(cluster, key),
+    )
 }
@@ -1,4 +1,6 @@
 pub mod aux_lp_mint;
 pub mod pda;
+pub mod vault_base;
 
 pub use aux_lp_mint::*;
+pub use vault_base::*;
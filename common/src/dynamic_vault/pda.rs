@@ -1,38 +1,45 @@
-use super::VAULT_WITH_NON_PDA_BASED_LP_MINT;
+use super::{get_vault_lp_mint_registry, VaultBase};
+use crate::cluster::Cluster;
+use crate::pda_cache::find_program_address;
 use solana_sdk::pubkey::Pubkey;
 
-pub fn derive_vault_key(mint: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(
+pub fn derive_vault_key(cluster: &Cluster, mint: Pubkey) -> Pubkey {
+    derive_vault_key_with_base(cluster, mint, VaultBase::Default)
+}
+
+/// Derive vault key of the default, or idle vault
+pub fn derive_vault_key_with_base(cluster: &Cluster, mint: Pubkey, base: VaultBase) -> Pubkey {
+    find_program_address(
         &[
             dynamic_vault::seed::VAULT_PREFIX.as_ref(),
             mint.as_ref(),
-            dynamic_vault::get_base_address().as_ref(),
+            base.address().as_ref(),
         ],
-        &dynamic_vault::ID,
+        &cluster.vault_program_id,
     )
     .0
 }
 
-pub fn derive_token_vault_key(vault: Pubkey) -> Pubkey {
-    Pubkey::find_program_address(
+pub fn derive_token_vault_key(cluster: &Cluster, vault: Pubkey) -> Pubkey {
+    find_program_address(
         &[
             dynamic_vault::seed::TOKEN_VAULT_PREFIX.as_ref(),
             vault.as_ref(),
         ],
-        &dynamic_vault::ID,
+        &cluster.vault_program_id,
     )
     .0
 }
 
-pub fn derive_lp_mint_key(vault: Pubkey) -> Pubkey {
-    let non_derived_based_lp_mint = VAULT_WITH_NON_PDA_BASED_LP_MINT.get(&vault).cloned();
+pub fn derive_lp_mint_key(cluster: &Cluster, vault: Pubkey) -> Pubkey {
+    let non_derived_based_lp_mint = get_vault_lp_mint_registry(cluster).get(&vault);
 
     if let Some(lp_mint) = non_derived_based_lp_mint {
         lp_mint
     } else {
-        Pubkey::find_program_address(
+        find_program_address(
             &[dynamic_vault::seed::LP_MINT_PREFIX.as_ref(), vault.as_ref()],
-            &dynamic_vault::ID,
+            &cluster.vault_program_id,
         )
         .0
     }
@@ -0,0 +1,58 @@
+use super::pda::derive_vault_key_with_base;
+use crate::cluster::Cluster;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+/// Base address of the vault PDA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VaultBase {
+    /// Vault created with `dynamic_vault::get_base_address`
+    #[default]
+    Default,
+    /// Vault created with `dynamic_vault::get_base_address_for_idle_vault`
+    Idle,
+}
+
+impl VaultBase {
+    pub fn address(&self) -> Pubkey {
+        match self {
+            Self::Default => dynamic_vault::get_base_address(),
+            Self::Idle => dynamic_vault::get_base_address_for_idle_vault(),
+        }
+    }
+
+    pub fn from_address(base: Pubkey) -> Option<Self> {
+        [Self::Default, Self::Idle]
+            .into_iter()
+            .find(|vault_base| vault_base.address() == base)
+    }
+}
+
+/// Base of vault for token pair, used when creating pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VaultBases {
+    pub token_a: VaultBase,
+    pub token_b: VaultBase,
+}
+
+/// Find the existing vault of the mint. Default base vault is preferred when both exist.
+pub fn get_existing_vault(
+    cluster: &Cluster,
+    rpc_client: &RpcClient,
+    mint: Pubkey,
+) -> anyhow::Result<Option<(Pubkey, VaultBase)>> {
+    let candidates = [VaultBase::Default, VaultBase::Idle].map(|vault_base| {
+        (
+            derive_vault_key_with_base(cluster, mint, vault_base),
+            vault_base,
+        )
+    });
+    let keys = candidates.map(|(vault, _)| vault);
+
+    let accounts = rpc_client.get_multiple_accounts(&keys)?;
+
+    Ok(candidates
+        .into_iter()
+        .zip(accounts)
+        .find_map(|(candidate, account)| account.map(|_| candidate)))
+}
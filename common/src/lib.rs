@@ -1,2 +1,9 @@
+pub mod account_decoder;
+pub mod cluster;
 pub mod dynamic_amm;
 pub mod dynamic_vault;
+pub mod lp_mint_registry;
+pub mod pda_cache;
+
+/// Max number of accounts of getMultipleAccounts
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
@@ -0,0 +1,111 @@
+use crate::MAX_MULTIPLE_ACCOUNTS;
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Registry of pool, or vault which lp mint is not derived from PDA. Extendable at runtime.
+#[derive(Debug, Default)]
+pub struct LpMintRegistry {
+    lp_mints: RwLock<HashMap<Pubkey, Pubkey>>,
+}
+
+impl LpMintRegistry {
+    pub fn new(lp_mints: HashMap<Pubkey, Pubkey>) -> Self {
+        Self {
+            lp_mints: RwLock::new(lp_mints),
+        }
+    }
+
+    pub fn get(&self, key: &Pubkey) -> Option<Pubkey> {
+        self.lp_mints.read().unwrap().get(key).copied()
+    }
+
+    /// Add, or replace the lp mint of the keys
+    pub fn extend(&self, lp_mints: impl IntoIterator<Item = (Pubkey, Pubkey)>) {
+        self.lp_mints.write().unwrap().extend(lp_mints);
+    }
+
+    /// Add lp mints from JSON object of key to lp mint, in base58. Return number of loaded entries.
+    pub fn load_from_json_str(&self, json: &str) -> anyhow::Result<usize> {
+        let entries: HashMap<String, String> = serde_json::from_str(json)?;
+
+        let mut lp_mints = HashMap::with_capacity(entries.len());
+        for (key, lp_mint) in entries {
+            let key = Pubkey::from_str(&key).map_err(|e| anyhow!("Invalid key {key}: {e}"))?;
+            let lp_mint = Pubkey::from_str(&lp_mint)
+                .map_err(|e| anyhow!("Invalid lp mint {lp_mint}: {e}"))?;
+            lp_mints.insert(key, lp_mint);
+        }
+
+        let count = lp_mints.len();
+        self.extend(lp_mints);
+
+        Ok(count)
+    }
+
+    /// Add lp mints from JSON file. Refer `load_from_json_str` for the format.
+    pub fn load_from_json_file(&self, path: impl AsRef<Path>) -> anyhow::Result<usize> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        self.load_from_json_str(&json)
+    }
+
+    pub fn entries(&self) -> Vec<(Pubkey, Pubkey)> {
+        let mut entries: Vec<(Pubkey, Pubkey)> = self
+            .lp_mints
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(key, lp_mint)| (*key, *lp_mint))
+            .collect();
+        entries.sort();
+        entries
+    }
+}
+
+/// Lp mint stored in the account differ from the derived lp mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LpMintMismatch {
+    pub key: Pubkey,
+    pub lp_mint: Pubkey,
+    pub derived_lp_mint: Pubkey,
+}
+
+/// Fetch the accounts, and compare the stored lp mint against the derived lp mint
+pub(crate) fn verify_lp_mints<T: AccountDeserialize>(
+    rpc_client: &RpcClient,
+    keys: &[Pubkey],
+    get_lp_mint: impl Fn(&T) -> Pubkey,
+    derive_lp_mint: impl Fn(Pubkey) -> Pubkey,
+) -> anyhow::Result<Vec<LpMintMismatch>> {
+    let mut mismatches = vec![];
+
+    for keys in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc_client.get_multiple_accounts(keys)?;
+
+        for (key, account) in keys.iter().zip(accounts) {
+            let account = account.ok_or_else(|| anyhow!("Account {key} not found"))?;
+            let state = T::try_deserialize(&mut account.data.as_ref())
+                .with_context(|| format!("Failed to deserialize {key}"))?;
+
+            let lp_mint = get_lp_mint(&state);
+            let derived_lp_mint = derive_lp_mint(*key);
+
+            if lp_mint != derived_lp_mint {
+                mismatches.push(LpMintMismatch {
+                    key: *key,
+                    lp_mint,
+                    derived_lp_mint,
+                });
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
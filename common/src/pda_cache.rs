@@ -0,0 +1,172 @@
+use lazy_static::lazy_static;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+pub const DEFAULT_PDA_CACHE_CAPACITY: usize = 4096;
+
+/// Program id, seeds, and the bump supplied by the caller. Bump is None for the canonical bump searched by `find_program_address`,
+/// so addresses derived with an arbitrary bump never shadow the canonical address.
+type PdaCacheKey = (Pubkey, Vec<Vec<u8>>, Option<u8>);
+
+fn to_cache_key(seeds: &[&[u8]], program_id: &Pubkey, bump: Option<u8>) -> PdaCacheKey {
+    (
+        *program_id,
+        seeds.iter().map(|seed| seed.to_vec()).collect(),
+        bump,
+    )
+}
+
+#[derive(Debug, Default)]
+struct PdaCacheInner {
+    /// Increase on every access. Smallest tick is the least recently used entry.
+    tick: u64,
+    entries: HashMap<PdaCacheKey, ((Pubkey, u8), u64)>,
+    recency: BTreeMap<u64, PdaCacheKey>,
+}
+
+impl PdaCacheInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick = self.tick.wrapping_add(1);
+        self.tick
+    }
+
+    fn get(&mut self, key: &PdaCacheKey) -> Option<(Pubkey, u8)> {
+        let tick = self.next_tick();
+        let (address, last_tick) = self.entries.get_mut(key)?;
+        let last_tick = std::mem::replace(last_tick, tick);
+        let address = *address;
+
+        if let Some(key) = self.recency.remove(&last_tick) {
+            self.recency.insert(tick, key);
+        }
+
+        Some(address)
+    }
+
+    fn insert(&mut self, key: PdaCacheKey, address: (Pubkey, u8), capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+
+        let tick = self.next_tick();
+        if let Some((_, last_tick)) = self.entries.insert(key.clone(), (address, tick)) {
+            self.recency.remove(&last_tick);
+        }
+        self.recency.insert(tick, key);
+
+        while self.entries.len() > capacity {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Thread safe, bounded LRU cache of program derived address, and its bump
+#[derive(Debug)]
+pub struct PdaCache {
+    capacity: usize,
+    inner: Mutex<PdaCacheInner>,
+}
+
+impl PdaCache {
+    /// Cache with capacity of 0 never store any address
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(PdaCacheInner::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.clear();
+        inner.recency.clear();
+    }
+
+    /// Cached address of `find_program_address`, and its canonical bump
+    pub fn get(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+        self.inner
+            .lock()
+            .unwrap()
+            .get(&to_cache_key(seeds, program_id, None))
+    }
+
+    /// Store the address, and its canonical bump
+    pub fn insert(&self, seeds: &[&[u8]], program_id: &Pubkey, address: (Pubkey, u8)) {
+        self.inner.lock().unwrap().insert(
+            to_cache_key(seeds, program_id, None),
+            address,
+            self.capacity,
+        );
+    }
+
+    /// Same as `Pubkey::find_program_address`, but only search the bump on cache miss
+    pub fn find_program_address(&self, seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        if let Some(address) = self.get(seeds, program_id) {
+            return address;
+        }
+
+        // Lock is not held while searching, so other threads are not blocked. Concurrent miss of the same seeds only search twice.
+        let address = Pubkey::find_program_address(seeds, program_id);
+        self.insert(seeds, program_id, address);
+
+        address
+    }
+
+    /// Derive the address with a known bump, such as the bump stored in the account, which cost a single hash.
+    /// The bump is not verified to be canonical, so the address is cached apart from `find_program_address`.
+    pub fn create_program_address(
+        &self,
+        seeds: &[&[u8]],
+        bump: u8,
+        program_id: &Pubkey,
+    ) -> Option<Pubkey> {
+        let key = to_cache_key(seeds, program_id, Some(bump));
+        if let Some((address, _)) = self.inner.lock().unwrap().get(&key) {
+            return Some(address);
+        }
+
+        let bump_seed = [bump];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed);
+
+        let address = Pubkey::create_program_address(&seeds_with_bump, program_id).ok()?;
+        self.inner
+            .lock()
+            .unwrap()
+            .insert(key, (address, bump), self.capacity);
+
+        Some(address)
+    }
+}
+
+impl Default for PdaCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PDA_CACHE_CAPACITY)
+    }
+}
+
+lazy_static! {
+    /// Cache shared by every PDA derivation of the crate
+    pub static ref PDA_CACHE: PdaCache = PdaCache::default();
+}
+
+/// `Pubkey::find_program_address` through `PDA_CACHE`
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    PDA_CACHE.find_program_address(seeds, program_id)
+}
@@ -0,0 +1,209 @@
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_spl::token::spl_token;
+use common::account_decoder::{decode_account, DecodedAccount};
+use common::cluster::Cluster;
+use dynamic_amm::state::{Config, LockEscrow, Pool, PoolFees};
+use dynamic_vault::state::Vault;
+use solana_sdk::pubkey::Pubkey;
+
+fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn test_decode_pool() {
+    let cluster = Cluster::mainnet();
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    pool.lp_mint = Pubkey::new_unique();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.b_vault_lp = Pubkey::new_unique();
+    pool.a_vault_lp_bump = 254;
+    pool.enabled = true;
+    pool.fees.trade_fee_numerator = 250;
+    pool.fees.trade_fee_denominator = 100_000;
+    pool.total_locked_lp = 1_000;
+    pool.bootstrapping.activation_point = 123;
+
+    let account = decode_account(&cluster, &cluster.amm_program_id, &serialize(&pool)).unwrap();
+    let DecodedAccount::Pool(decoded) = &account else {
+        panic!("Must be decoded as pool, got {}", account.name());
+    };
+    assert_eq!(decoded.lp_mint, pool.lp_mint);
+    assert_eq!(decoded.token_a_mint, pool.token_a_mint);
+    assert_eq!(decoded.b_vault_lp, pool.b_vault_lp);
+    assert_eq!(decoded.a_vault_lp_bump, 254);
+    assert!(decoded.enabled);
+    assert_eq!(decoded.fees.trade_fee_numerator, 250);
+    assert_eq!(decoded.fees.trade_fee_denominator, 100_000);
+    assert_eq!(decoded.total_locked_lp, 1_000);
+    assert_eq!(decoded.bootstrapping.activation_point, 123);
+}
+
+#[test]
+fn test_decode_lock_escrow() {
+    let cluster = Cluster::mainnet();
+    let lock_escrow = LockEscrow {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        escrow_vault: Pubkey::new_unique(),
+        bump: 255,
+        total_locked_amount: 1_000,
+        lp_per_token: u128::MAX,
+        unclaimed_fee_pending: 10,
+        a_fee: 20,
+        b_fee: 30,
+    };
+
+    let account =
+        decode_account(&cluster, &cluster.amm_program_id, &serialize(&lock_escrow)).unwrap();
+    let DecodedAccount::LockEscrow(decoded) = &account else {
+        panic!("Must be decoded as lock escrow, got {}", account.name());
+    };
+    assert_eq!(decoded.pool, lock_escrow.pool);
+    assert_eq!(decoded.owner, lock_escrow.owner);
+    assert_eq!(decoded.escrow_vault, lock_escrow.escrow_vault);
+    assert_eq!(decoded.bump, 255);
+    assert_eq!(decoded.total_locked_amount, 1_000);
+    assert_eq!(decoded.lp_per_token, u128::MAX);
+    assert_eq!(decoded.unclaimed_fee_pending, 10);
+    assert_eq!(decoded.a_fee, 20);
+    assert_eq!(decoded.b_fee, 30);
+}
+
+#[test]
+fn test_decode_config() {
+    let cluster = Cluster::mainnet();
+    let config = Config {
+        pool_fees: PoolFees {
+            trade_fee_numerator: 2_500,
+            trade_fee_denominator: 100_000,
+            protocol_trade_fee_numerator: 20_000,
+            protocol_trade_fee_denominator: 100_000,
+        },
+        activation_duration: 3_600,
+        vault_config_key: Pubkey::new_unique(),
+        pool_creator_authority: Pubkey::new_unique(),
+        activation_type: 1,
+        partner_fee_numerator: 1_000,
+        _padding: [0; 219],
+    };
+
+    let account = decode_account(&cluster, &cluster.amm_program_id, &serialize(&config)).unwrap();
+    let DecodedAccount::Config(decoded) = &account else {
+        panic!("Must be decoded as config, got {}", account.name());
+    };
+    assert_eq!(decoded.pool_fees.trade_fee_numerator, 2_500);
+    assert_eq!(decoded.pool_fees.protocol_trade_fee_numerator, 20_000);
+    assert_eq!(decoded.activation_duration, 3_600);
+    assert_eq!(decoded.vault_config_key, config.vault_config_key);
+    assert_eq!(
+        decoded.pool_creator_authority,
+        config.pool_creator_authority
+    );
+    assert_eq!(decoded.activation_type, 1);
+    assert_eq!(decoded.partner_fee_numerator, 1_000);
+}
+
+#[test]
+fn test_decode_vault() {
+    let cluster = Cluster::mainnet();
+    let data = vec![0u8; 8 + std::mem::size_of::<Vault>()];
+    let mut vault = Vault::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    vault.enabled = 1;
+    vault.bumps.vault_bump = 253;
+    vault.bumps.token_vault_bump = 252;
+    vault.total_amount = 1_000_000;
+    vault.token_vault = Pubkey::new_unique();
+    vault.token_mint = Pubkey::new_unique();
+    vault.lp_mint = Pubkey::new_unique();
+    vault.strategies[1] = Pubkey::new_unique();
+    vault.base = dynamic_vault::get_base_address();
+    vault.locked_profit_tracker.last_report = 1_700_000_000;
+
+    let account = decode_account(&cluster, &cluster.vault_program_id, &serialize(&vault)).unwrap();
+    let DecodedAccount::Vault(decoded) = &account else {
+        panic!("Must be decoded as vault, got {}", account.name());
+    };
+    assert_eq!(decoded.enabled, 1);
+    assert_eq!(decoded.bumps.vault_bump, 253);
+    assert_eq!(decoded.bumps.token_vault_bump, 252);
+    assert_eq!(decoded.total_amount, 1_000_000);
+    assert_eq!(decoded.token_vault, vault.token_vault);
+    assert_eq!(decoded.token_mint, vault.token_mint);
+    assert_eq!(decoded.lp_mint, vault.lp_mint);
+    assert_eq!(decoded.strategies, vault.strategies);
+    assert_eq!(decoded.base, vault.base);
+    assert_eq!(decoded.locked_profit_tracker.last_report, 1_700_000_000);
+}
+
+#[test]
+fn test_decode_mint() {
+    let cluster = Cluster::mainnet();
+    let mint_authority = Pubkey::new_unique();
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(mint_authority),
+        supply: 1_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    let account = decode_account(&cluster, &spl_token::ID, &data).unwrap();
+    let DecodedAccount::Mint(decoded) = &account else {
+        panic!("Must be decoded as mint, got {}", account.name());
+    };
+    assert_eq!(decoded.mint_authority, COption::Some(mint_authority));
+    assert_eq!(decoded.supply, 1_000);
+    assert_eq!(decoded.decimals, 6);
+    assert_eq!(decoded.freeze_authority, COption::None);
+}
+
+#[test]
+fn test_decode_unknown_account() {
+    let cluster = Cluster::mainnet();
+    let lock_escrow_data = serialize(&LockEscrow {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        escrow_vault: Pubkey::new_unique(),
+        bump: 255,
+        total_locked_amount: 0,
+        lp_per_token: 0,
+        unclaimed_fee_pending: 0,
+        a_fee: 0,
+        b_fee: 0,
+    });
+
+    // Account is decoded by its owner
+    let owner = Pubkey::new_unique();
+    let account = decode_account(&cluster, &owner, &lock_escrow_data).unwrap();
+    let DecodedAccount::Unknown {
+        owner: decoded_owner,
+        data,
+    } = &account
+    else {
+        panic!("Must be decoded as unknown, got {}", account.name());
+    };
+    assert_eq!(*decoded_owner, owner);
+    assert_eq!(*data, lock_escrow_data);
+
+    // Unknown discriminator, and data shorter than discriminator
+    for data in [vec![1u8; 64], vec![1u8; 4]] {
+        let account = decode_account(&cluster, &cluster.amm_program_id, &data).unwrap();
+        assert_eq!(account.name(), "Unknown");
+    }
+
+    // Known discriminator with truncated data
+    assert!(decode_account(
+        &cluster,
+        &cluster.amm_program_id,
+        &lock_escrow_data[..lock_escrow_data.len() - 1]
+    )
+    .is_err());
+}
@@ -0,0 +1,171 @@
+use anchor_lang::AccountDeserialize;
+use common::cluster::Cluster;
+use common::dynamic_amm::admin::{
+    get_override_curve_type, validate_config_parameters, validate_pool_fees,
+    validate_update_activation_point,
+};
+use dynamic_amm::constants::fee::{
+    FEE_DENOMINATOR, MAX_BASIS_POINT, MAX_FEE_BPS, MAX_PARTNER_FEE_NUMERATOR,
+};
+use dynamic_amm::constants::stable_curve::MAX_AMP;
+use dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+use dynamic_amm::instructions::ConfigParameters;
+use dynamic_amm::state::{ActivationType, CurveType, Depeg, Pool, PoolFees, TokenMultiplier};
+use solana_sdk::pubkey::Pubkey;
+
+const MAX_TRADE_FEE_NUMERATOR: u64 = MAX_FEE_BPS * FEE_DENOMINATOR / MAX_BASIS_POINT;
+
+fn get_pool_fees(trade_fee_numerator: u64) -> PoolFees {
+    PoolFees {
+        trade_fee_numerator,
+        trade_fee_denominator: FEE_DENOMINATOR,
+        protocol_trade_fee_numerator: 20_000,
+        protocol_trade_fee_denominator: FEE_DENOMINATOR,
+    }
+}
+
+fn get_config_parameters(trade_fee_numerator: u64, partner_fee_numerator: u64) -> ConfigParameters {
+    ConfigParameters {
+        trade_fee_numerator,
+        protocol_trade_fee_numerator: 20_000,
+        activation_duration: 1_000,
+        pool_creator_authority: Pubkey::default(),
+        activation_type: ActivationType::Slot as u8,
+        index: 0,
+        partner_fee_numerator,
+    }
+}
+
+fn get_stable_curve(last_amp_updated_timestamp: u64) -> CurveType {
+    CurveType::Stable {
+        amp: 100,
+        token_multiplier: TokenMultiplier::default(),
+        depeg: Depeg::default(),
+        last_amp_updated_timestamp,
+    }
+}
+
+fn get_pool(activation_type: ActivationType, activation_point: u64) -> Pool {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    pool.bootstrapping.activation_type = activation_type as u8;
+    pool.bootstrapping.activation_point = activation_point;
+    pool
+}
+
+#[test]
+fn test_validate_pool_fees() {
+    assert!(validate_pool_fees(&get_pool_fees(MAX_TRADE_FEE_NUMERATOR), 0).is_ok());
+    assert!(validate_pool_fees(&get_pool_fees(MAX_TRADE_FEE_NUMERATOR + 1), 0).is_err());
+
+    assert!(validate_pool_fees(&get_pool_fees(250), MAX_PARTNER_FEE_NUMERATOR).is_ok());
+    assert!(validate_pool_fees(&get_pool_fees(250), MAX_PARTNER_FEE_NUMERATOR + 1).is_err());
+
+    // Protocol trade fee is a portion of the trade fee
+    let mut pool_fees = get_pool_fees(250);
+    pool_fees.protocol_trade_fee_numerator = FEE_DENOMINATOR + 1;
+    assert!(validate_pool_fees(&pool_fees, 0).is_err());
+
+    let mut pool_fees = get_pool_fees(250);
+    pool_fees.trade_fee_denominator = 0;
+    assert!(validate_pool_fees(&pool_fees, 0).is_err());
+}
+
+#[test]
+fn test_validate_config_parameters() {
+    let cluster = Cluster::mainnet();
+
+    let params = get_config_parameters(MAX_TRADE_FEE_NUMERATOR, MAX_PARTNER_FEE_NUMERATOR);
+    assert!(validate_config_parameters(&cluster, &params).is_ok());
+
+    let params = get_config_parameters(MAX_TRADE_FEE_NUMERATOR + 1, 0);
+    assert!(validate_config_parameters(&cluster, &params).is_err());
+
+    let params = get_config_parameters(250, MAX_PARTNER_FEE_NUMERATOR + 1);
+    assert!(validate_config_parameters(&cluster, &params).is_err());
+
+    let mut params = get_config_parameters(250, 0);
+    params.activation_duration = cluster.activation_buffers.max_activation_slot_duration;
+    assert!(validate_config_parameters(&cluster, &params).is_ok());
+    params.activation_duration += 1;
+    assert!(validate_config_parameters(&cluster, &params).is_err());
+
+    let mut params = get_config_parameters(250, 0);
+    params.activation_type = 2;
+    assert!(validate_config_parameters(&cluster, &params).is_err());
+}
+
+#[test]
+fn test_override_curve_type_amp() {
+    let curve_type = get_stable_curve(0);
+    let current_timestamp = MIN_CHANGE_AMP_DURATION;
+
+    assert!(get_override_curve_type(curve_type, 0, current_timestamp).is_err());
+    assert!(get_override_curve_type(curve_type, MAX_AMP + 1, current_timestamp).is_err());
+    assert!(get_override_curve_type(curve_type, 1, current_timestamp).is_ok());
+
+    let CurveType::Stable {
+        amp,
+        last_amp_updated_timestamp,
+        ..
+    } = get_override_curve_type(curve_type, MAX_AMP, current_timestamp).unwrap()
+    else {
+        panic!("Overridden curve must be stable");
+    };
+    assert_eq!(amp, MAX_AMP);
+    // Updated by the program
+    assert_eq!(last_amp_updated_timestamp, 0);
+}
+
+#[test]
+fn test_override_curve_type_min_change_amp_duration() {
+    let last_amp_updated_timestamp = 1_700_000_000;
+    let curve_type = get_stable_curve(last_amp_updated_timestamp);
+
+    assert!(get_override_curve_type(
+        curve_type,
+        200,
+        last_amp_updated_timestamp + MIN_CHANGE_AMP_DURATION - 1
+    )
+    .is_err());
+    assert!(get_override_curve_type(
+        curve_type,
+        200,
+        last_amp_updated_timestamp + MIN_CHANGE_AMP_DURATION
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_override_constant_product_curve() {
+    assert!(get_override_curve_type(CurveType::ConstantProduct, 100, u64::MAX).is_err());
+}
+
+#[test]
+fn test_validate_update_activation_point() {
+    let cluster = Cluster::mainnet();
+    let current_point = 1_000;
+    let pool = get_pool(ActivationType::Slot, current_point + 100);
+
+    assert!(
+        validate_update_activation_point(&cluster, &pool, current_point + 50, current_point)
+            .is_ok()
+    );
+    // New activation point is in the past
+    assert!(
+        validate_update_activation_point(&cluster, &pool, current_point - 1, current_point)
+            .is_err()
+    );
+
+    // Already activated
+    let pool = get_pool(ActivationType::Slot, current_point);
+    assert!(
+        validate_update_activation_point(&cluster, &pool, current_point + 50, current_point)
+            .is_err()
+    );
+    let pool = get_pool(ActivationType::Timestamp, current_point - 1);
+    assert!(
+        validate_update_activation_point(&cluster, &pool, current_point + 50, current_point)
+            .is_err()
+    );
+}
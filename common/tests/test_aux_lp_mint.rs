@@ -0,0 +1,66 @@
+use common::cluster::Cluster;
+use common::dynamic_amm::{
+    get_pool_lp_mint_registry, DEVNET_POOL_WITH_NON_PDA_BASED_LP_MINT,
+    POOL_WITH_NON_PDA_BASED_LP_MINT,
+};
+use common::dynamic_vault::{
+    get_vault_lp_mint_registry, DEVNET_VAULT_WITH_NON_PDA_BASED_LP_MINT,
+    VAULT_WITH_NON_PDA_BASED_LP_MINT,
+};
+use common::{dynamic_amm, dynamic_vault};
+use solana_sdk::pubkey;
+
+#[test]
+fn test_devnet_pool_lp_mint() {
+    let pool = pubkey!("GXy2cEDWFodXuXpEZZizVzcyiqF2QZCiMqfZX9BGx1vz");
+    let lp_mint = pubkey!("2nqgDcgfTzXJSckrVdqZGFpSfAAUY7NJKhCeikioaP5m");
+    assert_eq!(
+        DEVNET_POOL_WITH_NON_PDA_BASED_LP_MINT.get(&pool),
+        Some(&lp_mint)
+    );
+    assert!(!POOL_WITH_NON_PDA_BASED_LP_MINT.contains_key(&pool));
+
+    let devnet = Cluster::devnet();
+    assert_eq!(get_pool_lp_mint_registry(&devnet).get(&pool), Some(lp_mint));
+    assert_eq!(dynamic_amm::pda::derive_lp_mint_key(&devnet, pool), lp_mint);
+
+    // Mainnet derive the lp mint from PDA
+    let mainnet = Cluster::mainnet();
+    assert!(get_pool_lp_mint_registry(&mainnet).get(&pool).is_none());
+    assert_ne!(
+        dynamic_amm::pda::derive_lp_mint_key(&mainnet, pool),
+        lp_mint
+    );
+}
+
+#[test]
+fn test_devnet_vault_lp_mint() {
+    // wSOL vault has different lp mint on mainnet, and devnet
+    let vault = pubkey!("FERjPVNEa7Udq8CEv68h6tPL46Tq7ieE49HrE2wea3XT");
+    let mainnet_lp_mint = pubkey!("FZN7QZ8ZUUAxMPfxYEYkH3cXUASzH8EqA6B4tyCL8f1j");
+    let devnet_lp_mint = pubkey!("BvoAjwEDhpLzs3jtu4H72j96ShKT5rvZE9RP1vgpfSM");
+    assert_eq!(
+        VAULT_WITH_NON_PDA_BASED_LP_MINT.get(&vault),
+        Some(&mainnet_lp_mint)
+    );
+    assert_eq!(
+        DEVNET_VAULT_WITH_NON_PDA_BASED_LP_MINT.get(&vault),
+        Some(&devnet_lp_mint)
+    );
+
+    let devnet = Cluster::devnet();
+    assert_eq!(
+        get_vault_lp_mint_registry(&devnet).get(&vault),
+        Some(devnet_lp_mint)
+    );
+    assert_eq!(
+        dynamic_vault::pda::derive_lp_mint_key(&devnet, vault),
+        devnet_lp_mint
+    );
+    for cluster in [Cluster::mainnet(), Cluster::staging(), Cluster::localnet()] {
+        assert_eq!(
+            dynamic_vault::pda::derive_lp_mint_key(&cluster, vault),
+            mainnet_lp_mint
+        );
+    }
+}
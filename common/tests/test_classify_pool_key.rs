@@ -0,0 +1,149 @@
+use anchor_lang::AccountDeserialize;
+use common::cluster::Cluster;
+use common::dynamic_amm::pda::{
+    classify_pool_key, derive_customizable_permissionless_constant_product_pool_key,
+    derive_permissionless_constant_product_pool_with_config_key, derive_permissionless_pool_key,
+    derive_permissionless_pool_key_with_fee_tier, PoolKeyDerivation,
+};
+use dynamic_amm::state::{CurveType, Pool};
+use solana_sdk::pubkey::Pubkey;
+
+fn get_pool(curve_type: CurveType, token_a_mint: Pubkey, token_b_mint: Pubkey, bps: u64) -> Pool {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    pool.curve_type = curve_type;
+    pool.token_a_mint = token_a_mint;
+    pool.token_b_mint = token_b_mint;
+    pool.fees.trade_fee_numerator = bps * 10;
+    pool.fees.trade_fee_denominator = 100_000;
+    pool.fees.protocol_trade_fee_denominator = 100_000;
+    pool
+}
+
+fn get_stable_curve_type() -> CurveType {
+    CurveType::Stable {
+        amp: 100,
+        token_multiplier: Default::default(),
+        depeg: Default::default(),
+        last_amp_updated_timestamp: 0,
+    }
+}
+
+#[test]
+fn test_classify_permissionless_pool() {
+    let cluster = Cluster::mainnet();
+    let (token_a_mint, token_b_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for curve_type in [CurveType::ConstantProduct, get_stable_curve_type()] {
+        // Default trade fee of the fee tier pool share the permissionless pool address
+        let pool = get_pool(curve_type, token_a_mint, token_b_mint, 25);
+        let pool_key =
+            derive_permissionless_pool_key(&cluster, curve_type, token_a_mint, token_b_mint);
+        assert_eq!(
+            pool_key,
+            derive_permissionless_pool_key_with_fee_tier(
+                &cluster,
+                curve_type,
+                token_a_mint,
+                token_b_mint,
+                25
+            )
+        );
+
+        let classification = classify_pool_key(&cluster, pool_key, &pool, &[]).unwrap();
+        assert_eq!(classification.derivation, PoolKeyDerivation::Permissionless);
+
+        let seeds: Vec<&[u8]> = classification.seeds.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            Pubkey::find_program_address(&seeds, &cluster.amm_program_id),
+            (pool_key, classification.bump)
+        );
+    }
+}
+
+#[test]
+fn test_classify_fee_tier_pool() {
+    let cluster = Cluster::mainnet();
+    let (token_a_mint, token_b_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    for curve_type in [CurveType::ConstantProduct, get_stable_curve_type()] {
+        let pool = get_pool(curve_type, token_a_mint, token_b_mint, 30);
+        let pool_key = derive_permissionless_pool_key_with_fee_tier(
+            &cluster,
+            curve_type,
+            token_a_mint,
+            token_b_mint,
+            30,
+        );
+
+        let classification = classify_pool_key(&cluster, pool_key, &pool, &[]).unwrap();
+        assert_eq!(
+            classification.derivation,
+            PoolKeyDerivation::PermissionlessWithFeeTier { trade_fee_bps: 30 }
+        );
+
+        // Trade fee updated after the creation
+        let pool = get_pool(curve_type, token_a_mint, token_b_mint, 100);
+        assert!(classify_pool_key(&cluster, pool_key, &pool, &[]).is_none());
+    }
+}
+
+#[test]
+fn test_classify_constant_product_pool_with_config() {
+    let cluster = Cluster::mainnet();
+    let (token_a_mint, token_b_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config = Pubkey::new_unique();
+    let pool_key = derive_permissionless_constant_product_pool_with_config_key(
+        &cluster,
+        token_a_mint,
+        token_b_mint,
+        config,
+    );
+
+    let pool = get_pool(CurveType::ConstantProduct, token_a_mint, token_b_mint, 100);
+    let classification =
+        classify_pool_key(&cluster, pool_key, &pool, &[Pubkey::new_unique(), config]).unwrap();
+    assert_eq!(
+        classification.derivation,
+        PoolKeyDerivation::ConstantProductWithConfig { config }
+    );
+
+    // Config is not a candidate
+    assert!(classify_pool_key(&cluster, pool_key, &pool, &[Pubkey::new_unique()]).is_none());
+    // Only constant product pool is created with config
+    let pool = get_pool(get_stable_curve_type(), token_a_mint, token_b_mint, 100);
+    assert!(classify_pool_key(&cluster, pool_key, &pool, &[config]).is_none());
+}
+
+#[test]
+fn test_classify_customizable_pool() {
+    let cluster = Cluster::mainnet();
+    let (token_a_mint, token_b_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Mints are sorted in the seeds
+    let pool_key = derive_customizable_permissionless_constant_product_pool_key(
+        &cluster,
+        token_b_mint,
+        token_a_mint,
+    );
+    let pool = get_pool(CurveType::ConstantProduct, token_a_mint, token_b_mint, 100);
+    let classification = classify_pool_key(&cluster, pool_key, &pool, &[]).unwrap();
+    assert_eq!(
+        classification.derivation,
+        PoolKeyDerivation::CustomizableConstantProduct
+    );
+    assert_eq!(classification.seeds[0], b"pool".to_vec());
+}
+
+#[test]
+fn test_classify_non_pda_pool() {
+    let cluster = Cluster::mainnet();
+    let pool = get_pool(
+        CurveType::ConstantProduct,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        25,
+    );
+
+    assert!(classify_pool_key(&cluster, Pubkey::new_unique(), &pool, &[]).is_none());
+}
@@ -0,0 +1,24 @@
+use common::cluster::{ActivationBuffers, Cluster};
+use dynamic_amm::constants::activation::{
+    FIVE_MINUTES_SLOT_BUFFER, FIVE_MINUTES_TIME_BUFFER, MAX_ACTIVATION_SLOT_DURATION,
+    MAX_ACTIVATION_TIME_DURATION, SLOT_BUFFER, TIME_BUFFER,
+};
+
+#[test]
+fn test_deployed_activation_buffers_match_program() {
+    assert_eq!(
+        ActivationBuffers::deployed(),
+        ActivationBuffers {
+            slot_buffer: SLOT_BUFFER,
+            time_buffer: TIME_BUFFER,
+            max_activation_slot_duration: MAX_ACTIVATION_SLOT_DURATION,
+            max_activation_time_duration: MAX_ACTIVATION_TIME_DURATION,
+            five_minutes_slot_buffer: FIVE_MINUTES_SLOT_BUFFER,
+            five_minutes_time_buffer: FIVE_MINUTES_TIME_BUFFER,
+        }
+    );
+    assert_eq!(
+        Cluster::mainnet().activation_buffers,
+        ActivationBuffers::deployed()
+    );
+}
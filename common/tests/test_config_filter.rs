@@ -0,0 +1,93 @@
+use anchor_lang::AccountSerialize;
+use common::dynamic_amm::config::ConfigFilter;
+use dynamic_amm::state::{ActivationType, Config, PoolFees};
+use solana_rpc_client_api::filter::RpcFilterType;
+use solana_sdk::pubkey::Pubkey;
+
+fn get_config(pool_creator_authority: Pubkey, activation_type: ActivationType) -> Config {
+    Config {
+        pool_fees: PoolFees {
+            trade_fee_numerator: 250,
+            trade_fee_denominator: 100_000,
+            protocol_trade_fee_numerator: 20_000,
+            protocol_trade_fee_denominator: 100_000,
+        },
+        activation_duration: 1_000,
+        vault_config_key: Pubkey::new_unique(),
+        pool_creator_authority,
+        activation_type: activation_type as u8,
+        partner_fee_numerator: 0,
+        _padding: [0; 219],
+    }
+}
+
+/// Whether every memcmp filter match the serialized config
+fn rpc_filters_match(filter: &ConfigFilter, config: &Config) -> bool {
+    let mut data = vec![];
+    config.try_serialize(&mut data).unwrap();
+
+    filter.to_rpc_filters().iter().all(|filter| match filter {
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+        _ => panic!("Unexpected filter {:?}", filter),
+    })
+}
+
+#[test]
+fn test_rpc_filters_offset() {
+    let pool_creator_authority = Pubkey::new_unique();
+    let config = get_config(pool_creator_authority, ActivationType::Timestamp);
+
+    let filter = ConfigFilter {
+        pool_creator_authority: Some(pool_creator_authority),
+        activation_type: Some(ActivationType::Timestamp),
+        ..Default::default()
+    };
+    assert_eq!(filter.to_rpc_filters().len(), 3);
+    assert!(rpc_filters_match(&filter, &config));
+    assert!(filter.matches(&config));
+
+    let filter = ConfigFilter {
+        pool_creator_authority: Some(Pubkey::new_unique()),
+        ..Default::default()
+    };
+    assert!(!rpc_filters_match(&filter, &config));
+    assert!(!filter.matches(&config));
+
+    let filter = ConfigFilter {
+        activation_type: Some(ActivationType::Slot),
+        ..Default::default()
+    };
+    assert!(!rpc_filters_match(&filter, &config));
+    assert!(!filter.matches(&config));
+
+    // Config which anyone can use
+    let config = get_config(Pubkey::default(), ActivationType::Slot);
+    let filter = ConfigFilter {
+        pool_creator_authority: Some(Pubkey::default()),
+        activation_type: Some(ActivationType::Slot),
+        ..Default::default()
+    };
+    assert!(rpc_filters_match(&filter, &config));
+    assert!(filter.matches(&config));
+}
+
+#[test]
+fn test_fee_filter() {
+    let config = get_config(Pubkey::default(), ActivationType::Slot);
+
+    // Fee is not filtered by the RPC node
+    let filter = ConfigFilter {
+        trade_fee_bps: Some(25),
+        protocol_trade_fee_bps: Some(2_000),
+        ..Default::default()
+    };
+    assert_eq!(filter.to_rpc_filters().len(), 1);
+    assert!(filter.matches(&config));
+
+    let filter = ConfigFilter {
+        trade_fee_bps: Some(30),
+        ..Default::default()
+    };
+    assert!(rpc_filters_match(&filter, &config));
+    assert!(!filter.matches(&config));
+}
@@ -0,0 +1,180 @@
+use anchor_lang::__private::base64;
+use anchor_lang::{Discriminator, Event};
+use common::dynamic_amm::event_decoder::{parse_event_logs, DynamicAmmEvent};
+use dynamic_amm::event::{CloseConfig, PoolEnabled};
+use solana_sdk::pubkey::Pubkey;
+
+fn invoke_log(program_id: Pubkey, depth: usize) -> String {
+    format!("Program {} invoke [{}]", program_id, depth)
+}
+
+fn success_log(program_id: Pubkey) -> String {
+    format!("Program {} success", program_id)
+}
+
+fn failed_log(program_id: Pubkey) -> String {
+    format!("Program {} failed: custom program error: 0x1", program_id)
+}
+
+fn data_log(data: &[u8]) -> String {
+    format!("Program data: {}", base64::encode(data))
+}
+
+fn pool_enabled_log(pool: Pubkey) -> String {
+    data_log(
+        &PoolEnabled {
+            pool,
+            enabled: true,
+        }
+        .data(),
+    )
+}
+
+#[test]
+fn test_parse_event_logs_instruction_index() {
+    let program_id = Pubkey::new_unique();
+    let aggregator = Pubkey::new_unique();
+    let config = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    let logs = vec![
+        invoke_log(program_id, 1),
+        "Program log: Instruction: CloseConfig".to_string(),
+        data_log(&CloseConfig { config }.data()),
+        success_log(program_id),
+        // Invoked through CPI of the second instruction
+        invoke_log(aggregator, 1),
+        invoke_log(program_id, 2),
+        pool_enabled_log(pool),
+        success_log(program_id),
+        success_log(aggregator),
+    ];
+
+    let events = parse_event_logs(&logs, program_id);
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].instruction_index, 0);
+    assert_eq!(events[0].invoke_depth, 1);
+    assert_eq!(events[0].inner_instruction_position, 0);
+    assert_eq!(events[0].program_id, program_id);
+    let DynamicAmmEvent::CloseConfig(event) = &events[0].event else {
+        panic!("Unexpected event {}", events[0].event.name());
+    };
+    assert_eq!(event.config, config);
+
+    assert_eq!(events[1].instruction_index, 1);
+    assert_eq!(events[1].invoke_depth, 2);
+    // Invoked as the first inner instruction of the aggregator
+    assert_eq!(events[1].inner_instruction_position, 1);
+    let DynamicAmmEvent::PoolEnabled(event) = &events[1].event else {
+        panic!("Unexpected event {}", events[1].event.name());
+    };
+    assert_eq!(event.pool, pool);
+}
+
+#[test]
+fn test_parse_event_logs_failed_pop() {
+    let program_id = Pubkey::new_unique();
+    let aggregator = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    let logs = vec![
+        invoke_log(aggregator, 1),
+        invoke_log(program_id, 2),
+        failed_log(program_id),
+        // Logged by the aggregator after the failed CPI is popped
+        pool_enabled_log(pool),
+        invoke_log(program_id, 2),
+        pool_enabled_log(pool),
+        success_log(program_id),
+        success_log(aggregator),
+    ];
+
+    let events = parse_event_logs(&logs, program_id);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].instruction_index, 0);
+    assert_eq!(events[0].invoke_depth, 2);
+}
+
+#[test]
+fn test_parse_event_logs_ignore_other_program() {
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    let logs = vec![
+        invoke_log(other_program_id, 1),
+        pool_enabled_log(pool),
+        success_log(other_program_id),
+        invoke_log(program_id, 1),
+        // Data logged by the program invoked through CPI belong to the invoked program
+        invoke_log(other_program_id, 2),
+        pool_enabled_log(pool),
+        success_log(other_program_id),
+        pool_enabled_log(pool),
+        success_log(program_id),
+    ];
+
+    let events = parse_event_logs(&logs, program_id);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].instruction_index, 1);
+    assert_eq!(events[0].invoke_depth, 1);
+}
+
+#[test]
+fn test_parse_event_logs_skip_invalid_data() {
+    let program_id = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    // Known discriminator with truncated event data
+    let mut truncated_data = PoolEnabled::DISCRIMINATOR.to_vec();
+    truncated_data.extend_from_slice(&pool.to_bytes()[..16]);
+
+    let logs = vec![
+        invoke_log(program_id, 1),
+        data_log(&[1, 2, 3]),
+        data_log(&truncated_data),
+        "Program data: !!!!".to_string(),
+        pool_enabled_log(pool),
+        data_log(&[0; 16]),
+        success_log(program_id),
+    ];
+
+    let events = parse_event_logs(&logs, program_id);
+    assert_eq!(events.len(), 2);
+    assert!(matches!(events[0].event, DynamicAmmEvent::PoolEnabled(_)));
+    assert!(matches!(
+        events[1].event,
+        DynamicAmmEvent::Unknown { discriminator, .. } if discriminator == [0; 8]
+    ));
+}
+
+#[test]
+fn test_parse_event_logs_inner_instruction_position() {
+    let program_id = Pubkey::new_unique();
+    let aggregator = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    let logs = vec![
+        invoke_log(aggregator, 1),
+        invoke_log(program_id, 2),
+        pool_enabled_log(pool),
+        // Event CPI
+        invoke_log(program_id, 3),
+        success_log(program_id),
+        pool_enabled_log(pool),
+        success_log(program_id),
+        success_log(aggregator),
+        // Position is counted from the start of each transaction instruction
+        invoke_log(program_id, 1),
+        pool_enabled_log(pool),
+        success_log(program_id),
+    ];
+
+    let events = parse_event_logs(&logs, program_id);
+    let positions: Vec<(usize, usize)> = events
+        .iter()
+        .map(|event| (event.instruction_index, event.inner_instruction_position))
+        .collect();
+    assert_eq!(positions, vec![(0, 1), (0, 2), (1, 0)]);
+}
@@ -0,0 +1,25 @@
+use common::dynamic_amm::fee_tier::select_trade_fee_bps;
+
+const ALLOWED_TRADE_FEE_BPS: [u64; 4] = [25, 100, 400, 600];
+
+#[test]
+fn test_select_trade_fee_bps() {
+    for trade_fee_bps in ALLOWED_TRADE_FEE_BPS {
+        assert_eq!(
+            select_trade_fee_bps(&ALLOWED_TRADE_FEE_BPS, Some(trade_fee_bps)).unwrap(),
+            trade_fee_bps
+        );
+    }
+    assert!(select_trade_fee_bps(&ALLOWED_TRADE_FEE_BPS, Some(30)).is_err());
+}
+
+#[test]
+fn test_default_trade_fee_bps() {
+    assert_eq!(
+        select_trade_fee_bps(&ALLOWED_TRADE_FEE_BPS, None).unwrap(),
+        25
+    );
+    // Lowest, regardless of the order
+    assert_eq!(select_trade_fee_bps(&[100, 4, 10], None).unwrap(), 4);
+    assert!(select_trade_fee_bps(&[], None).is_err());
+}
@@ -0,0 +1,331 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use common::cluster::Cluster;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::pda::derive_lock_escrow_key;
+use common::dynamic_amm::SOLIDO_STATE;
+use dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, TokenMultiplier};
+use dynamic_vault::state::Vault;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+
+fn get_vault(token_mint: Pubkey) -> Vault {
+    let data = vec![0u8; 8 + std::mem::size_of::<Vault>()];
+    let mut vault = Vault::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    vault.token_mint = token_mint;
+    vault.token_vault = Pubkey::new_unique();
+    vault.lp_mint = Pubkey::new_unique();
+    vault
+}
+
+fn get_pool(curve_type: CurveType) -> Pool {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    pool.lp_mint = Pubkey::new_unique();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.a_vault = Pubkey::new_unique();
+    pool.b_vault = Pubkey::new_unique();
+    pool.a_vault_lp = Pubkey::new_unique();
+    pool.b_vault_lp = Pubkey::new_unique();
+    pool.protocol_token_a_fee = Pubkey::new_unique();
+    pool.protocol_token_b_fee = Pubkey::new_unique();
+    pool.curve_type = curve_type;
+    pool
+}
+
+fn get_lido_pool() -> Pool {
+    let mut pool = get_pool(CurveType::Stable {
+        amp: 100,
+        token_multiplier: TokenMultiplier::default(),
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: DepegType::Lido,
+        },
+        last_amp_updated_timestamp: 0,
+    });
+    pool.stake = Pubkey::new_unique();
+    pool
+}
+
+/// `AddOrRemoveBalanceLiquidity` accounts, followed by the depeg remaining accounts
+fn get_balance_liquidity_metas(
+    cluster: &Cluster,
+    pool_key: Pubkey,
+    pool: &Pool,
+    vault_a: &Vault,
+    vault_b: &Vault,
+    user: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(pool_key, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(get_associated_token_address(&user, &pool.lp_mint), false),
+        AccountMeta::new(pool.a_vault_lp, false),
+        AccountMeta::new(pool.b_vault_lp, false),
+        AccountMeta::new(pool.a_vault, false),
+        AccountMeta::new(pool.b_vault, false),
+        AccountMeta::new(vault_a.lp_mint, false),
+        AccountMeta::new(vault_b.lp_mint, false),
+        AccountMeta::new(vault_a.token_vault, false),
+        AccountMeta::new(vault_b.token_vault, false),
+        AccountMeta::new(
+            get_associated_token_address(&user, &pool.token_a_mint),
+            false,
+        ),
+        AccountMeta::new(
+            get_associated_token_address(&user, &pool.token_b_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(user, true),
+        AccountMeta::new_readonly(cluster.vault_program_id, false),
+        AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        AccountMeta::new_readonly(SOLIDO_STATE, false),
+        AccountMeta::new_readonly(pool.stake, false),
+    ]
+}
+
+#[test]
+fn test_swap_accounts() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_lido_pool();
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    let ix = ix_builder.swap(user, pool.token_a_mint, 1_000, 990);
+    assert_eq!(ix.program_id, cluster.amm_program_id);
+    assert_eq!(
+        ix.data,
+        dynamic_amm::instruction::Swap {
+            in_amount: 1_000,
+            minimum_out_amount: 990,
+        }
+        .data()
+    );
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(pool_key, false),
+            AccountMeta::new(
+                get_associated_token_address(&user, &pool.token_a_mint),
+                false
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&user, &pool.token_b_mint),
+                false
+            ),
+            AccountMeta::new(pool.a_vault, false),
+            AccountMeta::new(pool.b_vault, false),
+            AccountMeta::new(vault_a.token_vault, false),
+            AccountMeta::new(vault_b.token_vault, false),
+            AccountMeta::new(vault_a.lp_mint, false),
+            AccountMeta::new(vault_b.lp_mint, false),
+            AccountMeta::new(pool.a_vault_lp, false),
+            AccountMeta::new(pool.b_vault_lp, false),
+            // Fee is charged in the in token
+            AccountMeta::new(pool.protocol_token_a_fee, false),
+            AccountMeta::new_readonly(user, true),
+            AccountMeta::new_readonly(cluster.vault_program_id, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(SOLIDO_STATE, false),
+            AccountMeta::new_readonly(pool.stake, false),
+        ]
+    );
+
+    // Swap B to A
+    let ix = ix_builder.swap(user, pool.token_b_mint, 1_000, 990);
+    assert_eq!(
+        ix.accounts[1].pubkey,
+        get_associated_token_address(&user, &pool.token_b_mint)
+    );
+    assert_eq!(
+        ix.accounts[2].pubkey,
+        get_associated_token_address(&user, &pool.token_a_mint)
+    );
+    assert_eq!(ix.accounts[11].pubkey, pool.protocol_token_b_fee);
+}
+
+#[test]
+fn test_balance_liquidity_accounts() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_lido_pool();
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+    let expected_metas =
+        get_balance_liquidity_metas(&cluster, pool_key, &pool, &vault_a, &vault_b, user);
+
+    let ix = ix_builder.add_balance_liquidity(user, 100, 10, 20);
+    assert_eq!(ix.accounts, expected_metas);
+    assert_eq!(
+        ix.data,
+        dynamic_amm::instruction::AddBalanceLiquidity {
+            pool_token_amount: 100,
+            maximum_token_a_amount: 10,
+            maximum_token_b_amount: 20,
+        }
+        .data()
+    );
+
+    let ix = ix_builder.remove_balance_liquidity(user, 100, 10, 20);
+    assert_eq!(ix.accounts, expected_metas);
+    assert_eq!(
+        ix.data,
+        dynamic_amm::instruction::RemoveBalanceLiquidity {
+            pool_token_amount: 100,
+            minimum_a_token_out: 10,
+            minimum_b_token_out: 20,
+        }
+        .data()
+    );
+
+    let ix = ix_builder.add_imbalance_liquidity(user, 100, 10, 0);
+    assert_eq!(ix.accounts, expected_metas);
+    assert_eq!(
+        ix.data,
+        dynamic_amm::instruction::AddImbalanceLiquidity {
+            minimum_pool_token_amount: 100,
+            token_a_amount: 10,
+            token_b_amount: 0,
+        }
+        .data()
+    );
+}
+
+#[test]
+fn test_remove_liquidity_single_side_accounts() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_lido_pool();
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    let ix = ix_builder.remove_liquidity_single_side(user, pool.token_b_mint, 100, 10);
+
+    // Same as balance liquidity, with the out token account in place of user token A, and B
+    let mut expected_metas =
+        get_balance_liquidity_metas(&cluster, pool_key, &pool, &vault_a, &vault_b, user);
+    expected_metas.splice(
+        11..13,
+        [AccountMeta::new(
+            get_associated_token_address(&user, &pool.token_b_mint),
+            false,
+        )],
+    );
+    assert_eq!(ix.accounts, expected_metas);
+    assert_eq!(
+        ix.data,
+        dynamic_amm::instruction::RemoveLiquiditySingleSide {
+            pool_token_amount: 100,
+            minimum_out_amount: 10,
+        }
+        .data()
+    );
+}
+
+#[test]
+fn test_constant_product_without_remaining_accounts() {
+    let cluster = Cluster::mainnet();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, Pubkey::new_unique(), &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    assert_eq!(
+        ix_builder
+            .swap(user, pool.token_a_mint, 1, 0)
+            .accounts
+            .len(),
+        15
+    );
+    assert_eq!(
+        ix_builder
+            .add_balance_liquidity(user, 1, 1, 1)
+            .accounts
+            .len(),
+        16
+    );
+}
+
+#[test]
+fn test_create_lock_escrow_and_lock() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let payer = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let escrow_owner = Pubkey::new_unique();
+
+    let lock_escrow = derive_lock_escrow_key(&cluster, pool_key, escrow_owner);
+    let escrow_vault = get_associated_token_address(&lock_escrow, &pool.lp_mint);
+    assert_eq!(ix_builder.get_escrow_vault(lock_escrow), escrow_vault);
+
+    let ixs = ix_builder.create_lock_escrow_and_lock(payer, owner, escrow_owner, 500);
+    assert_eq!(ixs.len(), 3);
+
+    // Lock escrow is created for the escrow owner, paid by the payer
+    assert_eq!(ixs[0].program_id, cluster.amm_program_id);
+    assert_eq!(
+        ixs[0].accounts,
+        vec![
+            AccountMeta::new_readonly(pool_key, false),
+            AccountMeta::new(lock_escrow, false),
+            AccountMeta::new_readonly(escrow_owner, false),
+            AccountMeta::new_readonly(pool.lp_mint, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ]
+    );
+    assert_eq!(
+        ixs[0].data,
+        dynamic_amm::instruction::CreateLockEscrow {}.data()
+    );
+
+    // Escrow vault is created idempotently, so the sequence can be retried
+    assert_eq!(
+        ixs[1],
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer,
+            &lock_escrow,
+            &pool.lp_mint,
+            &anchor_spl::token::ID,
+        )
+    );
+
+    // LP is locked from the owner
+    assert_eq!(ixs[2].program_id, cluster.amm_program_id);
+    assert_eq!(
+        ixs[2].accounts,
+        vec![
+            AccountMeta::new(pool_key, false),
+            AccountMeta::new_readonly(pool.lp_mint, false),
+            AccountMeta::new(lock_escrow, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new(get_associated_token_address(&owner, &pool.lp_mint), false),
+            AccountMeta::new(escrow_vault, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(pool.a_vault, false),
+            AccountMeta::new_readonly(pool.b_vault, false),
+            AccountMeta::new_readonly(pool.a_vault_lp, false),
+            AccountMeta::new_readonly(pool.b_vault_lp, false),
+            AccountMeta::new_readonly(vault_a.lp_mint, false),
+            AccountMeta::new_readonly(vault_b.lp_mint, false),
+        ]
+    );
+    assert_eq!(
+        ixs[2].data,
+        dynamic_amm::instruction::Lock { amount: 500 }.data()
+    );
+}
@@ -0,0 +1,267 @@
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::cluster::Cluster;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::ix_decoder::{
+    decode_compiled_instruction, decode_instruction, DynamicAmmInstruction,
+};
+use common::dynamic_amm::pda::derive_lock_escrow_key;
+use common::dynamic_amm::MARINADE_STATE;
+use dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, TokenMultiplier};
+use dynamic_vault::state::Vault;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+
+fn get_vault(token_mint: Pubkey) -> Vault {
+    let data = vec![0u8; 8 + std::mem::size_of::<Vault>()];
+    let mut vault = Vault::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    vault.token_mint = token_mint;
+    vault.token_vault = Pubkey::new_unique();
+    vault.lp_mint = Pubkey::new_unique();
+    vault
+}
+
+fn get_pool(curve_type: CurveType) -> Pool {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    pool.lp_mint = Pubkey::new_unique();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.a_vault = Pubkey::new_unique();
+    pool.b_vault = Pubkey::new_unique();
+    pool.a_vault_lp = Pubkey::new_unique();
+    pool.b_vault_lp = Pubkey::new_unique();
+    pool.protocol_token_a_fee = Pubkey::new_unique();
+    pool.protocol_token_b_fee = Pubkey::new_unique();
+    pool.curve_type = curve_type;
+    pool
+}
+
+fn get_marinade_curve() -> CurveType {
+    CurveType::Stable {
+        amp: 100,
+        token_multiplier: TokenMultiplier::default(),
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: DepegType::Marinade,
+        },
+        last_amp_updated_timestamp: 0,
+    }
+}
+
+#[test]
+fn test_decode_swap() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    let ix = ix_builder.swap(user, pool.token_b_mint, 1_000, 990);
+    let DynamicAmmInstruction::Swap {
+        in_amount,
+        minimum_out_amount,
+        pool: decoded_pool,
+        user_source_token,
+        user_destination_token,
+        a_token_vault,
+        b_vault_lp_mint,
+        protocol_token_fee,
+        user: decoded_user,
+        vault_program,
+        remaining_accounts,
+        ..
+    } = decode_instruction(&cluster, &ix).unwrap()
+    else {
+        panic!("Must be decoded as swap");
+    };
+
+    assert_eq!(in_amount, 1_000);
+    assert_eq!(minimum_out_amount, 990);
+    assert_eq!(decoded_pool, pool_key);
+    assert_eq!(
+        user_source_token,
+        get_associated_token_address(&user, &pool.token_b_mint)
+    );
+    assert_eq!(
+        user_destination_token,
+        get_associated_token_address(&user, &pool.token_a_mint)
+    );
+    assert_eq!(a_token_vault, vault_a.token_vault);
+    assert_eq!(b_vault_lp_mint, vault_b.lp_mint);
+    assert_eq!(protocol_token_fee, pool.protocol_token_b_fee);
+    assert_eq!(decoded_user, user);
+    assert_eq!(vault_program, cluster.vault_program_id);
+    assert!(remaining_accounts.is_empty());
+}
+
+#[test]
+fn test_decode_remaining_accounts() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let mut pool = get_pool(get_marinade_curve());
+    pool.stake = Pubkey::new_unique();
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    let ix = ix_builder.add_imbalance_liquidity(user, 10, 100, 0);
+    let DynamicAmmInstruction::AddImbalanceLiquidity {
+        minimum_pool_token_amount,
+        token_a_amount,
+        token_b_amount,
+        user_pool_lp,
+        user_b_token,
+        remaining_accounts,
+        ..
+    } = decode_instruction(&cluster, &ix).unwrap()
+    else {
+        panic!("Must be decoded as add imbalance liquidity");
+    };
+
+    assert_eq!(minimum_pool_token_amount, 10);
+    assert_eq!(token_a_amount, 100);
+    assert_eq!(token_b_amount, 0);
+    assert_eq!(
+        user_pool_lp,
+        get_associated_token_address(&user, &pool.lp_mint)
+    );
+    assert_eq!(
+        user_b_token,
+        get_associated_token_address(&user, &pool.token_b_mint)
+    );
+    assert_eq!(remaining_accounts, vec![MARINADE_STATE, pool.stake]);
+}
+
+#[test]
+fn test_decode_lock() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let owner = Pubkey::new_unique();
+    let escrow_owner = Pubkey::new_unique();
+    let lock_escrow_key = derive_lock_escrow_key(&cluster, pool_key, escrow_owner);
+
+    let ix = ix_builder.lock(owner, escrow_owner, 500);
+    let DynamicAmmInstruction::Lock {
+        amount,
+        lock_escrow,
+        owner: decoded_owner,
+        source_tokens,
+        escrow_vault,
+        a_vault_lp,
+        ..
+    } = decode_instruction(&cluster, &ix).unwrap()
+    else {
+        panic!("Must be decoded as lock");
+    };
+
+    assert_eq!(amount, 500);
+    assert_eq!(lock_escrow, lock_escrow_key);
+    assert_eq!(decoded_owner, owner);
+    assert_eq!(
+        source_tokens,
+        get_associated_token_address(&owner, &pool.lp_mint)
+    );
+    assert_eq!(
+        escrow_vault,
+        get_associated_token_address(&lock_escrow_key, &pool.lp_mint)
+    );
+    assert_eq!(a_vault_lp, pool.a_vault_lp);
+}
+
+#[test]
+fn test_decode_compiled_instruction() {
+    let cluster = Cluster::mainnet();
+    let pool_key = Pubkey::new_unique();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, pool_key, &pool, &vault_a, &vault_b);
+    let user = Pubkey::new_unique();
+
+    let ix = ix_builder.remove_balance_liquidity(user, 1_000, 1, 2);
+    let message = Message::new(&[ix.clone()], Some(&user));
+
+    let decoded =
+        decode_compiled_instruction(&cluster, &message.instructions[0], &message.account_keys)
+            .unwrap();
+    assert_eq!(decoded.name(), "RemoveBalanceLiquidity");
+    assert_eq!(
+        format!("{:?}", decoded),
+        format!("{:?}", decode_instruction(&cluster, &ix).unwrap())
+    );
+
+    // Account index out of range
+    assert!(decode_compiled_instruction(
+        &cluster,
+        &message.instructions[0],
+        &message.account_keys[..message.account_keys.len() - 1]
+    )
+    .is_err());
+}
+
+#[test]
+fn test_decode_unknown() {
+    let cluster = Cluster::mainnet();
+    let accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect(),
+        data: data.clone(),
+    };
+
+    let DynamicAmmInstruction::Unknown {
+        discriminator,
+        data: decoded_data,
+        accounts: decoded_accounts,
+    } = decode_instruction(&cluster, &ix).unwrap()
+    else {
+        panic!("Must be decoded as unknown");
+    };
+    assert_eq!(discriminator, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(decoded_data, data);
+    assert_eq!(decoded_accounts, accounts);
+}
+
+#[test]
+fn test_decode_invalid_instruction() {
+    let cluster = Cluster::mainnet();
+    let pool = get_pool(CurveType::ConstantProduct);
+    let vault_a = get_vault(pool.token_a_mint);
+    let vault_b = get_vault(pool.token_b_mint);
+    let ix_builder = PoolIxBuilder::new(&cluster, Pubkey::new_unique(), &pool, &vault_a, &vault_b);
+    let ix = ix_builder.swap(Pubkey::new_unique(), pool.token_a_mint, 1_000, 0);
+
+    // Other program
+    let mut other_program_ix = ix.clone();
+    other_program_ix.program_id = Pubkey::new_unique();
+    assert!(decode_instruction(&cluster, &other_program_ix).is_err());
+
+    // Shorter than discriminator
+    let mut short_data_ix = ix.clone();
+    short_data_ix.data.truncate(7);
+    assert!(decode_instruction(&cluster, &short_data_ix).is_err());
+
+    // Truncated args
+    let mut truncated_args_ix = ix.clone();
+    truncated_args_ix.data.truncate(12);
+    assert!(decode_instruction(&cluster, &truncated_args_ix).is_err());
+
+    // Missing accounts
+    let mut missing_accounts_ix = ix;
+    missing_accounts_ix.accounts.pop();
+    assert!(decode_instruction(&cluster, &missing_accounts_ix).is_err());
+}
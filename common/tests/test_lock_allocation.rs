@@ -0,0 +1,87 @@
+use common::dynamic_amm::lock_allocation::{get_lock_amounts, LockAllocation};
+use solana_sdk::pubkey::Pubkey;
+
+fn get_allocation(owner: Pubkey, weight: u64) -> LockAllocation {
+    LockAllocation { owner, weight }
+}
+
+#[test]
+fn test_parse_lock_allocation() {
+    let owner = Pubkey::new_unique();
+    let allocation: LockAllocation = format!("{}:80", owner).parse().unwrap();
+    assert_eq!(allocation, get_allocation(owner, 80));
+
+    assert!("80".parse::<LockAllocation>().is_err());
+    assert!(format!("{}:-1", owner).parse::<LockAllocation>().is_err());
+    assert!("owner:80".parse::<LockAllocation>().is_err());
+}
+
+#[test]
+fn test_lock_amounts_rounding_remainder_to_last_owner() {
+    let (a, b, c) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let allocations = [
+        get_allocation(a, 1),
+        get_allocation(b, 1),
+        get_allocation(c, 1),
+    ];
+
+    // 1000 / 3 = 333 rounded down, the last owner get 1000 - 333 * 2
+    let lock_amounts = get_lock_amounts(1_000, &allocations).unwrap();
+    assert_eq!(lock_amounts, vec![(a, 333), (b, 333), (c, 334)]);
+
+    let lock_amounts = get_lock_amounts(2, &allocations).unwrap();
+    assert_eq!(lock_amounts, vec![(a, 0), (b, 0), (c, 2)]);
+}
+
+#[test]
+fn test_lock_amounts_by_weight() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Weights don't need to sum to 100
+    let allocations = [get_allocation(a, 3), get_allocation(b, 1)];
+    let lock_amounts = get_lock_amounts(1_001, &allocations).unwrap();
+    assert_eq!(lock_amounts, vec![(a, 750), (b, 251)]);
+
+    // Zero weight of the last owner still get the remainder
+    let allocations = [get_allocation(a, 3), get_allocation(b, 0)];
+    let lock_amounts = get_lock_amounts(1_001, &allocations).unwrap();
+    assert_eq!(lock_amounts, vec![(a, 1_001), (b, 0)]);
+
+    let allocations = [get_allocation(a, u64::MAX), get_allocation(b, 0)];
+    let lock_amounts = get_lock_amounts(u64::MAX, &allocations).unwrap();
+    assert_eq!(lock_amounts, vec![(a, u64::MAX), (b, 0)]);
+}
+
+#[test]
+fn test_lock_amounts_sum_to_lp_amount() {
+    let allocations: Vec<LockAllocation> = (1..=7)
+        .map(|weight| get_allocation(Pubkey::new_unique(), weight))
+        .collect();
+
+    for lp_amount in [0, 1, 27, 28, 1_000_003, u64::MAX] {
+        let lock_amounts = get_lock_amounts(lp_amount, &allocations).unwrap();
+        let sum: u128 = lock_amounts
+            .iter()
+            .map(|(_, amount)| u128::from(*amount))
+            .sum();
+        assert_eq!(sum, u128::from(lp_amount));
+    }
+}
+
+#[test]
+fn test_invalid_lock_allocations() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    assert!(get_lock_amounts(1_000, &[]).is_err());
+    assert!(get_lock_amounts(1_000, &[get_allocation(a, 0), get_allocation(b, 0)]).is_err());
+    assert!(get_lock_amounts(1_000, &[get_allocation(a, u64::MAX), get_allocation(b, 1)]).is_err());
+
+    let error = get_lock_amounts(1_000, &[get_allocation(a, 1), get_allocation(a, 1)])
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Duplicated owner"), "{}", error);
+}
@@ -0,0 +1,155 @@
+use common::dynamic_amm::lock_manifest::{
+    get_manifest_lock_amounts, parse_lock_manifest_csv, parse_lock_manifest_json,
+    LockManifestAmount, LockManifestEntry,
+};
+use solana_sdk::pubkey::Pubkey;
+
+fn get_entry(owner: Pubkey, amount: LockManifestAmount) -> LockManifestEntry {
+    LockManifestEntry { owner, amount }
+}
+
+#[test]
+fn test_parse_csv_with_blank_cells_and_lines() {
+    let (a, b, c) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let csv = format!("owner, amount, share\n{a}, 100,\n\n{b},, 1\n  {c} ,,3 \n");
+
+    let entries = parse_lock_manifest_csv(&csv).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            get_entry(a, LockManifestAmount::Amount(100)),
+            get_entry(b, LockManifestAmount::Share(1)),
+            get_entry(c, LockManifestAmount::Share(3)),
+        ]
+    );
+
+    // Missing trailing cell is blank
+    let csv = format!("owner,amount,share\n{a},,2\n{b},5\n");
+    let entries = parse_lock_manifest_csv(&csv).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            get_entry(a, LockManifestAmount::Share(2)),
+            get_entry(b, LockManifestAmount::Amount(5)),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_invalid_csv() {
+    let a = Pubkey::new_unique();
+
+    assert!(parse_lock_manifest_csv("").is_err());
+    assert!(parse_lock_manifest_csv(&format!("wallet,amount\n{a},1")).is_err());
+    assert!(parse_lock_manifest_csv(&format!("owner,weight\n{a},1")).is_err());
+    assert!(parse_lock_manifest_csv(&format!("owner,amount\n{a},x")).is_err());
+    // Neither, or both amount and share
+    assert!(parse_lock_manifest_csv(&format!("owner,amount,share\n{a},,")).is_err());
+    assert!(parse_lock_manifest_csv(&format!("owner,amount,share\n{a},1,1")).is_err());
+
+    let error = parse_lock_manifest_csv("owner,amount\n\nnot-a-pubkey,1").unwrap_err();
+    let error = format!("{:#}", error);
+    assert!(error.contains("line 3"), "{}", error);
+    assert!(error.contains("Invalid owner not-a-pubkey"), "{}", error);
+}
+
+#[test]
+fn test_parse_json() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let json = format!(r#"[{{ "owner": "{a}", "amount": 5 }}, {{ "owner": "{b}", "share": 7 }}]"#);
+
+    let entries = parse_lock_manifest_json(&json).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            get_entry(a, LockManifestAmount::Amount(5)),
+            get_entry(b, LockManifestAmount::Share(7)),
+        ]
+    );
+
+    assert!(parse_lock_manifest_json(&format!(r#"[{{ "owner": "{a}" }}]"#)).is_err());
+    assert!(parse_lock_manifest_json(&format!(r#"[{{ "owner": "{a}", "amount": -1 }}]"#)).is_err());
+    assert!(parse_lock_manifest_json(&format!(
+        r#"[{{ "owner": "{a}", "amount": 5, "share": 1 }}]"#
+    ))
+    .is_err());
+    assert!(parse_lock_manifest_json(r#"[{ "amount": 5 }]"#).is_err());
+}
+
+#[test]
+fn test_mixed_amount_and_share() {
+    let (a, b, c) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let entries = [
+        get_entry(b, LockManifestAmount::Share(1)),
+        get_entry(a, LockManifestAmount::Amount(100)),
+        get_entry(c, LockManifestAmount::Share(3)),
+    ];
+
+    // 1001 remaining after the amount, split 1:3 with the remainder to the last share
+    let lock_amounts = get_manifest_lock_amounts(1_101, &entries).unwrap();
+    assert_eq!(lock_amounts, vec![(b, 250), (a, 100), (c, 751)]);
+
+    // Nothing remaining for the shares
+    let lock_amounts = get_manifest_lock_amounts(100, &entries).unwrap();
+    assert_eq!(lock_amounts, vec![(b, 0), (a, 100), (c, 0)]);
+}
+
+#[test]
+fn test_sum_of_amount_exceed_lp_amount() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let entries = [
+        get_entry(a, LockManifestAmount::Amount(60)),
+        get_entry(b, LockManifestAmount::Amount(41)),
+    ];
+
+    assert_eq!(
+        get_manifest_lock_amounts(101, &entries).unwrap(),
+        vec![(a, 60), (b, 41)]
+    );
+    let error = get_manifest_lock_amounts(100, &entries)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("exceed the LP amount"), "{}", error);
+
+    let entries = [
+        get_entry(a, LockManifestAmount::Amount(u64::MAX)),
+        get_entry(b, LockManifestAmount::Amount(1)),
+    ];
+    assert!(get_manifest_lock_amounts(u64::MAX, &entries).is_err());
+}
+
+#[test]
+fn test_duplicated_owner() {
+    let a = Pubkey::new_unique();
+    let entries = [
+        get_entry(a, LockManifestAmount::Amount(1)),
+        get_entry(a, LockManifestAmount::Share(1)),
+    ];
+
+    let error = get_manifest_lock_amounts(100, &entries)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Duplicated owner"), "{}", error);
+}
+
+#[test]
+fn test_remaining_not_locked_without_share() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let entries = [
+        get_entry(a, LockManifestAmount::Amount(10)),
+        get_entry(b, LockManifestAmount::Amount(20)),
+    ];
+
+    let lock_amounts = get_manifest_lock_amounts(1_000, &entries).unwrap();
+    assert_eq!(lock_amounts, vec![(a, 10), (b, 20)]);
+
+    assert!(get_manifest_lock_amounts(1_000, &[]).is_err());
+}
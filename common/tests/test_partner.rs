@@ -0,0 +1,48 @@
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use common::dynamic_amm::partner::{has_pending_partner_fee, PARTNER_AUTHORITY_OFFSET};
+use dynamic_amm::state::Pool;
+use solana_rpc_client_api::filter::Memcmp;
+use solana_sdk::pubkey::Pubkey;
+
+fn get_pool() -> Pool {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+}
+
+#[test]
+fn test_partner_authority_offset() {
+    let partner_authority = Pubkey::new_unique();
+    let mut pool = get_pool();
+    pool.partner_info.partner_authority = partner_authority;
+    pool.partner_info.fee_numerator = u64::MAX;
+    pool.total_locked_lp = u64::MAX;
+
+    let mut data = vec![];
+    pool.try_serialize(&mut data).unwrap();
+
+    assert_eq!(
+        &data[PARTNER_AUTHORITY_OFFSET..PARTNER_AUTHORITY_OFFSET + 32],
+        partner_authority.as_ref()
+    );
+    assert!(
+        Memcmp::new_base58_encoded(PARTNER_AUTHORITY_OFFSET, partner_authority.as_ref())
+            .bytes_match(&data)
+    );
+    assert!(
+        !Memcmp::new_base58_encoded(PARTNER_AUTHORITY_OFFSET, Pubkey::new_unique().as_ref())
+            .bytes_match(&data)
+    );
+}
+
+#[test]
+fn test_has_pending_partner_fee() {
+    let mut pool = get_pool();
+    assert!(!has_pending_partner_fee(&pool));
+
+    pool.partner_info.pending_fee_b = 1;
+    assert!(has_pending_partner_fee(&pool));
+
+    pool.partner_info.pending_fee_a = 1;
+    pool.partner_info.pending_fee_b = 0;
+    assert!(has_pending_partner_fee(&pool));
+}
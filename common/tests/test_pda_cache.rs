@@ -0,0 +1,147 @@
+use common::cluster::Cluster;
+use common::dynamic_vault::pda::{derive_token_vault_key, derive_vault_key};
+use common::pda_cache::PdaCache;
+use dynamic_vault::seed::{TOKEN_VAULT_PREFIX, VAULT_PREFIX};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_find_program_address_match_uncached() {
+    let cache = PdaCache::new(8);
+    let program_id = Pubkey::new_unique();
+    let seeds: &[&[u8]] = &[b"seed", &[1, 2, 3]];
+
+    let expected = Pubkey::find_program_address(seeds, &program_id);
+    assert_eq!(cache.find_program_address(seeds, &program_id), expected);
+    assert_eq!(cache.get(seeds, &program_id), Some(expected));
+    // Cache hit
+    assert_eq!(cache.find_program_address(seeds, &program_id), expected);
+    assert_eq!(cache.len(), 1);
+
+    // Same seeds of another program is another entry
+    let other_program_id = Pubkey::new_unique();
+    assert_eq!(
+        cache.find_program_address(seeds, &other_program_id),
+        Pubkey::find_program_address(seeds, &other_program_id)
+    );
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_evict_least_recently_used() {
+    let cache = PdaCache::new(2);
+    let program_id = Pubkey::new_unique();
+
+    cache.find_program_address(&[b"a"], &program_id);
+    cache.find_program_address(&[b"b"], &program_id);
+    // Access "a", so "b" is the least recently used
+    assert!(cache.get(&[b"a"], &program_id).is_some());
+
+    cache.find_program_address(&[b"c"], &program_id);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&[b"a"], &program_id).is_some());
+    assert!(cache.get(&[b"b"], &program_id).is_none());
+    assert!(cache.get(&[b"c"], &program_id).is_some());
+
+    // Re-insert of the existing entry doesn't evict
+    let c = Pubkey::find_program_address(&[b"c"], &program_id);
+    cache.insert(&[b"c"], &program_id, c);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&[b"a"], &program_id).is_some());
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_zero_capacity_never_cache() {
+    let cache = PdaCache::new(0);
+    let program_id = Pubkey::new_unique();
+
+    assert_eq!(
+        cache.find_program_address(&[b"a"], &program_id),
+        Pubkey::find_program_address(&[b"a"], &program_id)
+    );
+    assert!(cache.is_empty());
+    assert!(cache.get(&[b"a"], &program_id).is_none());
+}
+
+#[test]
+fn test_create_program_address_with_bump() {
+    let cache = PdaCache::new(8);
+    let program_id = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"a"], &program_id);
+
+    assert_eq!(
+        cache.create_program_address(&[b"a"], bump, &program_id),
+        Some(address)
+    );
+    // Cache hit
+    assert_eq!(
+        cache.create_program_address(&[b"a"], bump, &program_id),
+        Some(address)
+    );
+    assert_eq!(cache.len(), 1);
+    // Bump is not verified to be canonical, so it is not used by find_program_address
+    assert!(cache.get(&[b"a"], &program_id).is_none());
+    assert_eq!(
+        cache.find_program_address(&[b"a"], &program_id),
+        (address, bump)
+    );
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_non_canonical_bump_keep_find_program_address() {
+    let cache = PdaCache::new(8);
+    let program_id = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"a"], &program_id);
+
+    // Valid bump below the canonical bump derive another address
+    let (non_canonical_address, non_canonical_bump) = (0..bump)
+        .rev()
+        .find_map(|bump| {
+            Pubkey::create_program_address(&[b"a", &[bump]], &program_id)
+                .ok()
+                .map(|address| (address, bump))
+        })
+        .unwrap();
+    assert_ne!(non_canonical_address, address);
+
+    assert_eq!(
+        cache.create_program_address(&[b"a"], non_canonical_bump, &program_id),
+        Some(non_canonical_address)
+    );
+    assert_eq!(
+        cache.find_program_address(&[b"a"], &program_id),
+        (address, bump)
+    );
+    assert_eq!(
+        cache.create_program_address(&[b"a"], non_canonical_bump, &program_id),
+        Some(non_canonical_address)
+    );
+    assert_eq!(
+        cache.create_program_address(&[b"a"], bump, &program_id),
+        Some(address)
+    );
+}
+
+#[test]
+fn test_derive_vault_keys_cached() {
+    let cluster = Cluster::mainnet();
+    let mint = Pubkey::new_unique();
+    let base = dynamic_vault::get_base_address();
+    let (vault, _) = Pubkey::find_program_address(
+        &[VAULT_PREFIX.as_ref(), mint.as_ref(), base.as_ref()],
+        &cluster.vault_program_id,
+    );
+    let (token_vault, _) = Pubkey::find_program_address(
+        &[TOKEN_VAULT_PREFIX.as_ref(), vault.as_ref()],
+        &cluster.vault_program_id,
+    );
+
+    assert_eq!(derive_vault_key(&cluster, mint), vault);
+    // Cache hit
+    assert_eq!(derive_vault_key(&cluster, mint), vault);
+    assert_eq!(derive_token_vault_key(&cluster, vault), token_vault);
+    assert_eq!(derive_token_vault_key(&cluster, vault), token_vault);
+}
@@ -0,0 +1,122 @@
+use common::dynamic_amm::stable_curve::{
+    compute_token_multiplier, get_depeg_type, validate_constant_product_depeg, StableCurve,
+    MARINADE_STATE, SOLIDO_STATE,
+};
+use dynamic_amm::constants::stable_curve::MAX_AMP;
+use dynamic_amm::state::{CurveType, DepegType, TokenMultiplier};
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_token_multiplier_upscale_to_highest_decimals() {
+    assert_eq!(
+        compute_token_multiplier(6, 9),
+        Some(TokenMultiplier {
+            token_a_multiplier: 1_000,
+            token_b_multiplier: 1,
+            precision_factor: 9,
+        })
+    );
+    assert_eq!(
+        compute_token_multiplier(9, 6),
+        Some(TokenMultiplier {
+            token_a_multiplier: 1,
+            token_b_multiplier: 1_000,
+            precision_factor: 9,
+        })
+    );
+    assert_eq!(
+        compute_token_multiplier(6, 6),
+        Some(TokenMultiplier {
+            token_a_multiplier: 1,
+            token_b_multiplier: 1,
+            precision_factor: 6,
+        })
+    );
+
+    // 10^19 is the largest power of 10 in u64
+    assert_eq!(
+        compute_token_multiplier(0, 19).map(|multiplier| multiplier.token_a_multiplier),
+        Some(10_000_000_000_000_000_000)
+    );
+    assert!(compute_token_multiplier(0, 20).is_none());
+}
+
+#[test]
+fn test_stable_curve_type() {
+    let stable_curve = StableCurve::new(6, 9, 100, DepegType::None, None).unwrap();
+    assert!(stable_curve.remaining_accounts().is_empty());
+
+    let CurveType::Stable {
+        amp,
+        token_multiplier,
+        depeg,
+        last_amp_updated_timestamp,
+    } = CurveType::from(stable_curve)
+    else {
+        panic!("Expect stable curve type");
+    };
+    assert_eq!(amp, 100);
+    assert_eq!(token_multiplier, compute_token_multiplier(6, 9).unwrap());
+    assert_eq!(depeg.depeg_type, DepegType::None);
+    assert_eq!(depeg.base_virtual_price, 0);
+    assert_eq!(last_amp_updated_timestamp, 0);
+}
+
+#[test]
+fn test_amp_range() {
+    assert!(StableCurve::new(6, 6, 0, DepegType::None, None).is_err());
+    assert!(StableCurve::new(6, 6, 1, DepegType::None, None).is_ok());
+    assert!(StableCurve::new(6, 6, MAX_AMP, DepegType::None, None).is_ok());
+    assert!(StableCurve::new(6, 6, MAX_AMP + 1, DepegType::None, None).is_err());
+    assert!(StableCurve::new(0, 20, 100, DepegType::None, None).is_err());
+}
+
+#[test]
+fn test_depeg_stake_account() {
+    let stake_pool = Pubkey::new_unique();
+
+    // Non depeg pool has no stake account
+    assert!(StableCurve::new(9, 9, 100, DepegType::None, Some(stake_pool)).is_err());
+
+    // Marinade, and lido use their state account
+    for (depeg_type, state) in [
+        (DepegType::Marinade, MARINADE_STATE),
+        (DepegType::Lido, SOLIDO_STATE),
+    ] {
+        for stake in [None, Some(state)] {
+            let stable_curve = StableCurve::new(9, 9, 100, depeg_type, stake).unwrap();
+            assert_eq!(stable_curve.stake(), Some(state));
+            assert_eq!(
+                stable_curve.remaining_accounts(),
+                vec![AccountMeta::new_readonly(state, false)]
+            );
+        }
+        assert!(StableCurve::new(9, 9, 100, depeg_type, Some(stake_pool)).is_err());
+    }
+
+    // SPL stake pool must be provided
+    assert!(StableCurve::new(9, 9, 100, DepegType::SplStake, None).is_err());
+    let stable_curve = StableCurve::new(9, 9, 100, DepegType::SplStake, Some(stake_pool)).unwrap();
+    assert_eq!(stable_curve.stake(), Some(stake_pool));
+    assert_eq!(stable_curve.depeg_type(), DepegType::SplStake);
+}
+
+#[test]
+fn test_get_depeg_type() {
+    assert_eq!(get_depeg_type(0).unwrap(), DepegType::None);
+    assert_eq!(get_depeg_type(1).unwrap(), DepegType::Marinade);
+    assert_eq!(get_depeg_type(2).unwrap(), DepegType::Lido);
+    assert_eq!(get_depeg_type(3).unwrap(), DepegType::SplStake);
+    assert!(get_depeg_type(4).is_err());
+}
+
+#[test]
+fn test_constant_product_depeg() {
+    assert!(validate_constant_product_depeg(DepegType::None, None).is_ok());
+
+    // Depeg type, and stake account are only for stable pool
+    assert!(validate_constant_product_depeg(DepegType::Marinade, None).is_err());
+    assert!(validate_constant_product_depeg(DepegType::SplStake, None).is_err());
+    assert!(validate_constant_product_depeg(DepegType::None, Some(Pubkey::new_unique())).is_err());
+}
@@ -0,0 +1,117 @@
+use common::cluster::Cluster;
+use common::dynamic_vault::pda::{derive_vault_key, derive_vault_key_with_base};
+use common::dynamic_vault::{get_existing_vault, VaultBase};
+use dynamic_vault::seed::VAULT_PREFIX;
+use serde_json::json;
+use solana_rpc_client::mock_sender::Mocks;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::request::RpcRequest;
+use solana_sdk::pubkey::Pubkey;
+
+/// RPC client which return the default, and idle vault account when it exists
+fn get_rpc_client(
+    cluster: &Cluster,
+    default_vault_exists: bool,
+    idle_vault_exists: bool,
+) -> RpcClient {
+    let account = |exists: bool| {
+        exists.then(|| {
+            json!({
+                "lamports": 1_000_000,
+                "data": ["", "base64"],
+                "owner": cluster.vault_program_id.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+            })
+        })
+    };
+    let mut mocks = Mocks::new();
+    mocks.insert(
+        RpcRequest::GetMultipleAccounts,
+        json!({
+            "context": { "slot": 1 },
+            "value": [account(default_vault_exists), account(idle_vault_exists)],
+        }),
+    );
+    RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)
+}
+
+#[test]
+fn test_vault_base_address() {
+    assert_eq!(VaultBase::default(), VaultBase::Default);
+    assert_eq!(
+        VaultBase::Default.address(),
+        dynamic_vault::get_base_address()
+    );
+    assert_eq!(
+        VaultBase::Idle.address(),
+        dynamic_vault::get_base_address_for_idle_vault()
+    );
+    assert_ne!(VaultBase::Default.address(), VaultBase::Idle.address());
+
+    for vault_base in [VaultBase::Default, VaultBase::Idle] {
+        assert_eq!(
+            VaultBase::from_address(vault_base.address()),
+            Some(vault_base)
+        );
+    }
+    assert_eq!(VaultBase::from_address(Pubkey::new_unique()), None);
+}
+
+#[test]
+fn test_derive_vault_key_with_base() {
+    let cluster = Cluster::mainnet();
+    let mint = Pubkey::new_unique();
+
+    for vault_base in [VaultBase::Default, VaultBase::Idle] {
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                VAULT_PREFIX.as_ref(),
+                mint.as_ref(),
+                vault_base.address().as_ref(),
+            ],
+            &cluster.vault_program_id,
+        );
+        assert_eq!(
+            derive_vault_key_with_base(&cluster, mint, vault_base),
+            expected
+        );
+    }
+
+    assert_eq!(
+        derive_vault_key(&cluster, mint),
+        derive_vault_key_with_base(&cluster, mint, VaultBase::Default)
+    );
+    assert_ne!(
+        derive_vault_key(&cluster, mint),
+        derive_vault_key_with_base(&cluster, mint, VaultBase::Idle)
+    );
+}
+
+#[test]
+fn test_get_existing_vault() {
+    let cluster = Cluster::mainnet();
+    let mint = Pubkey::new_unique();
+    let default_vault = derive_vault_key_with_base(&cluster, mint, VaultBase::Default);
+    let idle_vault = derive_vault_key_with_base(&cluster, mint, VaultBase::Idle);
+
+    let existing_vault = |default_vault_exists, idle_vault_exists| {
+        let rpc_client = get_rpc_client(&cluster, default_vault_exists, idle_vault_exists);
+        get_existing_vault(&cluster, &rpc_client, mint).unwrap()
+    };
+
+    assert_eq!(existing_vault(false, false), None);
+    assert_eq!(
+        existing_vault(true, false),
+        Some((default_vault, VaultBase::Default))
+    );
+    assert_eq!(
+        existing_vault(false, true),
+        Some((idle_vault, VaultBase::Idle))
+    );
+    // Default base vault is preferred
+    assert_eq!(
+        existing_vault(true, true),
+        Some((default_vault, VaultBase::Default))
+    );
+}
@@ -0,0 +1,225 @@
+//! Cross pool arbitrage detection
+use crate::{compute_quote, QuoteData, QuoteResult};
+use anchor_lang::prelude::Pubkey;
+
+/// Maximum number of search iterations used to size an opportunity
+const MAX_SIZING_ITERATIONS: usize = 128;
+
+/// A single swap of an arbitrage cycle
+#[derive(Debug, Clone)]
+pub struct ArbitrageLeg {
+    /// Pool to swap with
+    pub pool: Pubkey,
+    /// Token mint swapped into the pool
+    pub in_token_mint: Pubkey,
+    /// Token mint received from the pool
+    pub out_token_mint: Pubkey,
+    /// Amount swapped into the pool
+    pub in_amount: u64,
+    /// Amount received from the pool
+    pub out_amount: u64,
+    /// Trade fee charged by the pool. Fee is charged based on in token mint.
+    pub fee: u64,
+    /// Protocol fee charged by the pool. Fee is charged based on in token mint.
+    pub protocol_fee: u64,
+}
+
+/// A profitable cycle of swaps which starts, and ends with the same token mint
+#[derive(Debug, Clone)]
+pub struct ArbitrageOpportunity {
+    /// Token mint the cycle starts, and ends with. Profit is denominated in this mint.
+    pub token_mint: Pubkey,
+    /// Amount swapped into the first leg
+    pub in_amount: u64,
+    /// Amount received from the last leg
+    pub out_amount: u64,
+    /// Expected profit, net of trade and protocol fees
+    pub profit: u64,
+    /// Swaps of the cycle, in execution order
+    pub legs: Vec<ArbitrageLeg>,
+}
+
+/// Pool with index, and direction used while walking the cycles
+#[derive(Clone, Copy)]
+struct Hop {
+    pool_index: usize,
+    in_token_mint: Pubkey,
+    out_token_mint: Pubkey,
+}
+
+fn get_other_mint(quote_data: &QuoteData, token_mint: Pubkey) -> Option<Pubkey> {
+    let pool = &quote_data.pool;
+    if pool.token_a_mint == token_mint {
+        Some(pool.token_b_mint)
+    } else if pool.token_b_mint == token_mint {
+        Some(pool.token_a_mint)
+    } else {
+        None
+    }
+}
+
+/// Enumerate all two pools, and triangular cycles starting from `token_mint`
+fn get_cycles(pools: &[(Pubkey, QuoteData)], token_mint: Pubkey) -> Vec<Vec<Hop>> {
+    let hops_from = |mint: Pubkey| {
+        pools
+            .iter()
+            .enumerate()
+            .filter_map(move |(pool_index, (_pool, quote_data))| {
+                get_other_mint(quote_data, mint).map(|out_token_mint| Hop {
+                    pool_index,
+                    in_token_mint: mint,
+                    out_token_mint,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut cycles = vec![];
+
+    for first in hops_from(token_mint) {
+        for second in hops_from(first.out_token_mint) {
+            if second.pool_index == first.pool_index {
+                continue;
+            }
+
+            if second.out_token_mint == token_mint {
+                cycles.push(vec![first, second]);
+                continue;
+            }
+
+            for third in hops_from(second.out_token_mint) {
+                if third.out_token_mint != token_mint
+                    || third.pool_index == first.pool_index
+                    || third.pool_index == second.pool_index
+                {
+                    continue;
+                }
+                cycles.push(vec![first, second, third]);
+            }
+        }
+    }
+
+    cycles
+}
+
+/// Quote every leg of the cycle. Return None if any of the leg is not swappable.
+fn quote_cycle(
+    pools: &[(Pubkey, QuoteData)],
+    cycle: &[Hop],
+    in_amount: u64,
+) -> Option<Vec<ArbitrageLeg>> {
+    let mut legs = Vec::with_capacity(cycle.len());
+    let mut amount = in_amount;
+
+    for hop in cycle {
+        let (pool, quote_data) = pools.get(hop.pool_index)?;
+        let QuoteResult {
+            out_amount,
+            fee,
+            protocol_fee,
+        } = compute_quote(hop.in_token_mint, amount, quote_data.clone()).ok()?;
+
+        legs.push(ArbitrageLeg {
+            pool: *pool,
+            in_token_mint: hop.in_token_mint,
+            out_token_mint: hop.out_token_mint,
+            in_amount: amount,
+            out_amount,
+            fee,
+            protocol_fee,
+        });
+
+        amount = out_amount;
+    }
+
+    Some(legs)
+}
+
+/// Profit of the cycle, or None if any leg fails to quote, such as the out amount exceeding the vault reserve
+fn get_cycle_profit(pools: &[(Pubkey, QuoteData)], cycle: &[Hop], in_amount: u64) -> Option<i128> {
+    let legs = quote_cycle(pools, cycle, in_amount)?;
+    let out_amount = legs.last().map(|leg| leg.out_amount).unwrap_or_default();
+    Some(i128::from(out_amount) - i128::from(in_amount))
+}
+
+/// Find the in amount which maximize the cycle profit. Profit of the cycle is concave to the in amount, therefore ternary search is used.
+/// Quote fails when the in amount is too large, so the bracket is shrunk below the failed probe. The final bracket is scanned linearly.
+fn size_cycle(
+    pools: &[(Pubkey, QuoteData)],
+    cycle: &[Hop],
+    max_in_amount: u64,
+) -> Option<(u64, i128)> {
+    let mut low = 1u64;
+    let mut high = max_in_amount;
+
+    for _ in 0..MAX_SIZING_ITERATIONS {
+        if high.saturating_sub(low) <= 2 {
+            break;
+        }
+        let third = (high - low) / 3;
+        let mid_low = low + third;
+        let mid_high = high - third;
+
+        let mid_low_profit = get_cycle_profit(pools, cycle, mid_low);
+        let mid_high_profit = get_cycle_profit(pools, cycle, mid_high);
+
+        match (mid_low_profit, mid_high_profit) {
+            (None, _) => high = mid_low - 1,
+            (Some(_), None) => high = mid_high - 1,
+            (Some(mid_low_profit), Some(mid_high_profit)) => {
+                if mid_low_profit < mid_high_profit {
+                    low = mid_low + 1;
+                } else if mid_low_profit > mid_high_profit {
+                    high = mid_high - 1;
+                } else {
+                    // Optimum of the concave profit is between the probes on tie, keep both probes in the bracket
+                    low = mid_low;
+                    high = mid_high;
+                }
+            }
+        }
+    }
+
+    (low..=high)
+        .filter_map(|in_amount| {
+            get_cycle_profit(pools, cycle, in_amount).map(|profit| (in_amount, profit))
+        })
+        .max_by_key(|(_, profit)| *profit)
+}
+
+/// Find profitable two pools, and triangular cycles across the given pools, keyed by pool address.
+/// Each cycle starts, and ends with `token_mint`, and is sized up to `max_in_amount` to maximize the profit.
+/// Only opportunities with profit larger than `min_profit` are returned, sorted by profit in descending order.
+pub fn find_arbitrage_opportunities(
+    pools: &[(Pubkey, QuoteData)],
+    token_mint: Pubkey,
+    max_in_amount: u64,
+    min_profit: u64,
+) -> Vec<ArbitrageOpportunity> {
+    let mut opportunities = vec![];
+
+    for cycle in get_cycles(pools, token_mint) {
+        let in_amount = match size_cycle(pools, &cycle, max_in_amount) {
+            Some((in_amount, profit)) if profit > i128::from(min_profit) => in_amount,
+            _ => continue,
+        };
+
+        let legs = match quote_cycle(pools, &cycle, in_amount) {
+            Some(legs) => legs,
+            None => continue,
+        };
+
+        let out_amount = legs.last().map(|leg| leg.out_amount).unwrap_or_default();
+
+        opportunities.push(ArbitrageOpportunity {
+            token_mint,
+            in_amount,
+            out_amount,
+            profit: out_amount.saturating_sub(in_amount),
+            legs,
+        });
+    }
+
+    opportunities.sort_by_key(|opportunity| std::cmp::Reverse(opportunity.profit));
+    opportunities
+}
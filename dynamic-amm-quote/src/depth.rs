@@ -0,0 +1,283 @@
+//! Liquidity depth, and price curve sampling
+use crate::depeg::update_base_virtual_price;
+use crate::math::*;
+use crate::{compute_pool_tokens, QuoteData, VaultInfo};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::constants::fee::MAX_BASIS_POINT;
+use prog_dynamic_amm::state::PoolFees;
+use serde::{Serialize, Serializer};
+use spl_token_swap::curve::calculator::TradeDirection;
+
+/// Price movement, in bps, reported by default. 0.1%, 1% and 5%.
+pub const DEFAULT_PRICE_CHANGE_BPS: [u64; 3] = [10, 100, 500];
+
+fn serialize_pubkey<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&pubkey.to_string())
+}
+
+/// Amount required to move the price by `price_change_bps`
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthLevel {
+    /// Price movement, in bps
+    pub price_change_bps: u64,
+    /// Amount of in token, including trade fee
+    pub in_amount: u64,
+    /// Amount of out token received
+    pub out_amount: u64,
+}
+
+/// A point of the price vs size curve
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceSample {
+    /// Amount of in token, including trade fee
+    pub in_amount: u64,
+    /// Amount of out token received
+    pub out_amount: u64,
+    /// out_amount / in_amount
+    pub average_price: f64,
+    /// Marginal price, in out token per in token, after the swap
+    pub marginal_price: f64,
+    /// Marginal price movement relative to the spot price. 0.01 = 1%.
+    pub price_impact: f64,
+}
+
+/// Depth of one swap direction
+#[derive(Debug, Clone, Serialize)]
+pub struct SideDepth {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub in_token_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub out_token_mint: Pubkey,
+    /// Spot price, in out token per in token
+    pub spot_price: f64,
+    /// Amount required to move the price for each requested level. None if the pool can't move the price that far.
+    pub levels: Vec<Option<DepthLevel>>,
+    /// Sampled price vs size curve
+    pub samples: Vec<PriceSample>,
+}
+
+/// Liquidity depth of the pool. All amounts, and prices are in atomic units.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquidityDepth {
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_a_mint: Pubkey,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub token_b_mint: Pubkey,
+    /// Token A amount held by the pool through vault A
+    pub token_a_amount: u64,
+    /// Token B amount held by the pool through vault B
+    pub token_b_amount: u64,
+    /// Swap token A to token B. Selling token A.
+    pub a_to_b: SideDepth,
+    /// Swap token B to token A. Buying token A.
+    pub b_to_a: SideDepth,
+}
+
+struct DepthCurve<'a> {
+    swap_curve: Box<dyn SwapCurve>,
+    fees: &'a PoolFees,
+    trade_direction: TradeDirection,
+    swap_source_amount: u64,
+    swap_destination_amount: u64,
+}
+
+impl<'a> DepthCurve<'a> {
+    /// Marginal price, in destination token per source token, of the given reserves. Derived analytically from the curve.
+    fn marginal_price(&self, swap_source_amount: u64, swap_destination_amount: u64) -> Option<f64> {
+        self.swap_curve.marginal_price(
+            swap_source_amount,
+            swap_destination_amount,
+            self.trade_direction,
+        )
+    }
+
+    fn spot_price(&self) -> Option<f64> {
+        self.marginal_price(self.swap_source_amount, self.swap_destination_amount)
+    }
+
+    /// Swap `in_amount`, including trade fee. Return out amount, and marginal price after the swap.
+    fn swap(&self, in_amount: u64) -> Option<(u64, f64)> {
+        let trade_fee: u64 = self.fees.trading_fee(in_amount.into())?.try_into().ok()?;
+        let in_amount_after_fee = in_amount.checked_sub(trade_fee)?;
+
+        let SwapResult {
+            new_swap_source_amount,
+            new_swap_destination_amount,
+            destination_amount_swapped,
+            ..
+        } = self.swap_curve.swap(
+            in_amount_after_fee,
+            self.swap_source_amount,
+            self.swap_destination_amount,
+            self.trade_direction,
+        )?;
+
+        // Pool never drain the whole reserve
+        if new_swap_destination_amount == 0 {
+            return None;
+        }
+
+        let marginal_price = self.marginal_price(
+            new_swap_source_amount.try_into().ok()?,
+            new_swap_destination_amount.try_into().ok()?,
+        )?;
+
+        Some((destination_amount_swapped.try_into().ok()?, marginal_price))
+    }
+
+    /// Find the largest in amount which move the price less than `price_change_bps`
+    fn depth(&self, spot_price: f64, price_change_bps: u64) -> Option<DepthLevel> {
+        let target_price = spot_price
+            * (MAX_BASIS_POINT - price_change_bps.min(MAX_BASIS_POINT)) as f64
+            / MAX_BASIS_POINT as f64;
+
+        let mut low = 0u64;
+        let mut high = u64::MAX;
+        let mut level = None;
+
+        while low < high {
+            let mid = low + (high - low) / 2 + 1;
+            match self.swap(mid) {
+                Some((out_amount, marginal_price)) if marginal_price > target_price => {
+                    level = Some(DepthLevel {
+                        price_change_bps,
+                        in_amount: mid,
+                        out_amount,
+                    });
+                    low = mid;
+                }
+                _ => high = mid - 1,
+            }
+        }
+
+        // The price never reach the target within the reserve
+        if high == u64::MAX {
+            return None;
+        }
+
+        level
+    }
+
+    fn sample(&self, spot_price: f64, sample_count: u64) -> Vec<PriceSample> {
+        (1..=sample_count)
+            .filter_map(|i| {
+                let in_amount =
+                    u128::from(self.swap_source_amount) * u128::from(i) / u128::from(sample_count);
+                let in_amount: u64 = in_amount.try_into().ok()?;
+                if in_amount == 0 {
+                    return None;
+                }
+                let (out_amount, marginal_price) = self.swap(in_amount)?;
+                Some(PriceSample {
+                    in_amount,
+                    out_amount,
+                    average_price: out_amount as f64 / in_amount as f64,
+                    marginal_price,
+                    price_impact: 1.0 - marginal_price / spot_price,
+                })
+            })
+            .collect()
+    }
+
+    fn side_depth(
+        &self,
+        in_token_mint: Pubkey,
+        out_token_mint: Pubkey,
+        price_change_bps: &[u64],
+        sample_count: u64,
+    ) -> anyhow::Result<SideDepth> {
+        let spot_price = self.spot_price().context("Fail to get spot price")?;
+
+        Ok(SideDepth {
+            in_token_mint,
+            out_token_mint,
+            spot_price,
+            levels: price_change_bps
+                .iter()
+                .map(|bps| self.depth(spot_price, *bps))
+                .collect(),
+            samples: self.sample(spot_price, sample_count),
+        })
+    }
+}
+
+/// Compute the amount required to move the price by each of `price_change_bps`, and sample the price vs size curve
+/// with `sample_count` evenly spaced swap sizes up to the in token reserve, for both swap directions.
+/// Price is measured as the marginal price of the curve, while amounts include trade fee.
+pub fn compute_liquidity_depth(
+    quote_data: QuoteData,
+    price_change_bps: &[u64],
+    sample_count: u64,
+) -> anyhow::Result<LiquidityDepth> {
+    let QuoteData {
+        mut pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        stake_data,
+        ..
+    } = quote_data;
+
+    update_base_virtual_price(&mut pool, &clock, stake_data)?;
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let (token_a_amount, token_b_amount) = compute_pool_tokens(
+        current_time,
+        VaultInfo {
+            lp_amount: pool_vault_a_lp_token.amount,
+            lp_supply: vault_a_lp_mint.supply,
+            vault: vault_a,
+        },
+        VaultInfo {
+            lp_amount: pool_vault_b_lp_token.amount,
+            lp_supply: vault_b_lp_mint.supply,
+            vault: vault_b,
+        },
+    )?;
+
+    ensure!(
+        token_a_amount > 0 && token_b_amount > 0,
+        "Pool has no liquidity"
+    );
+
+    let a_to_b = DepthCurve {
+        swap_curve: get_swap_curve(pool.curve_type),
+        fees: &pool.fees,
+        trade_direction: TradeDirection::AtoB,
+        swap_source_amount: token_a_amount,
+        swap_destination_amount: token_b_amount,
+    };
+
+    let b_to_a = DepthCurve {
+        swap_curve: get_swap_curve(pool.curve_type),
+        fees: &pool.fees,
+        trade_direction: TradeDirection::BtoA,
+        swap_source_amount: token_b_amount,
+        swap_destination_amount: token_a_amount,
+    };
+
+    Ok(LiquidityDepth {
+        token_a_mint: pool.token_a_mint,
+        token_b_mint: pool.token_b_mint,
+        token_a_amount,
+        token_b_amount,
+        a_to_b: a_to_b.side_depth(
+            pool.token_a_mint,
+            pool.token_b_mint,
+            price_change_bps,
+            sample_count,
+        )?,
+        b_to_a: b_to_a.side_depth(
+            pool.token_b_mint,
+            pool.token_a_mint,
+            price_change_bps,
+            sample_count,
+        )?,
+    })
+}
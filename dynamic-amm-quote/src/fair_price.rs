@@ -0,0 +1,114 @@
+//! Manipulation resistant LP token pricing.
+//!
+//! Spot value of the pool, a * pA + b * pB, can be moved freely by swapping against the pool.
+//! Fair value is derived from the curve invariant D instead, which swaps can't change (other than accruing fees).
+//!
+//! Constant product, D = sqrt(k) = sqrt(a * b). At the fair reserves, where a * pA = b * pB:
+//!     fair_value = 2 * sqrt(k * pA * pB) = 2 * D * sqrt(pA * pB)
+//!
+//! Stable swap, D is the invariant of the normalized reserves, which equals to the sum of the normalized reserves when the pool is balanced:
+//!     fair_value = D * min(pA / fa, pB / fb)
+//! where fa, fb are the normalized amount of one token A, and B (token multiplier, and depeg virtual price).
+//! Taking the minimum price prices the pool as if it was entirely made of the cheaper token, which stays conservative when one side depeg.
+//!
+//! In both cases, a and b are the token amounts withdrawable from the vaults. Vault locked profit is excluded as it is not unlocked yet.
+//!
+//! fair_lp_price = fair_value / lp_supply
+use crate::depeg::update_base_virtual_price;
+use crate::math::{compute_d, get_normalize_factor};
+use crate::{compute_pool_tokens, QuoteData, VaultInfo};
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::state::CurveType;
+use serde::Serialize;
+
+/// Fair, and spot value of the pool, and LP token. Prices, and values are in the same quote currency as the given token prices.
+#[derive(Debug, Clone, Serialize)]
+pub struct LpFairPrice {
+    /// Token A amount held by the pool, excluding vault locked profit
+    pub token_a_amount: u64,
+    /// Token B amount held by the pool, excluding vault locked profit
+    pub token_b_amount: u64,
+    /// Invariant D of the pool
+    pub d: f64,
+    /// Pool value derived from the invariant
+    pub fair_value: f64,
+    /// LP token price derived from the invariant
+    pub fair_lp_price: f64,
+    /// Pool value derived from the reserves. Manipulable.
+    pub spot_value: f64,
+    /// LP token price derived from the reserves. Manipulable.
+    pub spot_lp_price: f64,
+}
+
+/// Compute the fair price of the pool LP token from the external price of token A, and token B.
+/// Token prices are per atomic unit of the token, and the LP price is per atomic unit of the pool LP token.
+pub fn compute_lp_fair_price(
+    quote_data: QuoteData,
+    lp_supply: u64,
+    token_a_price: f64,
+    token_b_price: f64,
+) -> anyhow::Result<LpFairPrice> {
+    ensure!(lp_supply > 0, "LP supply is zero");
+    ensure!(
+        token_a_price >= 0.0 && token_b_price >= 0.0,
+        "Token price must be non-negative"
+    );
+
+    let QuoteData {
+        mut pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        stake_data,
+        ..
+    } = quote_data;
+
+    update_base_virtual_price(&mut pool, &clock, stake_data)?;
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let (token_a_amount, token_b_amount) = compute_pool_tokens(
+        current_time,
+        VaultInfo {
+            lp_amount: pool_vault_a_lp_token.amount,
+            lp_supply: vault_a_lp_mint.supply,
+            vault: vault_a,
+        },
+        VaultInfo {
+            lp_amount: pool_vault_b_lp_token.amount,
+            lp_supply: vault_b_lp_mint.supply,
+            vault: vault_b,
+        },
+    )?;
+
+    let d = compute_d(pool.curve_type, token_a_amount, token_b_amount)
+        .context("Fail to compute invariant")? as f64;
+
+    let fair_value = match pool.curve_type {
+        CurveType::ConstantProduct => 2.0 * d * (token_a_price * token_b_price).sqrt(),
+        CurveType::Stable { .. } => {
+            let (token_a_factor, token_b_factor) =
+                get_normalize_factor(pool.curve_type).context("Fail to get normalize factor")?;
+            d * f64::min(
+                token_a_price / token_a_factor,
+                token_b_price / token_b_factor,
+            )
+        }
+    };
+
+    let spot_value = token_a_amount as f64 * token_a_price + token_b_amount as f64 * token_b_price;
+
+    Ok(LpFairPrice {
+        token_a_amount,
+        token_b_amount,
+        d,
+        fair_value,
+        fair_lp_price: fair_value / lp_supply as f64,
+        spot_value,
+        spot_lp_price: spot_value / lp_supply as f64,
+    })
+}
@@ -1,6 +1,12 @@
+pub mod arbitrage;
 pub mod curve;
 pub mod depeg;
+pub mod depth;
+pub mod fair_price;
+pub mod lock_escrow;
 pub mod math;
+pub mod pnl;
+pub mod stable_liquidity;
 use crate::depeg::update_base_virtual_price;
 use crate::math::*;
 use anchor_lang::prelude::*;
@@ -53,6 +59,8 @@ pub struct QuoteResult {
     pub out_amount: u64,
     /// Total fee amount. Fee is charged based on in token mint.
     pub fee: u64,
+    /// Protocol fee amount. Fee is charged based on in token mint.
+    pub protocol_fee: u64,
 }
 
 pub fn compute_quote(
@@ -239,6 +247,7 @@ pub fn compute_quote(
 
     Ok(QuoteResult {
         fee: trade_fee.try_into()?,
+        protocol_fee: protocol_fee.try_into()?,
         out_amount,
     })
 }
@@ -0,0 +1,105 @@
+//! Claimable fee of the lock escrow.
+//!
+//! The program track the LP virtual price, 2^64 * D / lp_supply, when the lock escrow was last updated, as `lp_per_token`.
+//! Fee accrued since then is the part of the locked LP explained by the virtual price growth:
+//!     new_fee = total_locked_amount * (virtual_price - lp_per_token) / virtual_price
+//! Claimable fee is capped by the escrow vault balance, same as the program.
+use crate::depeg::update_base_virtual_price;
+use crate::math::compute_d;
+use crate::{compute_pool_tokens, QuoteData, VaultInfo};
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::state::LockEscrow;
+use serde::Serialize;
+
+/// Claimable fee of the lock escrow
+#[derive(Debug, Clone, Serialize)]
+pub struct LockEscrowFee {
+    /// LP amount claimable as fee
+    pub lp_amount: u64,
+    /// Token A amount of the claimable LP
+    pub token_a_amount: u64,
+    /// Token B amount of the claimable LP
+    pub token_b_amount: u64,
+    /// Current LP virtual price, in the same scale as `LockEscrow::lp_per_token`
+    pub virtual_price: f64,
+}
+
+/// Compute the fee claimable from the lock escrow.
+/// `lp_supply` is the current pool LP mint supply. `escrow_vault_amount` is the LP balance of the escrow vault.
+pub fn compute_lock_escrow_fee(
+    quote_data: QuoteData,
+    lp_supply: u64,
+    lock_escrow: &LockEscrow,
+    escrow_vault_amount: u64,
+) -> anyhow::Result<LockEscrowFee> {
+    ensure!(lp_supply > 0, "LP supply is zero");
+
+    let QuoteData {
+        mut pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        stake_data,
+        ..
+    } = quote_data;
+
+    update_base_virtual_price(&mut pool, &clock, stake_data)?;
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let (pool_token_a_amount, pool_token_b_amount) = compute_pool_tokens(
+        current_time,
+        VaultInfo {
+            lp_amount: pool_vault_a_lp_token.amount,
+            lp_supply: vault_a_lp_mint.supply,
+            vault: vault_a,
+        },
+        VaultInfo {
+            lp_amount: pool_vault_b_lp_token.amount,
+            lp_supply: vault_b_lp_mint.supply,
+            vault: vault_b,
+        },
+    )?;
+
+    let d = compute_d(pool.curve_type, pool_token_a_amount, pool_token_b_amount)
+        .context("Fail to compute d")?;
+    let virtual_price = d
+        .checked_mul(1 << 64)
+        .and_then(|value| value.checked_div(lp_supply.into()))
+        .context("Fail to compute virtual price")?;
+
+    let lp_per_token = lock_escrow.lp_per_token;
+    let new_fee = if virtual_price > lp_per_token && lp_per_token > 0 {
+        (lock_escrow.total_locked_amount as f64 * (virtual_price - lp_per_token) as f64
+            / virtual_price as f64) as u64
+    } else {
+        0
+    };
+
+    let lp_amount = new_fee
+        .checked_add(lock_escrow.unclaimed_fee_pending)
+        .context("Fail to get unclaimed fee")?
+        .min(escrow_vault_amount);
+
+    let token_a_amount = u128::from(pool_token_a_amount)
+        .checked_mul(lp_amount.into())
+        .and_then(|amount| amount.checked_div(lp_supply.into()))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .context("Fail to get token a amount")?;
+    let token_b_amount = u128::from(pool_token_b_amount)
+        .checked_mul(lp_amount.into())
+        .and_then(|amount| amount.checked_div(lp_supply.into()))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .context("Fail to get token b amount")?;
+
+    Ok(LockEscrowFee {
+        lp_amount,
+        token_a_amount,
+        token_b_amount,
+        virtual_price: virtual_price as f64,
+    })
+}
@@ -3,6 +3,29 @@ use spl_token_swap::curve::{calculator::SwapWithoutFeesResult, constant_product:
 
 pub struct ConstantProduct {}
 
+impl ConstantProduct {
+    /// Invariant D = sqrt(a * b), rounded down
+    pub fn compute_d(&self, token_a_amount: u64, token_b_amount: u64) -> Option<u128> {
+        let k = u128::from(token_a_amount).checked_mul(token_b_amount.into())?;
+        Some(sqrt(k))
+    }
+}
+
+/// Integer square root, rounded down. Newton method converge from above.
+fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = value / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
 impl SwapCurve for ConstantProduct {
     fn swap(
         &self,
@@ -28,4 +51,17 @@ impl SwapCurve for ConstantProduct {
             destination_amount_swapped,
         })
     }
+
+    /// x * y = k, the marginal price is y / x
+    fn marginal_price(
+        &self,
+        swap_source_amount: u64,
+        swap_destination_amount: u64,
+        _trade_direction: TradeDirection,
+    ) -> Option<f64> {
+        if swap_source_amount == 0 {
+            return None;
+        }
+        Some(swap_destination_amount as f64 / swap_source_amount as f64)
+    }
 }
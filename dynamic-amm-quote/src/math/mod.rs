@@ -1,6 +1,6 @@
 use spl_token_swap::curve::calculator::TradeDirection;
 
-use prog_dynamic_amm::state::CurveType;
+use prog_dynamic_amm::state::{CurveType, PoolFees};
 
 use self::{constant_product::ConstantProduct, stable_swap::StableSwap};
 
@@ -28,6 +28,14 @@ pub trait SwapCurve {
         swap_destination_amount: u64,
         trade_direction: TradeDirection,
     ) -> Option<SwapResult>;
+
+    /// Marginal price of the curve, in destination token per source token, excluding trade fee
+    fn marginal_price(
+        &self,
+        swap_source_amount: u64,
+        swap_destination_amount: u64,
+        trade_direction: TradeDirection,
+    ) -> Option<f64>;
 }
 
 /// Get swap curve for calculation
@@ -47,3 +55,111 @@ pub fn get_swap_curve(curve_type: CurveType) -> Box<dyn SwapCurve> {
         }),
     }
 }
+
+/// Normalized amount of one token A, and one token B, in the same scale as the invariant D. Stable swap upscale the token amounts by the token multiplier, and depeg virtual price.
+pub fn get_normalize_factor(curve_type: CurveType) -> Option<(f64, f64)> {
+    match curve_type {
+        CurveType::ConstantProduct => Some((1.0, 1.0)),
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        } => StableSwap {
+            amp,
+            depeg,
+            last_amp_updated_timestamp,
+            token_multiplier,
+        }
+        .normalize_factor(),
+    }
+}
+
+/// Invariant D of the curve, same as the on chain curve.
+/// Constant product: D = sqrt(a * b)
+/// Stable swap: 2 * amp * (a + b) + D = 2 * amp * D + D^3 / (4ab), with a, b normalized
+pub fn compute_d(curve_type: CurveType, token_a_amount: u64, token_b_amount: u64) -> Option<u128> {
+    match curve_type {
+        CurveType::ConstantProduct => ConstantProduct {}.compute_d(token_a_amount, token_b_amount),
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        } => StableSwap {
+            amp,
+            depeg,
+            last_amp_updated_timestamp,
+            token_multiplier,
+        }
+        .compute_d(token_a_amount, token_b_amount),
+    }
+}
+
+/// LP minted by depositing token A, and token B in any ratio. Only stable swap curve support imbalance deposit.
+pub fn compute_imbalance_deposit(
+    curve_type: CurveType,
+    deposit_a_amount: u64,
+    deposit_b_amount: u64,
+    swap_a_amount: u64,
+    swap_b_amount: u64,
+    lp_supply: u64,
+    fees: &PoolFees,
+) -> Option<u64> {
+    match curve_type {
+        CurveType::ConstantProduct => None,
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        } => StableSwap {
+            amp,
+            depeg,
+            last_amp_updated_timestamp,
+            token_multiplier,
+        }
+        .compute_mint_amount_for_deposit(
+            deposit_a_amount,
+            deposit_b_amount,
+            swap_a_amount,
+            swap_b_amount,
+            lp_supply,
+            fees,
+        ),
+    }
+}
+
+/// Token amount received by burning LP, and withdraw only one token. Only stable swap curve support single side withdraw.
+pub fn compute_single_side_withdraw(
+    curve_type: CurveType,
+    pool_token_amount: u64,
+    lp_supply: u64,
+    swap_a_amount: u64,
+    swap_b_amount: u64,
+    is_token_a: bool,
+    fees: &PoolFees,
+) -> Option<u64> {
+    match curve_type {
+        CurveType::ConstantProduct => None,
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        } => StableSwap {
+            amp,
+            depeg,
+            last_amp_updated_timestamp,
+            token_multiplier,
+        }
+        .compute_withdraw_one(
+            pool_token_amount,
+            lp_supply,
+            swap_a_amount,
+            swap_b_amount,
+            is_token_a,
+            fees,
+        ),
+    }
+}
@@ -2,7 +2,7 @@ use super::*;
 use meteora_stable_swap_client::fees::Fees as SaberFees;
 use meteora_stable_swap_math::curve::StableSwap as SaberStableSwap;
 use prog_dynamic_amm::constants::{depeg::PRECISION, fee::FEE_DENOMINATOR};
-use prog_dynamic_amm::state::{Depeg, DepegType, TokenMultiplier};
+use prog_dynamic_amm::state::{Depeg, DepegType, PoolFees, TokenMultiplier};
 
 /// Stable swap curve
 #[derive(Clone, Copy, Debug)]
@@ -55,6 +55,112 @@ impl StableSwap {
     }
 }
 
+impl StableSwap {
+    /// Normalized amount of one token A, and one token B, in the same scale as the invariant D
+    pub fn normalize_factor(&self) -> Option<(f64, f64)> {
+        let token_a_factor = self.upscale_token_a(1)? as f64;
+        let token_b_factor = self.upscale_token_b(1)? as f64;
+
+        if self.depeg.depeg_type != DepegType::None {
+            Some((
+                token_a_factor / PRECISION as f64,
+                token_b_factor / PRECISION as f64,
+            ))
+        } else {
+            Some((token_a_factor, token_b_factor))
+        }
+    }
+
+    /// Stable swap invariant D of the upscaled token amounts, same as the on chain curve.
+    /// Extra precision of the depeg upscaling is removed.
+    pub fn compute_d(&self, token_a_amount: u64, token_b_amount: u64) -> Option<u128> {
+        let upscaled_token_a_amount = self.upscale_token_a(token_a_amount.into())?;
+        let upscaled_token_b_amount = self.upscale_token_b(token_b_amount.into())?;
+
+        let saber_stable_swap: SaberStableSwap = self.into();
+        let d = saber_stable_swap.compute_d(upscaled_token_a_amount, upscaled_token_b_amount)?;
+
+        if self.depeg.depeg_type != DepegType::None {
+            d.checked_div(PRECISION.into())
+        } else {
+            Some(d)
+        }
+    }
+
+    /// LP minted by depositing token A, and token B in any ratio.
+    /// Imbalance part of the deposit is charged half of the trade fee, same as the on chain curve.
+    pub fn compute_mint_amount_for_deposit(
+        &self,
+        deposit_a_amount: u64,
+        deposit_b_amount: u64,
+        swap_a_amount: u64,
+        swap_b_amount: u64,
+        lp_supply: u64,
+        fees: &PoolFees,
+    ) -> Option<u64> {
+        let saber_stable_swap: SaberStableSwap = self.into();
+        let mint_amount = saber_stable_swap.compute_mint_amount_for_deposit(
+            self.upscale_token_a(deposit_a_amount.into())?,
+            self.upscale_token_b(deposit_b_amount.into())?,
+            self.upscale_token_a(swap_a_amount.into())?,
+            self.upscale_token_b(swap_b_amount.into())?,
+            lp_supply.into(),
+            &get_saber_fees(fees),
+        )?;
+
+        mint_amount.try_into().ok()
+    }
+
+    /// Token amount received by burning LP, and withdraw only token A when `is_token_a`, else token B.
+    /// Trade fee is charged same as the on chain curve.
+    pub fn compute_withdraw_one(
+        &self,
+        pool_token_amount: u64,
+        lp_supply: u64,
+        swap_a_amount: u64,
+        swap_b_amount: u64,
+        is_token_a: bool,
+        fees: &PoolFees,
+    ) -> Option<u64> {
+        let upscaled_swap_a_amount = self.upscale_token_a(swap_a_amount.into())?;
+        let upscaled_swap_b_amount = self.upscale_token_b(swap_b_amount.into())?;
+        let (upscaled_swap_base_amount, upscaled_swap_quote_amount) = if is_token_a {
+            (upscaled_swap_a_amount, upscaled_swap_b_amount)
+        } else {
+            (upscaled_swap_b_amount, upscaled_swap_a_amount)
+        };
+
+        let saber_stable_swap: SaberStableSwap = self.into();
+        let (withdraw_amount, _fee) = saber_stable_swap.compute_withdraw_one(
+            pool_token_amount.into(),
+            lp_supply.into(),
+            upscaled_swap_base_amount,
+            upscaled_swap_quote_amount,
+            &get_saber_fees(fees),
+        )?;
+
+        let withdraw_amount = if is_token_a {
+            self.downscale_token_a(withdraw_amount)?
+        } else {
+            self.downscale_token_b(withdraw_amount)?
+        };
+
+        withdraw_amount.try_into().ok()
+    }
+}
+
+/// Saber fees charging the pool trade fee on the imbalance part of the deposit, and withdraw
+fn get_saber_fees(fees: &PoolFees) -> SaberFees {
+    SaberFees {
+        admin_trade_fee_denominator: FEE_DENOMINATOR,
+        admin_withdraw_fee_denominator: FEE_DENOMINATOR,
+        trade_fee_numerator: fees.trade_fee_numerator,
+        trade_fee_denominator: fees.trade_fee_denominator,
+        withdraw_fee_denominator: FEE_DENOMINATOR,
+        ..Default::default()
+    }
+}
+
 impl SwapCurve for StableSwap {
     fn swap(
         &self,
@@ -111,6 +217,50 @@ impl SwapCurve for StableSwap {
             new_swap_destination_amount,
         })
     }
+
+    /// dy / dx of the invariant on the upscaled amounts, 2A(x + y) + D = 2AD + D^3 / 4xy, converted back to the token amounts
+    fn marginal_price(
+        &self,
+        swap_source_amount: u64,
+        swap_destination_amount: u64,
+        trade_direction: TradeDirection,
+    ) -> Option<f64> {
+        let (upscaled_swap_source_amount, upscaled_swap_destination_amount) = match trade_direction
+        {
+            TradeDirection::AtoB => (
+                self.upscale_token_a(swap_source_amount.into())?,
+                self.upscale_token_b(swap_destination_amount.into())?,
+            ),
+            TradeDirection::BtoA => (
+                self.upscale_token_b(swap_source_amount.into())?,
+                self.upscale_token_a(swap_destination_amount.into())?,
+            ),
+        };
+        if upscaled_swap_source_amount == 0 || upscaled_swap_destination_amount == 0 {
+            return None;
+        }
+
+        let saber_stable_swap: SaberStableSwap = self.into();
+        let d = saber_stable_swap.compute_d(
+            upscaled_swap_source_amount,
+            upscaled_swap_destination_amount,
+        )? as f64;
+
+        let x = upscaled_swap_source_amount as f64;
+        let y = upscaled_swap_destination_amount as f64;
+        // amp * n
+        let leverage = self.amp as f64 * 2.0;
+        // D^3 / 4xy
+        let d_p = d / (x * 2.0) * d / (y * 2.0) * d;
+        let upscaled_price = (leverage + d_p / x) / (leverage + d_p / y);
+
+        // Upscaled amount of one source, and destination token
+        let source_factor = upscaled_swap_source_amount as f64 / swap_source_amount as f64;
+        let destination_factor =
+            upscaled_swap_destination_amount as f64 / swap_destination_amount as f64;
+
+        Some(upscaled_price * source_factor / destination_factor)
+    }
 }
 
 impl From<&StableSwap> for SaberStableSwap {
@@ -0,0 +1,270 @@
+//! Impermanent loss, and LP PnL.
+//!
+//! Trade fees stay in the pool, which grow the virtual price of the LP token, D / lp_supply.
+//! For depeg pools, the virtual price also grow with the base virtual price of the staking token (LST yield), which is not a fee.
+//! The growth since entry is split by the virtual price at the entry base virtual price, fee_virtual_price:
+//!     fee = entitlement * (fee_virtual_price - entry_virtual_price) / virtual_price
+//!     yield = entitlement * (virtual_price - fee_virtual_price) / virtual_price
+//! where entitlement is the current entitlement of the LP amount at entry, including the LP claimed as fee from the lock escrow.
+//! Impermanent loss compares the value excluding fee against holding the entry entitlement:
+//!     impermanent_loss = value_without_fee / hodl_value - 1
+use crate::depeg::update_base_virtual_price;
+use crate::math::compute_d;
+use crate::{compute_pool_tokens, QuoteData, VaultInfo};
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::state::{CurveType, LockEscrow};
+use serde::Serialize;
+
+/// LP position of a user
+pub enum LpPosition {
+    /// LP token held by the user
+    Wallet(u64),
+    /// LP token locked in the lock escrow. Total locked amount is reduced by the LP claimed as fee.
+    LockEscrow(LockEscrow),
+}
+
+/// Position, and pool state when the position was entered
+#[derive(Debug, Clone, Copy)]
+pub struct LpEntry {
+    /// LP amount of the position at entry. For lock escrow, the LP amount locked.
+    pub lp_amount: u64,
+    /// Token A amount held by the pool
+    pub token_a_amount: u64,
+    /// Token B amount held by the pool
+    pub token_b_amount: u64,
+    /// Pool LP mint supply
+    pub lp_supply: u64,
+    /// Depeg base virtual price of the pool. Ignored for non depeg pool.
+    pub base_virtual_price: u64,
+}
+
+/// PnL of the LP position. Values are in the same quote currency as the given token prices.
+#[derive(Debug, Clone, Serialize)]
+pub struct LpPnl {
+    /// Current LP amount of the position
+    pub lp_amount: u64,
+    /// LP amount of the position at entry
+    pub entry_lp_amount: u64,
+    /// Current token A entitlement
+    pub token_a_amount: u64,
+    /// Current token B entitlement
+    pub token_b_amount: u64,
+    /// Token A entitlement at entry
+    pub entry_token_a_amount: u64,
+    /// Token B entitlement at entry
+    pub entry_token_b_amount: u64,
+    /// Current virtual price of the LP token
+    pub virtual_price: f64,
+    /// Current virtual price of the LP token, at the entry base virtual price. Equals to virtual price for non depeg pool.
+    pub fee_virtual_price: f64,
+    /// Virtual price of the LP token at entry
+    pub entry_virtual_price: f64,
+    /// Token A fee accrued since entry, including the claimed fee
+    pub fee_token_a_amount: u64,
+    /// Token B fee accrued since entry, including the claimed fee
+    pub fee_token_b_amount: u64,
+    /// Token A from the depeg base virtual price growth since entry
+    pub yield_token_a_amount: u64,
+    /// Token B from the depeg base virtual price growth since entry
+    pub yield_token_b_amount: u64,
+    /// Token A fee claimed from the lock escrow so far
+    pub claimed_fee_token_a_amount: u64,
+    /// Token B fee claimed from the lock escrow so far
+    pub claimed_fee_token_b_amount: u64,
+    /// Current value of the entitlement, and the claimed fee
+    pub value: f64,
+    /// Current value, excluding fee accrued
+    pub value_without_fee: f64,
+    /// Current value of the entry entitlement if it was held instead
+    pub hodl_value: f64,
+    /// value_without_fee / hodl_value - 1. 0.01 = 1%.
+    pub impermanent_loss: f64,
+    /// value - hodl_value
+    pub pnl_vs_hodl: f64,
+}
+
+fn get_share(amount: u64, lp_amount: u64, lp_supply: u64) -> Option<u64> {
+    u128::from(amount)
+        .checked_mul(lp_amount.into())?
+        .checked_div(lp_supply.into())?
+        .try_into()
+        .ok()
+}
+
+fn get_virtual_price(
+    curve_type: CurveType,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    lp_supply: u64,
+) -> Option<f64> {
+    Some(compute_d(curve_type, token_a_amount, token_b_amount)? as f64 / lp_supply as f64)
+}
+
+/// Curve type with the depeg base virtual price replaced
+fn with_base_virtual_price(curve_type: CurveType, base_virtual_price: u64) -> CurveType {
+    match curve_type {
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            mut depeg,
+            last_amp_updated_timestamp,
+        } if !depeg.depeg_type.is_none() => {
+            depeg.base_virtual_price = base_virtual_price;
+            CurveType::Stable {
+                amp,
+                token_multiplier,
+                depeg,
+                last_amp_updated_timestamp,
+            }
+        }
+        _ => curve_type,
+    }
+}
+
+/// Compute the token entitlement, fee, and yield accrued, and impermanent loss of the LP position.
+/// `lp_supply` is the current pool LP mint supply. Token prices are per atomic unit of the token.
+pub fn compute_lp_pnl(
+    quote_data: QuoteData,
+    lp_supply: u64,
+    position: LpPosition,
+    entry: LpEntry,
+    token_a_price: f64,
+    token_b_price: f64,
+) -> anyhow::Result<LpPnl> {
+    ensure!(lp_supply > 0 && entry.lp_supply > 0, "LP supply is zero");
+
+    let (lp_amount, claimed_fee_token_a_amount, claimed_fee_token_b_amount) = match position {
+        LpPosition::Wallet(lp_amount) => (lp_amount, 0, 0),
+        LpPosition::LockEscrow(lock_escrow) => (
+            lock_escrow.total_locked_amount,
+            lock_escrow.a_fee,
+            lock_escrow.b_fee,
+        ),
+    };
+
+    let QuoteData {
+        mut pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        stake_data,
+        ..
+    } = quote_data;
+
+    update_base_virtual_price(&mut pool, &clock, stake_data)?;
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let (pool_token_a_amount, pool_token_b_amount) = compute_pool_tokens(
+        current_time,
+        VaultInfo {
+            lp_amount: pool_vault_a_lp_token.amount,
+            lp_supply: vault_a_lp_mint.supply,
+            vault: vault_a,
+        },
+        VaultInfo {
+            lp_amount: pool_vault_b_lp_token.amount,
+            lp_supply: vault_b_lp_mint.supply,
+            vault: vault_b,
+        },
+    )?;
+
+    let token_a_amount = get_share(pool_token_a_amount, lp_amount, lp_supply)
+        .context("Fail to get token a amount")?;
+    let token_b_amount = get_share(pool_token_b_amount, lp_amount, lp_supply)
+        .context("Fail to get token b amount")?;
+
+    let entry_token_a_amount = get_share(entry.token_a_amount, entry.lp_amount, entry.lp_supply)
+        .context("Fail to get entry token a amount")?;
+    let entry_token_b_amount = get_share(entry.token_b_amount, entry.lp_amount, entry.lp_supply)
+        .context("Fail to get entry token b amount")?;
+
+    // Current entitlement of the LP amount at entry, including the LP claimed as fee
+    let entry_lp_token_a_amount = get_share(pool_token_a_amount, entry.lp_amount, lp_supply)
+        .context("Fail to get token a amount")?;
+    let entry_lp_token_b_amount = get_share(pool_token_b_amount, entry.lp_amount, lp_supply)
+        .context("Fail to get token b amount")?;
+
+    let entry_curve_type = with_base_virtual_price(pool.curve_type, entry.base_virtual_price);
+
+    let virtual_price = get_virtual_price(
+        pool.curve_type,
+        pool_token_a_amount,
+        pool_token_b_amount,
+        lp_supply,
+    )
+    .context("Fail to get virtual price")?;
+
+    let fee_virtual_price = get_virtual_price(
+        entry_curve_type,
+        pool_token_a_amount,
+        pool_token_b_amount,
+        lp_supply,
+    )
+    .context("Fail to get fee virtual price")?;
+
+    let entry_virtual_price = get_virtual_price(
+        entry_curve_type,
+        entry.token_a_amount,
+        entry.token_b_amount,
+        entry.lp_supply,
+    )
+    .context("Fail to get entry virtual price")?;
+
+    // Portion of the entitlement which came from fee, and yield. Virtual price never decrease by swapping.
+    let fee_ratio = (fee_virtual_price - entry_virtual_price).max(0.0) / virtual_price;
+    let yield_ratio = (virtual_price - fee_virtual_price).max(0.0) / virtual_price;
+
+    let fee_token_a_amount = (entry_lp_token_a_amount as f64 * fee_ratio) as u64;
+    let fee_token_b_amount = (entry_lp_token_b_amount as f64 * fee_ratio) as u64;
+    let yield_token_a_amount = (entry_lp_token_a_amount as f64 * yield_ratio) as u64;
+    let yield_token_b_amount = (entry_lp_token_b_amount as f64 * yield_ratio) as u64;
+
+    let total_token_a_amount = token_a_amount
+        .checked_add(claimed_fee_token_a_amount)
+        .context("Fail to add claimed fee token a amount")?;
+    let total_token_b_amount = token_b_amount
+        .checked_add(claimed_fee_token_b_amount)
+        .context("Fail to add claimed fee token b amount")?;
+
+    let value =
+        total_token_a_amount as f64 * token_a_price + total_token_b_amount as f64 * token_b_price;
+    let fee_value =
+        fee_token_a_amount as f64 * token_a_price + fee_token_b_amount as f64 * token_b_price;
+    let value_without_fee = value - fee_value;
+    let hodl_value =
+        entry_token_a_amount as f64 * token_a_price + entry_token_b_amount as f64 * token_b_price;
+
+    let impermanent_loss = if hodl_value > 0.0 {
+        value_without_fee / hodl_value - 1.0
+    } else {
+        0.0
+    };
+
+    Ok(LpPnl {
+        lp_amount,
+        entry_lp_amount: entry.lp_amount,
+        token_a_amount,
+        token_b_amount,
+        entry_token_a_amount,
+        entry_token_b_amount,
+        virtual_price,
+        fee_virtual_price,
+        entry_virtual_price,
+        fee_token_a_amount,
+        fee_token_b_amount,
+        yield_token_a_amount,
+        yield_token_b_amount,
+        claimed_fee_token_a_amount,
+        claimed_fee_token_b_amount,
+        value,
+        value_without_fee,
+        hodl_value,
+        impermanent_loss,
+        pnl_vs_hodl: value - hodl_value,
+    })
+}
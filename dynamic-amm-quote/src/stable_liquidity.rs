@@ -0,0 +1,144 @@
+//! Quote of the stable swap pool imbalance deposit, and single side withdraw.
+//!
+//! Both follow the saber stable swap curve used by the program, where the imbalance part is charged half of the pool trade fee.
+//! Quotes are computed with the saber integer math on the upscaled token amounts, caller should apply a slippage on top of them.
+use crate::depeg::update_base_virtual_price;
+use crate::math::{compute_imbalance_deposit, compute_single_side_withdraw};
+use crate::{compute_pool_tokens, QuoteData, VaultInfo};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::state::{CurveType, Pool};
+
+/// Denominator of the slippage bps
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Minimum amount out after `slippage_bps` of slippage from the quote
+pub fn apply_slippage(amount: u64, slippage_bps: u64) -> anyhow::Result<u64> {
+    ensure!(
+        slippage_bps <= BPS_DENOMINATOR,
+        "Slippage bps > {}",
+        BPS_DENOMINATOR
+    );
+    let amount = u128::from(amount) * u128::from(BPS_DENOMINATOR - slippage_bps)
+        / u128::from(BPS_DENOMINATOR);
+    Ok(amount.try_into()?)
+}
+
+fn ensure_stable_pool(pool: &Pool) -> anyhow::Result<()> {
+    ensure!(
+        !matches!(pool.curve_type, CurveType::ConstantProduct),
+        "Constant product pool only support balanced liquidity"
+    );
+    Ok(())
+}
+
+fn get_pool_tokens(quote_data: QuoteData) -> anyhow::Result<(Pool, u64, u64)> {
+    let QuoteData {
+        mut pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        stake_data,
+        ..
+    } = quote_data;
+
+    update_base_virtual_price(&mut pool, &clock, stake_data)?;
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let (pool_token_a_amount, pool_token_b_amount) = compute_pool_tokens(
+        current_time,
+        VaultInfo {
+            lp_amount: pool_vault_a_lp_token.amount,
+            lp_supply: vault_a_lp_mint.supply,
+            vault: vault_a,
+        },
+        VaultInfo {
+            lp_amount: pool_vault_b_lp_token.amount,
+            lp_supply: vault_b_lp_mint.supply,
+            vault: vault_b,
+        },
+    )?;
+
+    Ok((pool, pool_token_a_amount, pool_token_b_amount))
+}
+
+/// Compute the LP amount minted by depositing token A, and token B in any ratio to a stable swap pool.
+/// `lp_supply` is the current pool LP mint supply.
+pub fn compute_imbalance_deposit_quote(
+    quote_data: QuoteData,
+    lp_supply: u64,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> anyhow::Result<u64> {
+    ensure_stable_pool(&quote_data.pool)?;
+    ensure!(
+        token_a_amount > 0 || token_b_amount > 0,
+        "Deposit amount is zero"
+    );
+
+    let (pool, pool_token_a_amount, pool_token_b_amount) = get_pool_tokens(quote_data)?;
+
+    compute_imbalance_deposit(
+        pool.curve_type,
+        token_a_amount,
+        token_b_amount,
+        pool_token_a_amount,
+        pool_token_b_amount,
+        lp_supply,
+        &pool.fees,
+    )
+    .context("Fail to compute imbalance deposit")
+}
+
+/// Compute the `out_token_mint` amount received by burning `pool_token_amount` LP of a stable swap pool.
+/// `lp_supply` is the current pool LP mint supply.
+pub fn compute_single_side_withdraw_quote(
+    quote_data: QuoteData,
+    lp_supply: u64,
+    out_token_mint: Pubkey,
+    pool_token_amount: u64,
+) -> anyhow::Result<u64> {
+    ensure_stable_pool(&quote_data.pool)?;
+    ensure!(
+        out_token_mint == quote_data.pool.token_a_mint
+            || out_token_mint == quote_data.pool.token_b_mint,
+        "Out token mint not matches with pool token mints"
+    );
+    ensure!(pool_token_amount > 0, "Pool token amount is zero");
+    ensure!(
+        pool_token_amount <= lp_supply,
+        "Pool token amount > LP supply"
+    );
+
+    let is_token_a = out_token_mint == quote_data.pool.token_a_mint;
+    let out_vault_token_amount = if is_token_a {
+        quote_data.vault_a_token.amount
+    } else {
+        quote_data.vault_b_token.amount
+    };
+
+    let (pool, pool_token_a_amount, pool_token_b_amount) = get_pool_tokens(quote_data)?;
+
+    let out_amount = compute_single_side_withdraw(
+        pool.curve_type,
+        pool_token_amount,
+        lp_supply,
+        pool_token_a_amount,
+        pool_token_b_amount,
+        is_token_a,
+        &pool.fees,
+    )
+    .context("Fail to compute single side withdraw")?;
+
+    ensure!(
+        out_amount < out_vault_token_amount,
+        "Out amount > vault reserve"
+    );
+
+    Ok(out_amount)
+}
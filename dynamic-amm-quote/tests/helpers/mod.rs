@@ -0,0 +1,185 @@
+//! Quote data of synthetic pools, where the vaults have no locked profit, and the pool owns all vault LP,
+//! and of the mainnet pool snapshot in tests/fixtures/accounts.
+#![allow(dead_code)]
+use anchor_lang::__private::base64;
+use anchor_lang::prelude::Clock;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState, Mint as SplMint};
+use anchor_spl::token::{Mint, TokenAccount};
+use dynamic_amm_quote::depeg::marinade;
+use dynamic_amm_quote::{compute_pool_tokens, QuoteData, VaultInfo};
+use prog_dynamic_amm::constants::fee::FEE_DENOMINATOR;
+use prog_dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, TokenMultiplier};
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+/// Unix timestamp of the quote data clock
+pub const CURRENT_TIME: i64 = 1_700_000_000;
+
+/// SOL / mSOL Marinade depeg stable pool of the fixture accounts
+pub const FIXTURE_POOL: Pubkey =
+    solana_sdk::pubkey!("HcjZvfeSNJbNkfLD4eEcRBr96AD3w1GpmMppaeRZf7ur");
+
+pub fn token_account(amount: u64) -> TokenAccount {
+    let account = Account {
+        amount,
+        state: AccountState::Initialized,
+        ..Default::default()
+    };
+    let mut data = [0u8; Account::LEN];
+    Account::pack(account, &mut data).unwrap();
+    TokenAccount::try_deserialize_unchecked(&mut data.as_ref()).unwrap()
+}
+
+pub fn mint(supply: u64) -> Mint {
+    let mint = SplMint {
+        supply,
+        is_initialized: true,
+        ..Default::default()
+    };
+    let mut data = [0u8; SplMint::LEN];
+    SplMint::pack(mint, &mut data).unwrap();
+    Mint::try_deserialize_unchecked(&mut data.as_ref()).unwrap()
+}
+
+fn vault(total_amount: u64) -> Vault {
+    let data = vec![0u8; 8 + std::mem::size_of::<Vault>()];
+    let mut vault = Vault::try_deserialize_unchecked(&mut data.as_ref()).unwrap();
+    vault.total_amount = total_amount;
+    vault
+}
+
+/// Constant product pool holding `token_a_amount`, and `token_b_amount`, with `trade_fee_bps` trade fee
+pub fn get_constant_product_quote_data(
+    token_a_amount: u64,
+    token_b_amount: u64,
+    trade_fee_bps: u64,
+) -> QuoteData {
+    let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+    let mut pool = Pool::try_deserialize_unchecked(&mut data.as_ref()).unwrap();
+    pool.enabled = true;
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.fees.trade_fee_numerator = trade_fee_bps * FEE_DENOMINATOR / 10_000;
+    pool.fees.trade_fee_denominator = FEE_DENOMINATOR;
+    pool.fees.protocol_trade_fee_denominator = FEE_DENOMINATOR;
+
+    QuoteData {
+        pool,
+        vault_a: vault(token_a_amount),
+        vault_b: vault(token_b_amount),
+        pool_vault_a_lp_token: token_account(token_a_amount),
+        pool_vault_b_lp_token: token_account(token_b_amount),
+        vault_a_lp_mint: mint(token_a_amount),
+        vault_b_lp_mint: mint(token_b_amount),
+        vault_a_token: token_account(token_a_amount),
+        vault_b_token: token_account(token_b_amount),
+        clock: Clock {
+            unix_timestamp: CURRENT_TIME,
+            ..Default::default()
+        },
+        stake_data: HashMap::new(),
+    }
+}
+
+/// Stable pool of two tokens with the same decimals. `base_virtual_price` is the depeg virtual price of token B,
+/// cached at the quote data clock, or None for a regular stable pool.
+pub fn get_stable_quote_data(
+    token_a_amount: u64,
+    token_b_amount: u64,
+    trade_fee_bps: u64,
+    amp: u64,
+    base_virtual_price: Option<u64>,
+) -> QuoteData {
+    let mut quote_data =
+        get_constant_product_quote_data(token_a_amount, token_b_amount, trade_fee_bps);
+    let depeg = match base_virtual_price {
+        Some(base_virtual_price) => Depeg {
+            base_virtual_price,
+            base_cache_updated: CURRENT_TIME as u64,
+            depeg_type: DepegType::Marinade,
+        },
+        None => Depeg::default(),
+    };
+    quote_data.pool.curve_type = CurveType::Stable {
+        amp,
+        token_multiplier: TokenMultiplier {
+            token_a_multiplier: 1,
+            token_b_multiplier: 1,
+            precision_factor: 9,
+        },
+        depeg,
+        last_amp_updated_timestamp: 0,
+    };
+    quote_data
+}
+
+fn get_fixture_account_data(key: Pubkey) -> Vec<u8> {
+    let path = format!(
+        "{}/tests/fixtures/accounts/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        key
+    );
+    let keyed_account: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let data = keyed_account["account"]["data"][0].as_str().unwrap();
+    base64::decode(data).unwrap()
+}
+
+fn get_fixture_account<T: AccountDeserialize>(key: Pubkey) -> T {
+    T::try_deserialize(&mut get_fixture_account_data(key).as_ref()).unwrap()
+}
+
+/// Quote data of the fixture pool, and its LP mint supply. The clock is at the depeg base virtual price cache time of the pool.
+pub fn get_fixture_quote_data() -> (QuoteData, u64) {
+    let pool: Pool = get_fixture_account(FIXTURE_POOL);
+    let vault_a: Vault = get_fixture_account(pool.a_vault);
+    let vault_b: Vault = get_fixture_account(pool.b_vault);
+    let lp_mint: Mint = get_fixture_account(pool.lp_mint);
+
+    let CurveType::Stable { depeg, .. } = pool.curve_type else {
+        panic!("Fixture pool must be stable");
+    };
+
+    let quote_data = QuoteData {
+        pool_vault_a_lp_token: get_fixture_account(pool.a_vault_lp),
+        pool_vault_b_lp_token: get_fixture_account(pool.b_vault_lp),
+        vault_a_lp_mint: get_fixture_account(vault_a.lp_mint),
+        vault_b_lp_mint: get_fixture_account(vault_b.lp_mint),
+        vault_a_token: get_fixture_account(vault_a.token_vault),
+        vault_b_token: get_fixture_account(vault_b.token_vault),
+        clock: Clock {
+            unix_timestamp: depeg.base_cache_updated as i64,
+            ..Default::default()
+        },
+        stake_data: HashMap::from([(
+            marinade::stake::ID,
+            get_fixture_account_data(marinade::stake::ID),
+        )]),
+        pool,
+        vault_a,
+        vault_b,
+    };
+
+    (quote_data, lp_mint.supply)
+}
+
+/// Token A, and token B amount held by the pool, excluding vault locked profit
+pub fn get_pool_tokens(quote_data: &QuoteData) -> (u64, u64) {
+    compute_pool_tokens(
+        quote_data.clock.unix_timestamp as u64,
+        VaultInfo {
+            lp_amount: quote_data.pool_vault_a_lp_token.amount,
+            lp_supply: quote_data.vault_a_lp_mint.supply,
+            vault: quote_data.vault_a.clone(),
+        },
+        VaultInfo {
+            lp_amount: quote_data.pool_vault_b_lp_token.amount,
+            lp_supply: quote_data.vault_b_lp_mint.supply,
+            vault: quote_data.vault_b.clone(),
+        },
+    )
+    .unwrap()
+}
@@ -0,0 +1,192 @@
+mod helpers;
+
+use dynamic_amm_quote::arbitrage::find_arbitrage_opportunities;
+use dynamic_amm_quote::compute_quote;
+use dynamic_amm_quote::QuoteData;
+use helpers::*;
+use solana_sdk::pubkey::Pubkey;
+
+/// Two constant product pools of the same token pair
+fn get_pools(
+    (token_a_amount_0, token_b_amount_0): (u64, u64),
+    (token_a_amount_1, token_b_amount_1): (u64, u64),
+) -> Vec<(Pubkey, QuoteData)> {
+    let quote_data_0 = get_constant_product_quote_data(token_a_amount_0, token_b_amount_0, 25);
+    let mut quote_data_1 = get_constant_product_quote_data(token_a_amount_1, token_b_amount_1, 25);
+    quote_data_1.pool.token_a_mint = quote_data_0.pool.token_a_mint;
+    quote_data_1.pool.token_b_mint = quote_data_0.pool.token_b_mint;
+
+    vec![
+        (Pubkey::new_unique(), quote_data_0),
+        (Pubkey::new_unique(), quote_data_1),
+    ]
+}
+
+fn get_cycle_profit(pools: &[(Pubkey, QuoteData)], token_mint: Pubkey, in_amount: u64) -> i128 {
+    let mut amount = in_amount;
+    let mut in_token_mint = token_mint;
+    for (_, quote_data) in pools {
+        amount = compute_quote(in_token_mint, amount, quote_data.clone())
+            .unwrap()
+            .out_amount;
+        in_token_mint = if in_token_mint == quote_data.pool.token_a_mint {
+            quote_data.pool.token_b_mint
+        } else {
+            quote_data.pool.token_a_mint
+        };
+    }
+    i128::from(amount) - i128::from(in_amount)
+}
+
+#[test]
+fn test_two_pools_arbitrage() {
+    // Token A is priced 1 in pool 0, and 1.1 in pool 1
+    let pools = get_pools(
+        (1_000_000_000, 1_000_000_000),
+        (1_000_000_000, 1_100_000_000),
+    );
+    let token_a_mint = pools[0].1.pool.token_a_mint;
+
+    let opportunities = find_arbitrage_opportunities(&pools, token_b_mint(&pools), 100_000_000, 0);
+    assert_eq!(opportunities.len(), 1);
+
+    let opportunity = &opportunities[0];
+    assert_eq!(opportunity.legs.len(), 2);
+    // Buy token A from the cheaper pool 0, and sell to pool 1
+    assert_eq!(opportunity.legs[0].pool, pools[0].0);
+    assert_eq!(opportunity.legs[0].out_token_mint, token_a_mint);
+    assert_eq!(opportunity.legs[1].pool, pools[1].0);
+    assert_eq!(
+        opportunity.profit,
+        opportunity.out_amount - opportunity.in_amount
+    );
+    assert_eq!(
+        i128::from(opportunity.profit),
+        get_cycle_profit(&pools, token_b_mint(&pools), opportunity.in_amount)
+    );
+
+    // Sized to the optimum
+    for in_amount in (1..100).map(|i| i * 1_000_000) {
+        assert!(
+            i128::from(opportunity.profit)
+                >= get_cycle_profit(&pools, token_b_mint(&pools), in_amount)
+        );
+    }
+}
+
+#[test]
+fn test_optimum_capped_by_max_in_amount() {
+    let pools = get_pools(
+        (1_000_000_000, 1_000_000_000),
+        (1_000_000_000, 1_100_000_000),
+    );
+
+    let opportunities = find_arbitrage_opportunities(&pools, token_b_mint(&pools), 1_000_000, 0);
+    let opportunity = &opportunities[0];
+    assert!(opportunity.in_amount <= 1_000_000);
+    assert!(
+        i128::from(opportunity.profit) >= get_cycle_profit(&pools, token_b_mint(&pools), 1_000_000)
+    );
+}
+
+#[test]
+fn test_no_arbitrage() {
+    // Price difference is within the trade fee of both pools
+    let pools = get_pools(
+        (1_000_000_000, 1_000_000_000),
+        (1_000_000_000, 1_002_000_000),
+    );
+    assert!(find_arbitrage_opportunities(&pools, token_b_mint(&pools), 100_000_000, 0).is_empty());
+
+    // Profit below the minimum profit
+    let pools = get_pools(
+        (1_000_000_000, 1_000_000_000),
+        (1_000_000_000, 1_100_000_000),
+    );
+    assert!(
+        find_arbitrage_opportunities(&pools, token_b_mint(&pools), 100_000_000, u64::MAX / 2)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_failed_quote_shrink_the_search() {
+    let mut pools = get_pools(
+        (1_000_000_000, 1_000_000_000),
+        (1_000_000_000, 1_100_000_000),
+    );
+    // Swap out of pool 1 fails when the out amount exceed the token B reserve of its vault
+    let reserve = 30_000_000;
+    pools[1].1.vault_b_token = token_account(reserve);
+    let token_b_mint = token_b_mint(&pools);
+
+    let quote_out_amount = |in_amount: u64| {
+        let a_amount = compute_quote(token_b_mint, in_amount, pools[0].1.clone())
+            .ok()?
+            .out_amount;
+        compute_quote(pools[0].1.pool.token_a_mint, a_amount, pools[1].1.clone())
+            .ok()
+            .map(|result| result.out_amount)
+    };
+    assert!(quote_out_amount(reserve).is_none());
+
+    // Both of the first probes fail, the feasible sizes below them are still searched
+    let opportunities = find_arbitrage_opportunities(&pools, token_b_mint, 1_000_000_000_000, 0);
+    assert_eq!(opportunities.len(), 1);
+
+    let opportunity = &opportunities[0];
+    assert!(opportunity.out_amount < reserve);
+    for in_amount in (1..30).map(|i| i * 1_000_000) {
+        if let Some(out_amount) = quote_out_amount(in_amount) {
+            assert!(
+                i128::from(opportunity.profit) >= i128::from(out_amount) - i128::from(in_amount)
+            );
+        }
+    }
+}
+
+fn token_b_mint(pools: &[(Pubkey, QuoteData)]) -> Pubkey {
+    pools[0].1.pool.token_b_mint
+}
+
+#[test]
+fn test_fixture_pool_arbitrage() {
+    // mSOL is priced 1 SOL in the constant product pool, and at the Marinade virtual price in the fixture pool
+    let (fixture_quote_data, _) = get_fixture_quote_data();
+    let sol_mint = fixture_quote_data.pool.token_a_mint;
+    let msol_mint = fixture_quote_data.pool.token_b_mint;
+
+    let mut quote_data = get_constant_product_quote_data(1_000_000_000_000, 1_000_000_000_000, 25);
+    quote_data.pool.token_a_mint = sol_mint;
+    quote_data.pool.token_b_mint = msol_mint;
+
+    let pools = vec![
+        (Pubkey::new_unique(), quote_data),
+        (FIXTURE_POOL, fixture_quote_data.clone()),
+    ];
+
+    let opportunities = find_arbitrage_opportunities(&pools, sol_mint, 100_000_000_000, 0);
+    assert_eq!(opportunities.len(), 1);
+
+    // Buy mSOL from the constant product pool, and sell to the fixture pool
+    let opportunity = &opportunities[0];
+    assert_eq!(opportunity.legs.len(), 2);
+    assert_eq!(opportunity.legs[0].pool, pools[0].0);
+    assert_eq!(opportunity.legs[0].out_token_mint, msol_mint);
+    assert_eq!(opportunity.legs[1].pool, FIXTURE_POOL);
+    assert_eq!(
+        opportunity.legs[1].in_amount,
+        opportunity.legs[0].out_amount
+    );
+    assert_eq!(
+        opportunity.legs[1].out_amount,
+        compute_quote(msol_mint, opportunity.legs[1].in_amount, fixture_quote_data)
+            .unwrap()
+            .out_amount
+    );
+    assert!(opportunity.profit > 0);
+    assert_eq!(
+        i128::from(opportunity.profit),
+        get_cycle_profit(&pools, sol_mint, opportunity.in_amount)
+    );
+}
@@ -0,0 +1,107 @@
+mod helpers;
+
+use dynamic_amm_quote::depth::compute_liquidity_depth;
+use helpers::*;
+
+#[test]
+fn test_constant_product_spot_price() {
+    let quote_data = get_constant_product_quote_data(1_000_000_000, 2_000_000_000, 25);
+    let depth = compute_liquidity_depth(quote_data, &[], 0).unwrap();
+
+    assert_eq!(depth.a_to_b.spot_price, 2.0);
+    assert_eq!(depth.b_to_a.spot_price, 0.5);
+}
+
+#[test]
+fn test_constant_product_depth() {
+    let quote_data = get_constant_product_quote_data(1_000_000_000, 2_000_000_000, 25);
+    let depth = compute_liquidity_depth(quote_data, &[100], 0).unwrap();
+
+    // Marginal price y / x move by 1% when the reserve grow by 1 / sqrt(0.99) - 1, before 0.25% trade fee
+    let level = depth.a_to_b.levels[0].as_ref().unwrap();
+    let expected_in_amount = 1_000_000_000.0 * (1.0 / 0.99f64.sqrt() - 1.0) / 0.9975;
+    assert!(
+        (level.in_amount as f64 / expected_in_amount - 1.0).abs() < 0.0001,
+        "in amount {}",
+        level.in_amount
+    );
+}
+
+#[test]
+fn test_stable_spot_price() {
+    let quote_data = get_stable_quote_data(1_000_000_000, 1_000_000_000, 25, 100, None);
+    let depth = compute_liquidity_depth(quote_data, &[], 0).unwrap();
+
+    assert_eq!(depth.a_to_b.spot_price, 1.0);
+    assert_eq!(depth.b_to_a.spot_price, 1.0);
+
+    // 1 token B = 1.2 token A, balanced by value
+    let quote_data = get_stable_quote_data(1_200_000_000, 1_000_000_000, 25, 100, Some(1_200_000));
+    let depth = compute_liquidity_depth(quote_data, &[], 0).unwrap();
+
+    assert!((depth.a_to_b.spot_price - 1.0 / 1.2).abs() < 1e-9);
+    assert!((depth.b_to_a.spot_price - 1.2).abs() < 1e-9);
+}
+
+#[test]
+fn test_stable_deeper_than_constant_product() {
+    let constant_product_depth = compute_liquidity_depth(
+        get_constant_product_quote_data(1_000_000_000, 1_000_000_000, 25),
+        &[100],
+        0,
+    )
+    .unwrap();
+    let stable_depth = compute_liquidity_depth(
+        get_stable_quote_data(1_000_000_000, 1_000_000_000, 25, 100, None),
+        &[100],
+        0,
+    )
+    .unwrap();
+
+    let constant_product_level = constant_product_depth.a_to_b.levels[0].as_ref().unwrap();
+    let stable_level = stable_depth.a_to_b.levels[0].as_ref().unwrap();
+    assert!(stable_level.in_amount > constant_product_level.in_amount * 10);
+}
+
+#[test]
+fn test_price_samples() {
+    let quote_data = get_stable_quote_data(1_000_000_000, 1_000_000_000, 25, 100, None);
+    let depth = compute_liquidity_depth(quote_data, &[], 10).unwrap();
+
+    let samples = &depth.a_to_b.samples;
+    assert_eq!(samples.len(), 10);
+    for (sample, next_sample) in samples.iter().zip(samples.iter().skip(1)) {
+        assert!(next_sample.in_amount > sample.in_amount);
+        assert!(next_sample.marginal_price < sample.marginal_price);
+        assert!(next_sample.price_impact > sample.price_impact);
+    }
+}
+
+#[test]
+fn test_fixture_pool_depth() {
+    let (quote_data, _) = get_fixture_quote_data();
+    let (token_a_amount, token_b_amount) = get_pool_tokens(&quote_data);
+    let depth = compute_liquidity_depth(quote_data, &[10, 50, 100], 10).unwrap();
+
+    assert_eq!(depth.token_a_amount, token_a_amount);
+    assert_eq!(depth.token_b_amount, token_b_amount);
+
+    // mSOL is in excess, so it is priced slightly below the cached Marinade virtual price, 1.198483 SOL
+    assert!(
+        depth.b_to_a.spot_price < 1.198483 && depth.b_to_a.spot_price > 1.19,
+        "spot price {}",
+        depth.b_to_a.spot_price
+    );
+    assert!((depth.a_to_b.spot_price * depth.b_to_a.spot_price - 1.0).abs() < 1e-6);
+
+    for side in [&depth.a_to_b, &depth.b_to_a] {
+        let levels: Vec<_> = side.levels.iter().flatten().collect();
+        for (level, next_level) in levels.iter().zip(levels.iter().skip(1)) {
+            assert!(next_level.in_amount > level.in_amount);
+            assert!(next_level.out_amount > level.out_amount);
+        }
+        for (sample, next_sample) in side.samples.iter().zip(side.samples.iter().skip(1)) {
+            assert!(next_sample.marginal_price < sample.marginal_price);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+mod helpers;
+
+use dynamic_amm_quote::fair_price::compute_lp_fair_price;
+use helpers::*;
+
+#[test]
+fn test_constant_product_fair_price() {
+    // D = sqrt(400 * 100) = 200
+    let quote_data = get_constant_product_quote_data(400_000_000, 100_000_000, 25);
+    let fair_price = compute_lp_fair_price(quote_data, 100_000_000, 1.0, 4.0).unwrap();
+
+    assert_eq!(fair_price.d, 200_000_000.0);
+    // 2 * D * sqrt(pA * pB)
+    assert_eq!(fair_price.fair_value, 800_000_000.0);
+    assert_eq!(fair_price.fair_lp_price, 8.0);
+    // At the fair reserves, spot value equals to the fair value
+    assert_eq!(fair_price.spot_value, 800_000_000.0);
+}
+
+#[test]
+fn test_constant_product_fair_price_resist_manipulation() {
+    // Same k, but token A is dumped into the pool
+    let quote_data = get_constant_product_quote_data(800_000_000, 50_000_000, 25);
+    let fair_price = compute_lp_fair_price(quote_data, 100_000_000, 1.0, 4.0).unwrap();
+
+    assert_eq!(fair_price.fair_value, 800_000_000.0);
+    assert_eq!(fair_price.spot_value, 1_000_000_000.0);
+}
+
+#[test]
+fn test_stable_fair_price() {
+    let quote_data = get_stable_quote_data(1_000_000_000, 1_000_000_000, 25, 100, None);
+    let fair_price = compute_lp_fair_price(quote_data, 2_000_000_000, 1.0, 0.99).unwrap();
+
+    // D * min(pA / fa, pB / fb), priced as the cheaper token
+    assert_eq!(fair_price.d, 2_000_000_000.0);
+    assert_eq!(fair_price.fair_value, 2_000_000_000.0 * 0.99);
+    assert_eq!(fair_price.spot_value, 1_990_000_000.0);
+}
+
+#[test]
+fn test_depeg_fair_price() {
+    // 1 token B = 1.2 token A, balanced by value. D is 2400 token A.
+    let quote_data = get_stable_quote_data(1_200_000_000, 1_000_000_000, 25, 100, Some(1_200_000));
+    let fair_price = compute_lp_fair_price(quote_data, 2_400_000_000, 1.0, 1.2).unwrap();
+
+    assert!((fair_price.d - 2_400_000_000.0).abs() <= 1.0);
+    assert!((fair_price.fair_value - 2_400_000_000.0).abs() <= 1.0);
+    assert!((fair_price.fair_lp_price - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_fair_price_exclude_locked_profit() {
+    let mut quote_data = get_constant_product_quote_data(500_000_000, 100_000_000, 25);
+    // 100 token A of vault A profit is not unlocked yet
+    quote_data
+        .vault_a
+        .locked_profit_tracker
+        .last_updated_locked_profit = 100_000_000;
+    quote_data.vault_a.locked_profit_tracker.last_report = CURRENT_TIME as u64;
+    quote_data
+        .vault_a
+        .locked_profit_tracker
+        .locked_profit_degradation = 1;
+
+    let fair_price = compute_lp_fair_price(quote_data, 100_000_000, 1.0, 4.0).unwrap();
+
+    assert_eq!(fair_price.token_a_amount, 400_000_000);
+    assert_eq!(fair_price.d, 200_000_000.0);
+    assert_eq!(fair_price.fair_value, 800_000_000.0);
+}
+
+#[test]
+fn test_fixture_pool_fair_price() {
+    let (quote_data, lp_supply) = get_fixture_quote_data();
+    let (token_a_amount, token_b_amount) = get_pool_tokens(&quote_data);
+    // mSOL priced at the cached Marinade virtual price
+    let fair_price = compute_lp_fair_price(quote_data, lp_supply, 1.0, 1.198483).unwrap();
+
+    assert_eq!(fair_price.token_a_amount, token_a_amount);
+    assert_eq!(fair_price.token_b_amount, token_b_amount);
+
+    // The pool is imbalanced, so D is slightly below the sum of the normalized reserves
+    assert!(fair_price.fair_value <= fair_price.spot_value);
+    assert!(
+        fair_price.fair_value > fair_price.spot_value * 0.999,
+        "fair value {}, spot value {}",
+        fair_price.fair_value,
+        fair_price.spot_value
+    );
+    assert_eq!(
+        fair_price.fair_lp_price,
+        fair_price.fair_value / lp_supply as f64
+    );
+    // Fee, and staking yield accrued since the pool launch
+    assert!(fair_price.fair_lp_price > 1.0);
+}
@@ -0,0 +1,105 @@
+mod helpers;
+
+use dynamic_amm_quote::lock_escrow::compute_lock_escrow_fee;
+use helpers::*;
+use prog_dynamic_amm::state::LockEscrow;
+
+const LP_PER_TOKEN_ONE: u128 = 1 << 64;
+
+fn get_lock_escrow(total_locked_amount: u64, lp_per_token: u128) -> LockEscrow {
+    LockEscrow {
+        total_locked_amount,
+        lp_per_token,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_depeg_pool_lock_escrow_fee() {
+    // 1 mSOL = 1.2 SOL, so the pool hold 2200 SOL worth of token, backing 2000 LP. Virtual price is 1.1.
+    let quote_data = get_stable_quote_data(
+        1_000_000_000_000,
+        1_000_000_000_000,
+        1,
+        1000,
+        Some(1_200_000),
+    );
+    let lock_escrow = get_lock_escrow(1_000_000_000, LP_PER_TOKEN_ONE);
+
+    let fee =
+        compute_lock_escrow_fee(quote_data, 2_000_000_000_000, &lock_escrow, u64::MAX).unwrap();
+
+    let virtual_price = fee.virtual_price / LP_PER_TOKEN_ONE as f64;
+    assert!(
+        (virtual_price - 1.1).abs() < 0.001,
+        "virtual price {}",
+        virtual_price
+    );
+
+    // 1 - 1 / 1.1 of the locked LP is fee
+    assert!(
+        fee.lp_amount > 90_000_000 && fee.lp_amount <= 90_909_090,
+        "fee {}",
+        fee.lp_amount
+    );
+    assert!(fee.token_a_amount > 0 && fee.token_b_amount > 0);
+}
+
+#[test]
+fn test_constant_product_pool_lock_escrow_fee() {
+    // D = sqrt(400 * 100) = 200, virtual price 200 / 100 = 2
+    let quote_data = get_constant_product_quote_data(400_000_000, 100_000_000, 25);
+    let lock_escrow = get_lock_escrow(10_000_000, LP_PER_TOKEN_ONE);
+
+    let fee =
+        compute_lock_escrow_fee(quote_data.clone(), 100_000_000, &lock_escrow, u64::MAX).unwrap();
+    assert_eq!(fee.virtual_price, 2.0 * LP_PER_TOKEN_ONE as f64);
+    assert_eq!(fee.lp_amount, 5_000_000);
+    assert_eq!(fee.token_a_amount, 20_000_000);
+    assert_eq!(fee.token_b_amount, 5_000_000);
+
+    // Capped by the escrow vault balance
+    let fee =
+        compute_lock_escrow_fee(quote_data.clone(), 100_000_000, &lock_escrow, 1_000_000).unwrap();
+    assert_eq!(fee.lp_amount, 1_000_000);
+
+    // No fee accrued since the last update
+    let lock_escrow = get_lock_escrow(10_000_000, 2 * LP_PER_TOKEN_ONE);
+    let fee = compute_lock_escrow_fee(quote_data, 100_000_000, &lock_escrow, u64::MAX).unwrap();
+    assert_eq!(fee.lp_amount, 0);
+}
+
+#[test]
+fn test_fixture_pool_lock_escrow_fee() {
+    let (quote_data, lp_supply) = get_fixture_quote_data();
+    let (token_a_amount, token_b_amount) = get_pool_tokens(&quote_data);
+    let total_locked_lp = quote_data.pool.total_locked_lp;
+
+    // Locked when the virtual price was 1
+    let lock_escrow = get_lock_escrow(total_locked_lp, LP_PER_TOKEN_ONE);
+    let fee =
+        compute_lock_escrow_fee(quote_data.clone(), lp_supply, &lock_escrow, u64::MAX).unwrap();
+
+    let virtual_price = fee.virtual_price / LP_PER_TOKEN_ONE as f64;
+    assert!(virtual_price > 1.0, "virtual price {}", virtual_price);
+    let expected_lp_amount = total_locked_lp as f64 * (1.0 - 1.0 / virtual_price);
+    assert!(
+        (fee.lp_amount as f64 - expected_lp_amount).abs() <= 1.0,
+        "fee {}",
+        fee.lp_amount
+    );
+    assert_eq!(
+        fee.token_a_amount as u128,
+        token_a_amount as u128 * fee.lp_amount as u128 / lp_supply as u128
+    );
+    assert_eq!(
+        fee.token_b_amount as u128,
+        token_b_amount as u128 * fee.lp_amount as u128 / lp_supply as u128
+    );
+
+    // Updated at the current virtual price, only the pending fee is claimable
+    let mut lock_escrow = get_lock_escrow(total_locked_lp, fee.virtual_price as u128);
+    lock_escrow.unclaimed_fee_pending = 1_000;
+    let fee = compute_lock_escrow_fee(quote_data, lp_supply, &lock_escrow, u64::MAX).unwrap();
+    assert_eq!(fee.lp_amount, 1_000);
+}
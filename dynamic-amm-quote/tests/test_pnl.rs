@@ -0,0 +1,197 @@
+mod helpers;
+
+use dynamic_amm_quote::pnl::{compute_lp_pnl, LpEntry, LpPosition};
+use helpers::*;
+use prog_dynamic_amm::state::LockEscrow;
+
+/// Entry of 10 LP in a constant product pool of 400 token A, and 100 token B, with 100 LP supply. Virtual price is 2.
+const ENTRY: LpEntry = LpEntry {
+    lp_amount: 10_000_000,
+    token_a_amount: 400_000_000,
+    token_b_amount: 100_000_000,
+    lp_supply: 100_000_000,
+    base_virtual_price: 0,
+};
+
+#[test]
+fn test_no_change_since_entry() {
+    let quote_data = get_constant_product_quote_data(400_000_000, 100_000_000, 25);
+    let pnl = compute_lp_pnl(
+        quote_data,
+        100_000_000,
+        LpPosition::Wallet(10_000_000),
+        ENTRY,
+        1.0,
+        4.0,
+    )
+    .unwrap();
+
+    assert_eq!(pnl.token_a_amount, 40_000_000);
+    assert_eq!(pnl.token_b_amount, 10_000_000);
+    assert_eq!(pnl.virtual_price, 2.0);
+    assert_eq!(pnl.fee_token_a_amount, 0);
+    assert_eq!(pnl.fee_token_b_amount, 0);
+    assert_eq!(pnl.impermanent_loss, 0.0);
+    assert_eq!(pnl.pnl_vs_hodl, 0.0);
+}
+
+#[test]
+fn test_fee_accrued() {
+    // Fee grow both reserves by 10%, virtual price is 2.2
+    let quote_data = get_constant_product_quote_data(440_000_000, 110_000_000, 25);
+    let pnl = compute_lp_pnl(
+        quote_data,
+        100_000_000,
+        LpPosition::Wallet(10_000_000),
+        ENTRY,
+        1.0,
+        4.0,
+    )
+    .unwrap();
+
+    assert_eq!(pnl.fee_token_a_amount, 4_000_000);
+    assert_eq!(pnl.fee_token_b_amount, 1_000_000);
+    assert_eq!(pnl.yield_token_a_amount, 0);
+    assert_eq!(pnl.value, 88_000_000.0);
+    assert_eq!(pnl.hodl_value, 80_000_000.0);
+    assert!(pnl.impermanent_loss.abs() < 1e-9);
+}
+
+#[test]
+fn test_impermanent_loss() {
+    // Token B price x4 with the same k
+    let quote_data = get_constant_product_quote_data(800_000_000, 50_000_000, 25);
+    let pnl = compute_lp_pnl(
+        quote_data,
+        100_000_000,
+        LpPosition::Wallet(10_000_000),
+        ENTRY,
+        1.0,
+        16.0,
+    )
+    .unwrap();
+
+    // 2 * sqrt(r) / (1 + r) - 1
+    assert_eq!(pnl.value, 160_000_000.0);
+    assert_eq!(pnl.hodl_value, 200_000_000.0);
+    assert!((pnl.impermanent_loss + 0.2).abs() < 1e-9);
+}
+
+#[test]
+fn test_lock_escrow_with_claimed_fee() {
+    // LP worth 4 token A, and 1 token B of fee was claimed from the 10 LP locked
+    let quote_data = get_constant_product_quote_data(440_000_000, 110_000_000, 25);
+    let lock_escrow = LockEscrow {
+        total_locked_amount: 9_090_910,
+        a_fee: 4_000_000,
+        b_fee: 1_000_000,
+        ..Default::default()
+    };
+    let pnl = compute_lp_pnl(
+        quote_data,
+        100_000_000,
+        LpPosition::LockEscrow(lock_escrow),
+        ENTRY,
+        1.0,
+        4.0,
+    )
+    .unwrap();
+
+    assert_eq!(pnl.lp_amount, 9_090_910);
+    assert_eq!(pnl.entry_lp_amount, 10_000_000);
+    assert_eq!(pnl.entry_token_a_amount, 40_000_000);
+    assert_eq!(pnl.entry_token_b_amount, 10_000_000);
+    assert_eq!(pnl.fee_token_a_amount, 4_000_000);
+    assert_eq!(pnl.fee_token_b_amount, 1_000_000);
+    assert_eq!(pnl.claimed_fee_token_a_amount, 4_000_000);
+    assert!((pnl.value - 88_000_000.0).abs() < 10.0);
+    assert!(pnl.impermanent_loss.abs() < 1e-6);
+}
+
+#[test]
+fn test_depeg_yield_is_not_fee() {
+    // Base virtual price grow from 1.1 to 1.2 without any swap
+    let quote_data = get_stable_quote_data(1_000_000_000, 1_000_000_000, 25, 1000, Some(1_200_000));
+    let entry = LpEntry {
+        lp_amount: 21_000_000,
+        token_a_amount: 1_000_000_000,
+        token_b_amount: 1_000_000_000,
+        lp_supply: 2_100_000_000,
+        base_virtual_price: 1_100_000,
+    };
+    let pnl = compute_lp_pnl(
+        quote_data,
+        2_100_000_000,
+        LpPosition::Wallet(21_000_000),
+        entry,
+        1.0,
+        1.2,
+    )
+    .unwrap();
+
+    assert_eq!(pnl.fee_virtual_price, pnl.entry_virtual_price);
+    assert_eq!(pnl.fee_token_a_amount, 0);
+    assert_eq!(pnl.fee_token_b_amount, 0);
+
+    // 1 - 2100 / 2200 of the entitlement is from the staking yield
+    let expected_yield_amount = 10_000_000.0 * (1.0 - 2100.0 / 2200.0);
+    assert!(
+        (pnl.yield_token_a_amount as f64 / expected_yield_amount - 1.0).abs() < 0.01,
+        "yield {}",
+        pnl.yield_token_a_amount
+    );
+    assert_eq!(pnl.yield_token_a_amount, pnl.yield_token_b_amount);
+}
+
+#[test]
+fn test_fixture_pool_pnl() {
+    let (quote_data, lp_supply) = get_fixture_quote_data();
+    let (token_a_amount, token_b_amount) = get_pool_tokens(&quote_data);
+    let lp_amount = quote_data.pool.total_locked_lp;
+
+    // Entered at the current pool state, and the cached Marinade virtual price
+    let entry = LpEntry {
+        lp_amount,
+        token_a_amount,
+        token_b_amount,
+        lp_supply,
+        base_virtual_price: 1_198_483,
+    };
+    let pnl = compute_lp_pnl(
+        quote_data.clone(),
+        lp_supply,
+        LpPosition::Wallet(lp_amount),
+        entry,
+        1.0,
+        1.198483,
+    )
+    .unwrap();
+
+    assert_eq!(pnl.token_a_amount, pnl.entry_token_a_amount);
+    assert_eq!(pnl.token_b_amount, pnl.entry_token_b_amount);
+    assert_eq!(pnl.virtual_price, pnl.entry_virtual_price);
+    assert_eq!(pnl.fee_token_a_amount, 0);
+    assert_eq!(pnl.yield_token_a_amount, 0);
+    assert_eq!(pnl.impermanent_loss, 0.0);
+
+    // Same reserves, entered at a lower Marinade virtual price. The growth is staking yield, not fee.
+    let entry = LpEntry {
+        base_virtual_price: 1_150_000,
+        ..entry
+    };
+    let pnl = compute_lp_pnl(
+        quote_data,
+        lp_supply,
+        LpPosition::Wallet(lp_amount),
+        entry,
+        1.0,
+        1.198483,
+    )
+    .unwrap();
+
+    assert!(pnl.virtual_price > pnl.entry_virtual_price);
+    assert_eq!(pnl.fee_virtual_price, pnl.entry_virtual_price);
+    assert_eq!(pnl.fee_token_a_amount, 0);
+    assert_eq!(pnl.fee_token_b_amount, 0);
+    assert!(pnl.yield_token_a_amount > 0 && pnl.yield_token_b_amount > 0);
+}
@@ -0,0 +1,163 @@
+mod helpers;
+
+use dynamic_amm_quote::stable_liquidity::{
+    apply_slippage, compute_imbalance_deposit_quote, compute_single_side_withdraw_quote,
+};
+use helpers::*;
+
+const RESERVE: u64 = 1_000_000_000_000;
+
+#[test]
+fn test_balanced_deposit_mint_proportional_lp() {
+    let quote_data = get_stable_quote_data(RESERVE, RESERVE, 25, 100, None);
+    let lp_supply = 2 * RESERVE;
+
+    // No imbalance, no fee
+    let lp_amount =
+        compute_imbalance_deposit_quote(quote_data, lp_supply, 1_000_000, 1_000_000).unwrap();
+    assert!(
+        lp_amount.abs_diff(2_000_000) <= 1,
+        "lp amount {}",
+        lp_amount
+    );
+}
+
+#[test]
+fn test_imbalance_deposit_charge_fee() {
+    let lp_supply = 2 * RESERVE;
+
+    let no_fee_lp_amount = compute_imbalance_deposit_quote(
+        get_stable_quote_data(RESERVE, RESERVE, 0, 100, None),
+        lp_supply,
+        10_000_000_000,
+        0,
+    )
+    .unwrap();
+    let lp_amount = compute_imbalance_deposit_quote(
+        get_stable_quote_data(RESERVE, RESERVE, 100, 100, None),
+        lp_supply,
+        10_000_000_000,
+        0,
+    )
+    .unwrap();
+
+    // Slightly below the deposit value due to the curve
+    assert!(no_fee_lp_amount < 10_000_000_000 && no_fee_lp_amount > 9_990_000_000);
+    // Both token amounts are off from the ideal balance by half of the deposit, which is charged half of the 1% trade fee
+    let fee = no_fee_lp_amount - lp_amount;
+    assert!(fee > 45_000_000 && fee < 55_000_000, "fee {}", fee);
+}
+
+#[test]
+fn test_single_side_withdraw() {
+    let quote_data = get_stable_quote_data(RESERVE, RESERVE, 25, 100, None);
+    let lp_supply = 2 * RESERVE;
+    let token_a_mint = quote_data.pool.token_a_mint;
+    let token_b_mint = quote_data.pool.token_b_mint;
+
+    let out_a_amount = compute_single_side_withdraw_quote(
+        quote_data.clone(),
+        lp_supply,
+        token_a_mint,
+        20_000_000_000,
+    )
+    .unwrap();
+    let out_b_amount =
+        compute_single_side_withdraw_quote(quote_data, lp_supply, token_b_mint, 20_000_000_000)
+            .unwrap();
+
+    // 1% of the pool, less the curve slippage, and fee
+    assert!(out_a_amount < 20_000_000_000 && out_a_amount > 19_900_000_000);
+    assert_eq!(out_a_amount, out_b_amount);
+}
+
+#[test]
+fn test_depeg_pool_single_side_withdraw() {
+    // 1 token B = 1.2 token A
+    let quote_data = get_stable_quote_data(RESERVE, RESERVE, 0, 1000, Some(1_200_000));
+    let lp_supply = 2 * RESERVE;
+    let token_a_mint = quote_data.pool.token_a_mint;
+    let token_b_mint = quote_data.pool.token_b_mint;
+
+    let out_a_amount = compute_single_side_withdraw_quote(
+        quote_data.clone(),
+        lp_supply,
+        token_a_mint,
+        1_000_000_000,
+    )
+    .unwrap();
+    let out_b_amount =
+        compute_single_side_withdraw_quote(quote_data, lp_supply, token_b_mint, 1_000_000_000)
+            .unwrap();
+
+    // LP is worth 1.1 token A
+    assert!(
+        out_a_amount.abs_diff(1_100_000_000) < 1_000_000,
+        "out a amount {}",
+        out_a_amount
+    );
+    let price = out_a_amount as f64 / out_b_amount as f64;
+    assert!((price - 1.2).abs() < 0.001, "price {}", price);
+}
+
+#[test]
+fn test_deposit_then_withdraw_no_profit() {
+    let lp_supply = 2 * RESERVE;
+    let quote_data = get_stable_quote_data(RESERVE, RESERVE, 25, 100, None);
+    let token_a_mint = quote_data.pool.token_a_mint;
+
+    let lp_amount =
+        compute_imbalance_deposit_quote(quote_data.clone(), lp_supply, 5_000_000_000, 0).unwrap();
+
+    let mut quote_data = get_stable_quote_data(RESERVE + 5_000_000_000, RESERVE, 25, 100, None);
+    quote_data.pool.token_a_mint = token_a_mint;
+    let out_amount = compute_single_side_withdraw_quote(
+        quote_data,
+        lp_supply + lp_amount,
+        token_a_mint,
+        lp_amount,
+    )
+    .unwrap();
+    assert!(out_amount < 5_000_000_000, "out amount {}", out_amount);
+}
+
+#[test]
+fn test_constant_product_pool_not_supported() {
+    let quote_data = get_constant_product_quote_data(RESERVE, RESERVE, 25);
+    let token_a_mint = quote_data.pool.token_a_mint;
+
+    assert!(compute_imbalance_deposit_quote(quote_data.clone(), RESERVE, 1, 0).is_err());
+    assert!(compute_single_side_withdraw_quote(quote_data, RESERVE, token_a_mint, 1).is_err());
+}
+
+#[test]
+fn test_apply_slippage() {
+    assert_eq!(apply_slippage(1_000_000, 50).unwrap(), 995_000);
+    assert_eq!(apply_slippage(1_000_000, 0).unwrap(), 1_000_000);
+    assert!(apply_slippage(1_000_000, 10_001).is_err());
+}
+
+#[test]
+fn test_fixture_pool_deposit_then_withdraw() {
+    let (quote_data, lp_supply) = get_fixture_quote_data();
+    let sol_mint = quote_data.pool.token_a_mint;
+
+    let lp_amount =
+        compute_imbalance_deposit_quote(quote_data.clone(), lp_supply, 1_000_000_000, 0).unwrap();
+    // LP is worth more than 1 SOL
+    assert!(
+        lp_amount < 1_000_000_000 && lp_amount > 800_000_000,
+        "lp amount {}",
+        lp_amount
+    );
+
+    let out_amount =
+        compute_single_side_withdraw_quote(quote_data, lp_supply + lp_amount, sol_mint, lp_amount)
+            .unwrap();
+    // Only lose the imbalance fee of both sides
+    assert!(
+        out_amount < 1_000_000_000 && out_amount > 999_000_000,
+        "out amount {}",
+        out_amount
+    );
+}
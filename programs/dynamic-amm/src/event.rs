@@ -5,6 +5,7 @@ use crate::state::PoolType;
 
 /// Add liquidity event
 #[event]
+#[derive(Debug, Clone)]
 pub struct AddLiquidity {
     /// LP amount user received upon add liquidity.
     pub lp_mint_amount: u64,
@@ -16,6 +17,7 @@ pub struct AddLiquidity {
 
 /// Remove liquidity event
 #[event]
+#[derive(Debug, Clone)]
 pub struct RemoveLiquidity {
     /// LP amount burned from user upon add remove liquidity.
     pub lp_unmint_amount: u64,
@@ -27,6 +29,7 @@ pub struct RemoveLiquidity {
 
 /// Bootstrap liquidity event
 #[event]
+#[derive(Debug, Clone)]
 pub struct BootstrapLiquidity {
     /// LP amount user received upon add liquidity.
     pub lp_mint_amount: u64,
@@ -40,6 +43,7 @@ pub struct BootstrapLiquidity {
 
 /// Swap event
 #[event]
+#[derive(Debug, Clone)]
 pub struct Swap {
     /// Token amount user deposited to the pool for token exchange.
     pub in_amount: u64,
@@ -55,6 +59,7 @@ pub struct Swap {
 
 /// Set pool fees event
 #[event]
+#[derive(Debug, Clone)]
 pub struct SetPoolFees {
     /// New trade fee numerator
     pub trade_fee_numerator: u64,
@@ -70,6 +75,7 @@ pub struct SetPoolFees {
 
 /// Pool info event
 #[event]
+#[derive(Debug, Clone)]
 pub struct PoolInfo {
     /// Total token A amount in the pool
     pub token_a_amount: u64,
@@ -83,6 +89,7 @@ pub struct PoolInfo {
 
 /// Transfer admin event
 #[event]
+#[derive(Debug, Clone)]
 pub struct TransferAdmin {
     /// Old admin of the pool
     pub admin: Pubkey,
@@ -94,6 +101,7 @@ pub struct TransferAdmin {
 
 /// Override curve param event
 #[event]
+#[derive(Debug, Clone)]
 pub struct OverrideCurveParam {
     /// The new amplification for stable curve
     pub new_amp: u64,
@@ -105,6 +113,7 @@ pub struct OverrideCurveParam {
 
 /// New pool created event
 #[event]
+#[derive(Debug, Clone)]
 pub struct PoolCreated {
     /// LP token mint of the pool
     pub lp_mint: Pubkey, //32
@@ -120,6 +129,7 @@ pub struct PoolCreated {
 
 /// Pool enabled state change event
 #[event]
+#[derive(Debug, Clone)]
 pub struct PoolEnabled {
     /// Pool address
     pub pool: Pubkey,
@@ -129,6 +139,7 @@ pub struct PoolEnabled {
 
 /// Migrate fee account event
 #[event]
+#[derive(Debug, Clone)]
 pub struct MigrateFeeAccount {
     /// Pool address
     pub pool: Pubkey,
@@ -144,6 +155,7 @@ pub struct MigrateFeeAccount {
 
 /// Create lock escrow
 #[event]
+#[derive(Debug, Clone)]
 pub struct CreateLockEscrow {
     /// Pool address
     pub pool: Pubkey,
@@ -153,6 +165,7 @@ pub struct CreateLockEscrow {
 
 /// Lock
 #[event]
+#[derive(Debug, Clone)]
 pub struct Lock {
     /// Pool address
     pub pool: Pubkey,
@@ -164,6 +177,7 @@ pub struct Lock {
 
 /// Claim fee
 #[event]
+#[derive(Debug, Clone)]
 pub struct ClaimFee {
     /// Pool address
     pub pool: Pubkey,
@@ -179,6 +193,7 @@ pub struct ClaimFee {
 
 /// Create config
 #[event]
+#[derive(Debug, Clone)]
 pub struct CreateConfig {
     /// New trade fee numerator
     pub trade_fee_numerator: u64,
@@ -190,6 +205,7 @@ pub struct CreateConfig {
 
 /// Close config
 #[event]
+#[derive(Debug, Clone)]
 pub struct CloseConfig {
     /// Config pubkey
     pub config: Pubkey,
@@ -197,6 +213,7 @@ pub struct CloseConfig {
 
 /// Withdraw protocol fees
 #[event]
+#[derive(Debug, Clone)]
 pub struct WithdrawProtocolFees {
     /// Pool address
     pub pool: Pubkey,
@@ -212,6 +229,7 @@ pub struct WithdrawProtocolFees {
 
 /// Partner claim fees
 #[event]
+#[derive(Debug, Clone)]
 pub struct PartnerClaimFees {
     /// Pool address
     pub pool: Pubkey,
@@ -0,0 +1,304 @@
+use crate::*;
+use anchor_lang::solana_program::program_option::COption;
+use common::account_decoder::{decode_account, DecodedAccount};
+use prog_dynamic_amm::state::{Config, CurveType, PoolFees};
+use serde_json::{json, Value};
+use solana_rpc_client::rpc_client::RpcClient;
+
+#[derive(Parser, Debug, Clone)]
+pub struct DecodeAccountArgs {
+    /// Account to decode
+    pub pubkey: Pubkey,
+}
+
+fn optional_pubkey(pubkey: &COption<Pubkey>) -> Value {
+    match pubkey {
+        COption::Some(pubkey) => json!(pubkey.to_string()),
+        COption::None => Value::Null,
+    }
+}
+
+fn pool_fees_to_json(fees: &PoolFees) -> Value {
+    json!({
+        "trade_fee_numerator": fees.trade_fee_numerator,
+        "trade_fee_denominator": fees.trade_fee_denominator,
+        "protocol_trade_fee_numerator": fees.protocol_trade_fee_numerator,
+        "protocol_trade_fee_denominator": fees.protocol_trade_fee_denominator,
+    })
+}
+
+fn curve_type_to_json(curve_type: &CurveType) -> Value {
+    match curve_type {
+        CurveType::ConstantProduct => json!({ "type": "ConstantProduct" }),
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        } => json!({
+            "type": "Stable",
+            "amp": amp,
+            "token_multiplier": {
+                "token_a_multiplier": token_multiplier.token_a_multiplier,
+                "token_b_multiplier": token_multiplier.token_b_multiplier,
+                "precision_factor": token_multiplier.precision_factor,
+            },
+            "depeg": {
+                "base_virtual_price": depeg.base_virtual_price,
+                "base_cache_updated": depeg.base_cache_updated,
+                "depeg_type": format!("{:?}", depeg.depeg_type),
+            },
+            "last_amp_updated_timestamp": last_amp_updated_timestamp,
+        }),
+    }
+}
+
+pub fn config_to_json(config: &Config) -> Value {
+    json!({
+        "pool_fees": pool_fees_to_json(&config.pool_fees),
+        "activation_duration": config.activation_duration,
+        "vault_config_key": config.vault_config_key.to_string(),
+        "pool_creator_authority": config.pool_creator_authority.to_string(),
+        "activation_type": config.activation_type,
+        "partner_fee_numerator": config.partner_fee_numerator,
+    })
+}
+
+/// JSON representation of the decoded account. Paddings are omitted, u128 are printed as string.
+#[allow(deprecated)]
+pub fn decoded_account_to_json(account: &DecodedAccount) -> Value {
+    let fields = match account {
+        DecodedAccount::Pool(pool) => json!({
+            "lp_mint": pool.lp_mint.to_string(),
+            "token_a_mint": pool.token_a_mint.to_string(),
+            "token_b_mint": pool.token_b_mint.to_string(),
+            "a_vault": pool.a_vault.to_string(),
+            "b_vault": pool.b_vault.to_string(),
+            "a_vault_lp": pool.a_vault_lp.to_string(),
+            "b_vault_lp": pool.b_vault_lp.to_string(),
+            "a_vault_lp_bump": pool.a_vault_lp_bump,
+            "enabled": pool.enabled,
+            "protocol_token_a_fee": pool.protocol_token_a_fee.to_string(),
+            "protocol_token_b_fee": pool.protocol_token_b_fee.to_string(),
+            "fee_last_updated_at": pool.fee_last_updated_at,
+            "fees": pool_fees_to_json(&pool.fees),
+            "pool_type": format!("{:?}", pool.pool_type),
+            "stake": pool.stake.to_string(),
+            "total_locked_lp": pool.total_locked_lp,
+            "bootstrapping": {
+                "activation_point": pool.bootstrapping.activation_point,
+                "whitelisted_vault": pool.bootstrapping.whitelisted_vault.to_string(),
+                "pool_creator": pool.bootstrapping.pool_creator.to_string(),
+                "activation_type": pool.bootstrapping.activation_type,
+            },
+            "partner_info": {
+                "fee_numerator": pool.partner_info.fee_numerator,
+                "partner_authority": pool.partner_info.partner_authority.to_string(),
+                "pending_fee_a": pool.partner_info.pending_fee_a,
+                "pending_fee_b": pool.partner_info.pending_fee_b,
+            },
+            "curve_type": curve_type_to_json(&pool.curve_type),
+        }),
+        DecodedAccount::LockEscrow(lock_escrow) => json!({
+            "pool": lock_escrow.pool.to_string(),
+            "owner": lock_escrow.owner.to_string(),
+            "escrow_vault": lock_escrow.escrow_vault.to_string(),
+            "bump": lock_escrow.bump,
+            "total_locked_amount": lock_escrow.total_locked_amount,
+            "lp_per_token": lock_escrow.lp_per_token.to_string(),
+            "unclaimed_fee_pending": lock_escrow.unclaimed_fee_pending,
+            "a_fee": lock_escrow.a_fee,
+            "b_fee": lock_escrow.b_fee,
+        }),
+        DecodedAccount::Config(config) => config_to_json(config),
+        DecodedAccount::Vault(vault) => json!({
+            "enabled": vault.enabled,
+            "bumps": {
+                "vault_bump": vault.bumps.vault_bump,
+                "token_vault_bump": vault.bumps.token_vault_bump,
+            },
+            "total_amount": vault.total_amount,
+            "token_vault": vault.token_vault.to_string(),
+            "fee_vault": vault.fee_vault.to_string(),
+            "token_mint": vault.token_mint.to_string(),
+            "lp_mint": vault.lp_mint.to_string(),
+            "strategies": vault
+                .strategies
+                .iter()
+                .map(|strategy| strategy.to_string())
+                .collect::<Vec<String>>(),
+            "base": vault.base.to_string(),
+            "admin": vault.admin.to_string(),
+            "operator": vault.operator.to_string(),
+            "locked_profit_tracker": {
+                "last_updated_locked_profit": vault.locked_profit_tracker.last_updated_locked_profit,
+                "last_report": vault.locked_profit_tracker.last_report,
+                "locked_profit_degradation": vault.locked_profit_tracker.locked_profit_degradation,
+            },
+        }),
+        DecodedAccount::Strategy(strategy) => json!({
+            "reserve": strategy.reserve.to_string(),
+            "collateral_vault": strategy.collateral_vault.to_string(),
+            "strategy_type": format!("{:?}", strategy.strategy_type),
+            "current_liquidity": strategy.current_liquidity,
+            "bumps": strategy.bumps,
+            "vault": strategy.vault.to_string(),
+            "is_disable": strategy.is_disable,
+        }),
+        DecodedAccount::TokenAccount(token_account) => json!({
+            "mint": token_account.mint.to_string(),
+            "owner": token_account.owner.to_string(),
+            "amount": token_account.amount,
+            "delegate": optional_pubkey(&token_account.delegate),
+            "state": format!("{:?}", token_account.state),
+            "is_native": Option::<u64>::from(token_account.is_native),
+            "delegated_amount": token_account.delegated_amount,
+            "close_authority": optional_pubkey(&token_account.close_authority),
+        }),
+        DecodedAccount::Mint(mint) => json!({
+            "mint_authority": optional_pubkey(&mint.mint_authority),
+            "supply": mint.supply,
+            "decimals": mint.decimals,
+            "is_initialized": mint.is_initialized,
+            "freeze_authority": optional_pubkey(&mint.freeze_authority),
+        }),
+        DecodedAccount::Unknown { owner, data } => json!({
+            "owner": owner.to_string(),
+            "data_len": data.len(),
+        }),
+    };
+
+    json!({
+        "type": account.name(),
+        "data": fields,
+    })
+}
+
+pub fn process_decode_account(args: &Args, sub_args: &DecodeAccountArgs) {
+    let DecodeAccountArgs { pubkey } = sub_args;
+
+    let client = RpcClient::new(args.rpc_url.clone());
+    let account = client.get_account(pubkey).unwrap();
+    let decoded_account =
+        decode_account(&args.get_cluster(), &account.owner, &account.data).unwrap();
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&decoded_account_to_json(&decoded_account)).unwrap()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, AccountSerialize};
+    use common::cluster::Cluster;
+    use prog_dynamic_amm::state::{LockEscrow, Pool};
+    use prog_dynamic_vault::state::Vault;
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn decode_to_json(cluster: &Cluster, owner: &Pubkey, data: &[u8]) -> Value {
+        decoded_account_to_json(&decode_account(cluster, owner, data).unwrap())
+    }
+
+    #[test]
+    fn test_pool_to_json() {
+        let cluster = Cluster::mainnet();
+        let data = vec![0u8; 8 + std::mem::size_of::<Pool>()];
+        let mut pool = Pool::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+        pool.lp_mint = Pubkey::new_unique();
+        pool.a_vault_lp_bump = 254;
+        pool.enabled = true;
+        pool.fees.trade_fee_numerator = 250;
+        pool.partner_info.pending_fee_b = 42;
+
+        let json = decode_to_json(&cluster, &cluster.amm_program_id, &serialize(&pool));
+        assert_eq!(json["type"], "Pool");
+        assert_eq!(json["data"]["lp_mint"], pool.lp_mint.to_string());
+        assert_eq!(json["data"]["a_vault_lp_bump"], 254);
+        assert_eq!(json["data"]["enabled"], true);
+        assert_eq!(json["data"]["fees"]["trade_fee_numerator"], 250);
+        assert_eq!(json["data"]["partner_info"]["pending_fee_b"], 42);
+        assert_eq!(json["data"]["curve_type"]["type"], "ConstantProduct");
+    }
+
+    #[test]
+    fn test_lock_escrow_to_json() {
+        let cluster = Cluster::mainnet();
+        let lock_escrow = LockEscrow {
+            pool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            escrow_vault: Pubkey::new_unique(),
+            bump: 255,
+            total_locked_amount: 1_000,
+            lp_per_token: u128::MAX,
+            unclaimed_fee_pending: 10,
+            a_fee: 20,
+            b_fee: 30,
+        };
+
+        let json = decode_to_json(&cluster, &cluster.amm_program_id, &serialize(&lock_escrow));
+        assert_eq!(json["type"], "LockEscrow");
+        assert_eq!(json["data"]["owner"], lock_escrow.owner.to_string());
+        assert_eq!(json["data"]["total_locked_amount"], 1_000);
+        // u128 is printed as string
+        assert_eq!(json["data"]["lp_per_token"], u128::MAX.to_string());
+        assert_eq!(json["data"]["b_fee"], 30);
+    }
+
+    #[test]
+    fn test_config_to_json() {
+        let cluster = Cluster::mainnet();
+        let config = Config {
+            pool_fees: PoolFees {
+                trade_fee_numerator: 2_500,
+                trade_fee_denominator: 100_000,
+                protocol_trade_fee_numerator: 20_000,
+                protocol_trade_fee_denominator: 100_000,
+            },
+            activation_duration: 3_600,
+            vault_config_key: Pubkey::new_unique(),
+            pool_creator_authority: Pubkey::new_unique(),
+            activation_type: 1,
+            partner_fee_numerator: 1_000,
+            _padding: [0; 219],
+        };
+
+        let json = decode_to_json(&cluster, &cluster.amm_program_id, &serialize(&config));
+        assert_eq!(json["type"], "Config");
+        assert_eq!(json["data"]["pool_fees"]["trade_fee_numerator"], 2_500);
+        assert_eq!(json["data"]["activation_duration"], 3_600);
+        assert_eq!(
+            json["data"]["pool_creator_authority"],
+            config.pool_creator_authority.to_string()
+        );
+        assert_eq!(json["data"]["activation_type"], 1);
+        assert!(json["data"].get("_padding").is_none());
+    }
+
+    #[test]
+    fn test_vault_to_json() {
+        let cluster = Cluster::mainnet();
+        let data = vec![0u8; 8 + std::mem::size_of::<Vault>()];
+        let mut vault = Vault::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+        vault.bumps.token_vault_bump = 252;
+        vault.total_amount = 1_000_000;
+        vault.token_mint = Pubkey::new_unique();
+        vault.strategies[1] = Pubkey::new_unique();
+
+        let json = decode_to_json(&cluster, &cluster.vault_program_id, &serialize(&vault));
+        assert_eq!(json["type"], "Vault");
+        assert_eq!(json["data"]["bumps"]["token_vault_bump"], 252);
+        assert_eq!(json["data"]["total_amount"], 1_000_000);
+        assert_eq!(json["data"]["token_mint"], vault.token_mint.to_string());
+        assert_eq!(
+            json["data"]["strategies"][1],
+            vault.strategies[1].to_string()
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use prog_dynamic_amm::state::Config;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CloseConfigArgs {
+    #[clap(long, env)]
+    pub config: Pubkey,
+    /// Receiver of the config rent. Default to the authority.
+    #[clap(long, env)]
+    pub rent_receiver: Option<Pubkey>,
+}
+
+pub fn process_close_config(args: &Args, admin_args: &AdminArgs, sub_args: &CloseConfigArgs) {
+    let CloseConfigArgs {
+        config,
+        rent_receiver,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    // Make sure the account is a config
+    let _config_state: Config = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*config)
+        .unwrap();
+
+    let authority = admin_args.get_authority(args).unwrap();
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::CloseConfig {
+            config: *config,
+            admin: authority,
+            rent_receiver: rent_receiver.unwrap_or(authority),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::CloseConfig {}.data(),
+    };
+
+    process_admin_ixs(args, admin_args, &client, &[ix], None, &[], "close config").unwrap();
+}
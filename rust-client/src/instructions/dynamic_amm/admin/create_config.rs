@@ -0,0 +1,81 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::admin::validate_config_parameters;
+use common::dynamic_amm::pda::derive_config_key;
+use prog_dynamic_amm::instructions::ConfigParameters;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateConfigArgs {
+    /// Index of the config, which derive the config key
+    #[clap(long, env)]
+    pub index: u64,
+    /// Trade fee numerator over 100000
+    #[clap(long, env)]
+    pub trade_fee_numerator: u64,
+    /// Protocol trade fee numerator over 100000. Portion of the trade fee.
+    #[clap(long, env, default_value = "0")]
+    pub protocol_trade_fee_numerator: u64,
+    /// Duration between the pool creation, and activation. In slot, or second depending on the activation type.
+    #[clap(long, env, default_value = "0")]
+    pub activation_duration: u64,
+    /// Only the pool creator authority can create pool with the config. Anyone can create pool with the config when not provided.
+    #[clap(long, env)]
+    pub pool_creator_authority: Option<Pubkey>,
+    /// 0 - Slot, 1 - Timestamp
+    #[clap(long, env, default_value = "0")]
+    pub activation_type: u8,
+    /// Partner fee numerator over 100000
+    #[clap(long, env, default_value = "0")]
+    pub partner_fee_numerator: u64,
+}
+
+pub fn process_create_config(args: &Args, admin_args: &AdminArgs, sub_args: &CreateConfigArgs) {
+    let CreateConfigArgs {
+        index,
+        trade_fee_numerator,
+        protocol_trade_fee_numerator,
+        activation_duration,
+        pool_creator_authority,
+        activation_type,
+        partner_fee_numerator,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+
+    let config_parameters = ConfigParameters {
+        trade_fee_numerator: *trade_fee_numerator,
+        protocol_trade_fee_numerator: *protocol_trade_fee_numerator,
+        activation_duration: *activation_duration,
+        pool_creator_authority: pool_creator_authority.unwrap_or_default(),
+        activation_type: *activation_type,
+        index: *index,
+        partner_fee_numerator: *partner_fee_numerator,
+    };
+    validate_config_parameters(&cluster, &config_parameters).unwrap();
+
+    let config = derive_config_key(&cluster, *index);
+    println!("config {}", config);
+    if client.get_account(&config).is_ok() {
+        println!("config {} already exists", config);
+        return;
+    }
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::CreateConfig {
+            config,
+            admin: admin_args.get_authority(args).unwrap(),
+            system_program: solana_program::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::CreateConfig { config_parameters }.data(),
+    };
+
+    process_admin_ixs(args, admin_args, &client, &[ix], None, &[], "create config").unwrap();
+}
@@ -0,0 +1,63 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::pda::derive_metadata_key;
+use prog_dynamic_amm::state::Pool;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateMintMetadataArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+}
+
+pub fn process_create_mint_metadata(
+    args: &Args,
+    admin_args: &AdminArgs,
+    sub_args: &CreateMintMetadataArgs,
+) {
+    let CreateMintMetadataArgs { pool } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let pool_state: Pool = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*pool)
+        .unwrap();
+
+    let mint_metadata = derive_metadata_key(&cluster, pool_state.lp_mint);
+    if client.get_account(&mint_metadata).is_ok() {
+        println!("mint metadata {} already exists", mint_metadata);
+        return;
+    }
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::CreateMintMetadata {
+            pool: *pool,
+            lp_mint: pool_state.lp_mint,
+            a_vault_lp: pool_state.a_vault_lp,
+            mint_metadata,
+            metadata_program: cluster.metadata_program_id,
+            system_program: solana_program::system_program::ID,
+            payer: admin_args.get_authority(args).unwrap(),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::CreateMintMetadata {}.data(),
+    };
+
+    process_admin_ixs(
+        args,
+        admin_args,
+        &client,
+        &[ix],
+        None,
+        &[],
+        "create mint metadata",
+    )
+    .unwrap();
+}
@@ -0,0 +1,58 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use prog_dynamic_amm::state::Pool;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct EnableOrDisablePoolArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// true to enable, false to disable the pool
+    #[clap(long, env, parse(try_from_str))]
+    pub enable: bool,
+}
+
+pub fn process_enable_or_disable_pool(
+    args: &Args,
+    admin_args: &AdminArgs,
+    sub_args: &EnableOrDisablePoolArgs,
+) {
+    let EnableOrDisablePoolArgs { pool, enable } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let pool_state: Pool = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*pool)
+        .unwrap();
+    if pool_state.enabled == *enable {
+        println!("pool enabled is already {}", enable);
+        return;
+    }
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::EnableOrDisablePool {
+            pool: *pool,
+            admin: admin_args.get_authority(args).unwrap(),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::EnableOrDisablePool { enable: *enable }.data(),
+    };
+
+    process_admin_ixs(
+        args,
+        admin_args,
+        &client,
+        &[ix],
+        None,
+        &[],
+        "enable or disable pool",
+    )
+    .unwrap();
+}
@@ -0,0 +1,117 @@
+use self::fee_estimation::CREATE_POOL_COMPUTE_UNIT;
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::ix_account_builder::IxAccountBuilder;
+use common::dynamic_vault::VaultBases;
+use prog_dynamic_amm::state::CurveType;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct InitializePermissionedPoolArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    /// Create stable pool with the amp. Constant product pool is created when not provided.
+    #[clap(long, env)]
+    pub amp: Option<u64>,
+    /// Depeg type of stable pool, token B is the staking token. 0 - None, 1 - Marinade, 2 - Lido, 3 - SplStake
+    #[clap(long, env, default_value = "0")]
+    pub depeg_type: u8,
+    /// SPL stake pool account of SplStake depeg pool
+    #[clap(long, env)]
+    pub stake_pool: Option<Pubkey>,
+    /// Keypair of the pool account. New keypair is generated when not provided.
+    /// The pool keypair partially sign the unsigned transaction, the authority must sign before the blockhash expired, unless `--nonce-account` is used.
+    #[clap(long, env)]
+    pub pool_keypair_path: Option<String>,
+}
+
+pub fn process_initialize_permissioned_pool(
+    args: &Args,
+    admin_args: &AdminArgs,
+    sub_args: &InitializePermissionedPoolArgs,
+) {
+    let InitializePermissionedPoolArgs {
+        token_a_mint,
+        token_b_mint,
+        amp,
+        depeg_type,
+        stake_pool,
+        pool_keypair_path,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let authority = admin_args.get_authority(args).unwrap();
+
+    let stable_curve = get_stable_curve(
+        &client,
+        *token_a_mint,
+        *token_b_mint,
+        *amp,
+        *depeg_type,
+        *stake_pool,
+    )
+    .unwrap();
+    let curve_type = stable_curve.map_or(CurveType::ConstantProduct, |stable_curve| {
+        stable_curve.curve_type()
+    });
+
+    let pool_keypair = match pool_keypair_path {
+        Some(path) => read_keypair_file(path).unwrap(),
+        None => Keypair::new(),
+    };
+    let pool = pool_keypair.pubkey();
+    println!("pool {}", pool);
+
+    let (token_a_vault_base, init_vault_a_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, authority, *token_a_mint);
+    let (token_b_vault_base, init_vault_b_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, authority, *token_b_mint);
+    let mut ixs = vec![];
+    ixs.extend(init_vault_a_ix);
+    ixs.extend(init_vault_b_ix);
+
+    let accounts = IxAccountBuilder::initialize_permissioned_pool_accounts(
+        &cluster,
+        pool,
+        *token_a_mint,
+        *token_b_mint,
+        authority,
+        VaultBases {
+            token_a: token_a_vault_base,
+            token_b: token_b_vault_base,
+        },
+    );
+    ixs.push(Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: accounts
+            .to_account_metas(None)
+            .into_iter()
+            .chain(
+                stable_curve
+                    .map(|stable_curve| stable_curve.remaining_accounts())
+                    .unwrap_or_default(),
+            )
+            .collect(),
+        data: prog_dynamic_amm::instruction::InitializePermissionedPool { curve_type }.data(),
+    });
+
+    process_admin_ixs(
+        args,
+        admin_args,
+        &client,
+        &ixs,
+        Some(CREATE_POOL_COMPUTE_UNIT),
+        &[&pool_keypair],
+        "create permissioned pool",
+    )
+    .unwrap();
+}
@@ -0,0 +1,191 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anyhow::{anyhow, bail, ensure, Context};
+use serde_json::json;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::nonce::state::{State, Versions};
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+pub mod close_config;
+pub use close_config::*;
+
+pub mod create_config;
+pub use create_config::*;
+
+pub mod create_mint_metadata;
+pub use create_mint_metadata::*;
+
+pub mod enable_or_disable_pool;
+pub use enable_or_disable_pool::*;
+
+pub mod initialize_permissioned_pool;
+pub use initialize_permissioned_pool::*;
+
+pub mod override_curve_param;
+pub use override_curve_param::*;
+
+pub mod set_pool_fees;
+pub use set_pool_fees::*;
+
+pub mod update_activation_point;
+pub use update_activation_point::*;
+
+#[derive(Parser, Debug, Clone)]
+pub struct AdminArgs {
+    /// Admin, or fee operator signing the instruction. Default to the payer.
+    #[clap(long, env)]
+    pub authority: Option<Pubkey>,
+    /// Print the unsigned transaction, paid by the authority, instead of signing. For authority held by multisig.
+    #[clap(long, env, requires = "authority")]
+    pub unsigned: bool,
+    /// Durable nonce account. The transaction use the nonce instead of the recent blockhash, so it doesn't expire while collecting the multisig signatures.
+    #[clap(long, env)]
+    pub nonce_account: Option<Pubkey>,
+    /// Authority of the nonce account. Default to the authority.
+    #[clap(long, env, requires = "nonce_account")]
+    pub nonce_authority: Option<Pubkey>,
+    #[clap(subcommand)]
+    pub command: AdminCommands,
+}
+
+#[derive(Debug, Parser, Clone)]
+pub enum AdminCommands {
+    /// Enable, or disable the pool. Disabled pool only allow remove balanced liquidity.
+    EnableOrDisablePool(EnableOrDisablePoolArgs),
+    /// Update the trade, protocol, and partner fee of the pool
+    SetPoolFees(SetPoolFeesArgs),
+    /// Override amp of the stable pool
+    OverrideCurveParam(OverrideCurveParamArgs),
+    /// Update activation point of the pool which is not activated yet
+    UpdateActivationPoint(UpdateActivationPointArgs),
+    /// Create config
+    CreateConfig(CreateConfigArgs),
+    /// Close config
+    CloseConfig(CloseConfigArgs),
+    /// Create permissioned pool
+    InitializePermissionedPool(InitializePermissionedPoolArgs),
+    /// Create lp mint metadata of old pool
+    CreateMintMetadata(CreateMintMetadataArgs),
+}
+
+impl AdminArgs {
+    /// Authority of the admin instruction
+    pub fn get_authority(&self, args: &Args) -> anyhow::Result<Pubkey> {
+        if let Some(authority) = self.authority {
+            return Ok(authority);
+        }
+        ensure!(
+            !self.unsigned,
+            "--authority is required to output the unsigned transaction"
+        );
+        let keypair_path = args
+            .keypair_path
+            .as_ref()
+            .context("Missing --authority, or payer keypair")?;
+        let keypair = read_keypair_file(keypair_path)
+            .map_err(|e| anyhow!("Failed to read keypair {}: {}", keypair_path, e))?;
+        Ok(keypair.pubkey())
+    }
+}
+
+/// Durable nonce stored in the nonce account, which is used as the blockhash of the transaction
+pub fn get_durable_nonce(client: &RpcClient, nonce_account: Pubkey) -> anyhow::Result<Hash> {
+    let account = client.get_account(&nonce_account)?;
+    ensure!(
+        account.owner == solana_program::system_program::ID,
+        "Nonce account {} is not owned by the system program",
+        nonce_account
+    );
+    let versions: Versions = bincode::deserialize(&account.data)
+        .with_context(|| format!("Invalid nonce account {}", nonce_account))?;
+    match versions.state() {
+        State::Initialized(data) => Ok(data.blockhash()),
+        State::Uninitialized => bail!("Nonce account {} is not initialized", nonce_account),
+    }
+}
+
+/// Sign the instructions by the authority, and handle the transaction by `tx_action`. Print the unsigned transaction instead when `unsigned`.
+/// `signers` are the signers other than the authority, such as new account, which partially sign the unsigned transaction.
+/// With `nonce_account`, the nonce is advanced by the first instruction, and used as the blockhash.
+pub fn process_admin_ixs(
+    args: &Args,
+    admin_args: &AdminArgs,
+    client: &RpcClient,
+    ixs: &[Instruction],
+    compute_unit: Option<u32>,
+    signers: &[&Keypair],
+    action: &str,
+) -> anyhow::Result<()> {
+    let authority = admin_args.get_authority(args)?;
+
+    let mut all_ixs = vec![];
+    let blockhash = match admin_args.nonce_account {
+        Some(nonce_account) => {
+            let nonce_authority = admin_args.nonce_authority.unwrap_or(authority);
+            all_ixs.push(system_instruction::advance_nonce_account(
+                &nonce_account,
+                &nonce_authority,
+            ));
+            get_durable_nonce(client, nonce_account)?
+        }
+        None => client.get_latest_blockhash()?,
+    };
+    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+        args.priority_fee,
+    ));
+    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_unit.unwrap_or(DEFAULT_COMPUTE_UNIT),
+    ));
+    all_ixs.extend_from_slice(ixs);
+
+    if admin_args.unsigned {
+        let mut tx = Transaction::new_with_payer(&all_ixs, Some(&authority));
+        tx.partial_sign(signers, blockhash);
+        let serialized_tx = bincode::serialize(&tx)?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "action": action,
+                "authority": authority.to_string(),
+                "recent_blockhash": blockhash.to_string(),
+                "nonce_account": admin_args.nonce_account.map(|nonce_account| nonce_account.to_string()),
+                "transaction_base58": solana_sdk::bs58::encode(&serialized_tx).into_string(),
+                "transaction_base64": anchor_lang::__private::base64::encode(&serialized_tx),
+            }))?
+        );
+        return Ok(());
+    }
+
+    let keypair_path = args
+        .keypair_path
+        .as_ref()
+        .context("Missing payer keypair")?;
+    let keypair = read_keypair_file(keypair_path)
+        .map_err(|e| anyhow!("Failed to read keypair {}: {}", keypair_path, e))?;
+    ensure!(
+        keypair.pubkey() == authority,
+        "Payer {} is not the authority {}, use --unsigned to output the unsigned transaction",
+        keypair.pubkey(),
+        authority
+    );
+    if let Some(nonce_authority) = admin_args.nonce_authority {
+        ensure!(
+            nonce_authority == authority,
+            "Nonce authority {} is not the authority {}, use --unsigned to output the unsigned transaction",
+            nonce_authority,
+            authority
+        );
+    }
+
+    let mut all_signers = vec![&keypair];
+    all_signers.extend_from_slice(signers);
+    let tx =
+        Transaction::new_signed_with_payer(&all_ixs, Some(&authority), &all_signers, blockhash);
+    process_transaction(args, &tx, authority, action)
+}
@@ -0,0 +1,59 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::admin::get_override_curve_type;
+use prog_dynamic_amm::state::{ActivationType, Pool};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct OverrideCurveParamArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// New amp of the stable pool
+    #[clap(long, env)]
+    pub amp: u64,
+}
+
+pub fn process_override_curve_param(
+    args: &Args,
+    admin_args: &AdminArgs,
+    sub_args: &OverrideCurveParamArgs,
+) {
+    let OverrideCurveParamArgs { pool, amp } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let pool_state: Pool = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*pool)
+        .unwrap();
+
+    let current_timestamp = get_current_point(&client, ActivationType::Timestamp).unwrap();
+    let curve_type =
+        get_override_curve_type(pool_state.curve_type, *amp, current_timestamp).unwrap();
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::OverrideCurveParam {
+            pool: *pool,
+            admin: admin_args.get_authority(args).unwrap(),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::OverrideCurveParam { curve_type }.data(),
+    };
+
+    process_admin_ixs(
+        args,
+        admin_args,
+        &client,
+        &[ix],
+        None,
+        &[],
+        "override curve param",
+    )
+    .unwrap();
+}
@@ -0,0 +1,72 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::admin::validate_pool_fees;
+use prog_dynamic_amm::constants::fee::FEE_DENOMINATOR;
+use prog_dynamic_amm::state::{Pool, PoolFees};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct SetPoolFeesArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Trade fee numerator over the fee denominator
+    #[clap(long, env)]
+    pub trade_fee_numerator: u64,
+    /// Protocol trade fee numerator over the fee denominator. Portion of the trade fee.
+    #[clap(long, env)]
+    pub protocol_trade_fee_numerator: u64,
+    /// Denominator of the trade, and protocol trade fee
+    #[clap(long, env, default_value_t = FEE_DENOMINATOR)]
+    pub fee_denominator: u64,
+    /// Partner fee numerator over 100000. Default to the current partner fee numerator of the pool.
+    #[clap(long, env)]
+    pub new_partner_fee_numerator: Option<u64>,
+}
+
+pub fn process_set_pool_fees(args: &Args, admin_args: &AdminArgs, sub_args: &SetPoolFeesArgs) {
+    let SetPoolFeesArgs {
+        pool,
+        trade_fee_numerator,
+        protocol_trade_fee_numerator,
+        fee_denominator,
+        new_partner_fee_numerator,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let pool_state: Pool = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*pool)
+        .unwrap();
+
+    let fees = PoolFees {
+        trade_fee_numerator: *trade_fee_numerator,
+        trade_fee_denominator: *fee_denominator,
+        protocol_trade_fee_numerator: *protocol_trade_fee_numerator,
+        protocol_trade_fee_denominator: *fee_denominator,
+    };
+    let new_partner_fee_numerator =
+        new_partner_fee_numerator.unwrap_or(pool_state.partner_info.fee_numerator);
+    validate_pool_fees(&fees, new_partner_fee_numerator).unwrap();
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::SetPoolFees {
+            pool: *pool,
+            fee_operator: admin_args.get_authority(args).unwrap(),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::SetPoolFees {
+            fees,
+            new_partner_fee_numerator,
+        }
+        .data(),
+    };
+
+    process_admin_ixs(args, admin_args, &client, &[ix], None, &[], "set pool fees").unwrap();
+}
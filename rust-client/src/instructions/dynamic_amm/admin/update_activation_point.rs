@@ -0,0 +1,67 @@
+use super::{process_admin_ixs, AdminArgs};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use common::dynamic_amm::admin::validate_update_activation_point;
+use prog_dynamic_amm::state::{ActivationType, Pool};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct UpdateActivationPointArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// New activation slot, or timestamp depending on the pool activation type
+    #[clap(long, env)]
+    pub activation_point: u64,
+}
+
+pub fn process_update_activation_point(
+    args: &Args,
+    admin_args: &AdminArgs,
+    sub_args: &UpdateActivationPointArgs,
+) {
+    let UpdateActivationPointArgs {
+        pool,
+        activation_point,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+    let pool_state: Pool = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*pool)
+        .unwrap();
+
+    let activation_type =
+        ActivationType::try_from(pool_state.bootstrapping.activation_type).unwrap();
+    let current_point = get_current_point(&client, activation_type).unwrap();
+    validate_update_activation_point(&cluster, &pool_state, *activation_point, current_point)
+        .unwrap();
+
+    let ix = Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: prog_dynamic_amm::accounts::UpdateActivationPoint {
+            pool: *pool,
+            admin: admin_args.get_authority(args).unwrap(),
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::UpdateActivationPoint {
+            new_activation_point: *activation_point,
+        }
+        .data(),
+    };
+
+    process_admin_ixs(
+        args,
+        admin_args,
+        &client,
+        &[ix],
+        None,
+        &[],
+        "update activation point",
+    )
+    .unwrap();
+}
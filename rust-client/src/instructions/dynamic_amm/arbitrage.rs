@@ -0,0 +1,205 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use dynamic_amm_quote::arbitrage::{find_arbitrage_opportunities, ArbitrageOpportunity};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use std::collections::HashSet;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ArbitrageDynamicAmmArgs {
+    /// Pools to search for arbitrage cycles, comma delimited
+    #[clap(long, env, use_value_delimiter = true, value_delimiter = ',')]
+    pub pools: Vec<Pubkey>,
+    /// Token mint the cycle starts, and ends with
+    #[clap(long, env)]
+    pub token_mint: Pubkey,
+    /// Maximum amount swapped into the first leg
+    #[clap(long, env)]
+    pub max_in_amount: u64,
+    /// Minimum profit, in token mint
+    #[clap(long, env, default_value = "0")]
+    pub min_profit: u64,
+    /// Execute the most profitable opportunity in a single transaction
+    #[clap(long, env)]
+    pub execute: bool,
+}
+
+/// Minimum out amount of each leg. Each intermediate leg must return at least the in amount of the next leg, so an under filled leg
+/// revert the whole cycle instead of spending the existing balance of the payer. The last leg must return at least the in amount, and the minimum profit.
+fn get_minimum_out_amounts(
+    opportunity: &ArbitrageOpportunity,
+    min_profit: u64,
+) -> Option<Vec<u64>> {
+    let last_minimum_out_amount = opportunity.in_amount.checked_add(min_profit)?;
+    if opportunity.legs.is_empty() {
+        return None;
+    }
+
+    Some(
+        opportunity
+            .legs
+            .iter()
+            .skip(1)
+            .map(|next_leg| next_leg.in_amount)
+            .chain(std::iter::once(last_minimum_out_amount))
+            .collect(),
+    )
+}
+
+pub fn process_arbitrage_dynamic_pool(args: &Args, sub_args: &ArbitrageDynamicAmmArgs) {
+    let ArbitrageDynamicAmmArgs {
+        pools,
+        token_mint,
+        max_in_amount,
+        min_profit,
+        execute,
+    } = sub_args;
+
+    let pools = pools
+        .iter()
+        .map(|pool| (*pool, get_quote_data(args, *pool).unwrap()))
+        .collect::<Vec<_>>();
+
+    let opportunities =
+        find_arbitrage_opportunities(&pools, *token_mint, *max_in_amount, *min_profit);
+
+    if opportunities.is_empty() {
+        println!("No arbitrage opportunity found");
+        return;
+    }
+
+    for opportunity in opportunities.iter() {
+        println!("{:?}", opportunity);
+    }
+
+    if !execute {
+        return;
+    }
+
+    let opportunity = &opportunities[0];
+
+    let cluster = args.get_cluster();
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(
+            DEFAULT_COMPUTE_UNIT * opportunity.legs.len() as u32,
+        ),
+    ];
+
+    let mut created_token_accounts = HashSet::new();
+    let minimum_out_amounts = get_minimum_out_amounts(opportunity, *min_profit).unwrap();
+
+    for (i, leg) in opportunity.legs.iter().enumerate() {
+        let (_, quote_data) = pools.iter().find(|(pool, _)| *pool == leg.pool).unwrap();
+
+        let user_destination_token =
+            get_associated_token_address(&keypair.pubkey(), &leg.out_token_mint);
+
+        if !created_token_accounts.contains(&user_destination_token)
+            && client.get_account(&user_destination_token).is_err()
+        {
+            ixs.push(
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &keypair.pubkey(),
+                    &keypair.pubkey(),
+                    &leg.out_token_mint,
+                    &spl_token::ID,
+                ),
+            );
+            created_token_accounts.insert(user_destination_token);
+        }
+
+        let minimum_out_amount = minimum_out_amounts[i];
+
+        let ix_builder = PoolIxBuilder::new(
+            &cluster,
+            leg.pool,
+            &quote_data.pool,
+            &quote_data.vault_a,
+            &quote_data.vault_b,
+        );
+        ixs.push(ix_builder.swap(
+            keypair.pubkey(),
+            leg.in_token_mint,
+            leg.in_amount,
+            minimum_out_amount,
+        ));
+    }
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    process_transaction(args, &tx, keypair.pubkey(), "arbitrage").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dynamic_amm_quote::arbitrage::ArbitrageLeg;
+
+    fn get_leg(in_amount: u64, out_amount: u64) -> ArbitrageLeg {
+        ArbitrageLeg {
+            pool: Pubkey::new_unique(),
+            in_token_mint: Pubkey::new_unique(),
+            out_token_mint: Pubkey::new_unique(),
+            in_amount,
+            out_amount,
+            fee: 0,
+            protocol_fee: 0,
+        }
+    }
+
+    #[test]
+    fn test_minimum_out_amounts() {
+        let opportunity = ArbitrageOpportunity {
+            token_mint: Pubkey::new_unique(),
+            in_amount: 1_000,
+            out_amount: 1_100,
+            profit: 100,
+            legs: vec![
+                get_leg(1_000, 2_000),
+                get_leg(2_000, 500),
+                get_leg(500, 1_100),
+            ],
+        };
+
+        assert_eq!(
+            get_minimum_out_amounts(&opportunity, 50).unwrap(),
+            vec![2_000, 500, 1_050]
+        );
+        assert!(get_minimum_out_amounts(&opportunity, u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_under_filled_leg_revert() {
+        let opportunity = ArbitrageOpportunity {
+            token_mint: Pubkey::new_unique(),
+            in_amount: 1_000,
+            out_amount: 1_100,
+            profit: 100,
+            legs: vec![get_leg(1_000, 2_000), get_leg(2_000, 1_100)],
+        };
+        let minimum_out_amounts = get_minimum_out_amounts(&opportunity, 0).unwrap();
+
+        // Under filled first leg fails its minimum out amount, instead of letting the second leg swap the existing balance of the payer
+        for (i, next_leg) in opportunity.legs.iter().enumerate().skip(1) {
+            let under_filled_out_amount = next_leg.in_amount - 1;
+            assert!(under_filled_out_amount < minimum_out_amounts[i - 1]);
+        }
+
+        let empty_opportunity = ArbitrageOpportunity {
+            legs: vec![],
+            ..opportunity
+        };
+        assert!(get_minimum_out_amounts(&empty_opportunity, 0).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use self::fee_estimation::CREATE_POOL_COMPUTE_UNIT;
+use crate::preflight::{process_preflight, PoolCreationKind, PoolCreationPlan};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use anyhow::bail;
+use common::dynamic_amm::customizable_params::CustomizablePoolParams;
+use common::dynamic_amm::ix_account_builder::IxAccountBuilder;
+use common::dynamic_vault::VaultBases;
+use prog_dynamic_amm::state::ActivationType;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateCustomizableDynamicAmmPoolArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_a_amount: u64,
+    #[clap(long, env)]
+    pub token_b_amount: u64,
+    #[clap(long, env)]
+    pub trade_fee_bps: u64,
+    /// 0 - Slot, 1 - Timestamp
+    #[clap(long, env, default_value = "0")]
+    pub activation_type: u8,
+    /// Slot, or timestamp depending on the activation type. The pool is activated on creation when not provided.
+    #[clap(long, env)]
+    pub activation_point: Option<u64>,
+    #[clap(long, env)]
+    pub has_alpha_vault: bool,
+    /// Check the pool creation before sending, and abort when it will fail
+    #[clap(long, env)]
+    pub preflight: bool,
+}
+
+pub fn process_new_customizable_dynamic_pool(
+    args: &Args,
+    sub_args: &CreateCustomizableDynamicAmmPoolArgs,
+) -> anyhow::Result<()> {
+    let CreateCustomizableDynamicAmmPoolArgs {
+        token_a_mint,
+        token_b_mint,
+        token_a_amount,
+        token_b_amount,
+        trade_fee_bps,
+        activation_type,
+        activation_point,
+        has_alpha_vault,
+        preflight,
+    } = sub_args;
+
+    let cluster = args.get_cluster();
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let Ok(activation_type) = ActivationType::try_from(*activation_type) else {
+        bail!("Invalid activation type {}", activation_type);
+    };
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(CREATE_POOL_COMPUTE_UNIT),
+    ];
+
+    let (token_a_vault_base, init_vault_a_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, keypair.pubkey(), *token_a_mint);
+    let (token_b_vault_base, init_vault_b_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, keypair.pubkey(), *token_b_mint);
+    ixs.extend(init_vault_a_ix);
+    ixs.extend(init_vault_b_ix);
+
+    let accounts = IxAccountBuilder::initialize_customizable_permissionless_constant_product_pool(
+        &cluster,
+        *token_a_mint,
+        *token_b_mint,
+        keypair.pubkey(),
+        VaultBases {
+            token_a: token_a_vault_base,
+            token_b: token_b_vault_base,
+        },
+    );
+    println!("pool {}", accounts.pool);
+    if *preflight
+        && !process_preflight(
+            &cluster,
+            &client,
+            &PoolCreationPlan {
+                pool: accounts.pool,
+                token_a_mint: *token_a_mint,
+                token_b_mint: *token_b_mint,
+                token_a_amount: *token_a_amount,
+                token_b_amount: *token_b_amount,
+                payer: keypair.pubkey(),
+                kind: PoolCreationKind::Customizable {
+                    trade_fee_bps: *trade_fee_bps,
+                    activation_type,
+                    activation_point: *activation_point,
+                },
+            },
+        )
+    {
+        return Ok(());
+    }
+
+    let current_point = get_current_point(&client, activation_type)?;
+    let params = CustomizablePoolParams {
+        trade_fee_bps: *trade_fee_bps,
+        activation_type,
+        activation_point: *activation_point,
+        has_alpha_vault: *has_alpha_vault,
+    }
+    .to_customizable_params(&cluster, *token_a_mint, *token_b_mint, current_point)?;
+
+    ixs.push(Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: accounts.to_account_metas(None),
+        data:
+            prog_dynamic_amm::instruction::InitializeCustomizablePermissionlessConstantProductPool {
+                token_a_amount: *token_a_amount,
+                token_b_amount: *token_b_amount,
+                params,
+            }
+            .data(),
+    });
+
+    let blockhash = client.get_latest_blockhash()?;
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    process_transaction(args, &tx, keypair.pubkey(), "create customizable pool")
+}
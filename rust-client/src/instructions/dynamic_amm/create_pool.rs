@@ -1,22 +1,31 @@
 use self::fee_estimation::CREATE_POOL_COMPUTE_UNIT;
+use crate::preflight::{
+    get_allowed_trade_fee_bps, process_preflight, PoolCreationKind, PoolCreationPlan,
+};
 use crate::*;
 use anchor_lang::AccountDeserialize;
 use anchor_lang::InstructionData;
 use anchor_lang::ToAccountMetas;
-use anchor_spl::associated_token::get_associated_token_address;
-use common::dynamic_amm::pda::derive_permissionless_pool_key_with_fee_tier;
-use common::dynamic_amm::pda::derive_protocol_fee_key;
-use common::dynamic_amm::pda::derive_vault_lp_key;
+use anchor_spl::token::Mint;
+use anyhow::Context;
+use common::cluster::Cluster;
+use common::dynamic_amm::fee_tier::select_trade_fee_bps;
+use common::dynamic_amm::ix_account_builder::{CurveTypeIx, IxAccountBuilder};
+use common::dynamic_amm::{get_depeg_type, validate_constant_product_depeg, StableCurve};
+use common::dynamic_vault::get_existing_vault;
 use common::dynamic_vault::pda::derive_token_vault_key;
 use common::dynamic_vault::pda::derive_vault_key;
+use common::dynamic_vault::{VaultBase, VaultBases};
+use prog_dynamic_amm::state::ActivationType;
 use prog_dynamic_amm::state::CurveType;
-use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_sdk::instruction::Instruction;
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::signer::Signer;
+use solana_sdk::sysvar::clock;
+use solana_sdk::sysvar::clock::Clock;
 use solana_sdk::transaction::Transaction;
 #[derive(Parser, Debug, Clone)]
 pub struct CreateDynamicAmmPoolArgs {
@@ -24,12 +33,108 @@ pub struct CreateDynamicAmmPoolArgs {
     pub token_a_mint: Pubkey,
     #[clap(long, env)]
     pub token_b_mint: Pubkey,
+    /// Must be one of the allowed trade fee bps of the curve. Default to the lowest allowed trade fee bps.
     #[clap(long, env)]
-    pub trade_fee_bps: u64,
+    pub trade_fee_bps: Option<u64>,
     #[clap(long, env)]
     pub token_a_amount: u64,
     #[clap(long, env)]
     pub token_b_amount: u64,
+    /// Create stable pool with the amp. Constant product pool is created when not provided.
+    #[clap(long, env)]
+    pub amp: Option<u64>,
+    /// Depeg type of stable pool, token B is the staking token. 0 - None, 1 - Marinade, 2 - Lido, 3 - SplStake
+    #[clap(long, env, default_value = "0")]
+    pub depeg_type: u8,
+    /// SPL stake pool account of SplStake depeg pool
+    #[clap(long, env)]
+    pub stake_pool: Option<Pubkey>,
+    /// Check the pool creation before sending, and abort when it will fail
+    #[clap(long, env)]
+    pub preflight: bool,
+}
+
+/// Stable curve from the mint decimals when amp is provided. Otherwise, constant product curve.
+pub fn get_stable_curve(
+    client: &RpcClient,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    amp: Option<u64>,
+    depeg_type: u8,
+    stake_pool: Option<Pubkey>,
+) -> anyhow::Result<Option<StableCurve>> {
+    let depeg_type = get_depeg_type(depeg_type)?;
+    let Some(amp) = amp else {
+        validate_constant_product_depeg(depeg_type, stake_pool)?;
+        return Ok(None);
+    };
+
+    let accounts = client.get_multiple_accounts(&[token_a_mint, token_b_mint])?;
+    let mut decimals = vec![];
+    for account in accounts {
+        let account = account.context("Mint not found")?;
+        let mint = Mint::try_deserialize_unchecked(&mut account.data.as_ref())?;
+        decimals.push(mint.decimals);
+    }
+
+    Ok(Some(StableCurve::new(
+        decimals[0],
+        decimals[1],
+        amp,
+        depeg_type,
+        stake_pool,
+    )?))
+}
+
+/// Validate the trade fee bps is allowed by the curve. Default to the lowest allowed trade fee bps.
+fn get_trade_fee_bps(curve_type: CurveType, trade_fee_bps: Option<u64>) -> anyhow::Result<u64> {
+    select_trade_fee_bps(&get_allowed_trade_fee_bps(curve_type), trade_fee_bps)
+}
+
+/// Initialize the default vault of the mint
+pub fn get_initialize_vault_ix(cluster: &Cluster, payer: Pubkey, mint: Pubkey) -> Instruction {
+    let vault = derive_vault_key(cluster, mint);
+    Instruction {
+        program_id: cluster.vault_program_id,
+        accounts: prog_dynamic_vault::accounts::Initialize {
+            vault,
+            token_vault: derive_token_vault_key(cluster, vault),
+            token_mint: mint,
+            token_program: spl_token::ID,
+            lp_mint: common::dynamic_vault::pda::derive_lp_mint_key(cluster, vault),
+            rent: anchor_client::solana_sdk::sysvar::rent::ID,
+            system_program: solana_program::system_program::ID,
+            payer,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_vault::instruction::Initialize {}.data(),
+    }
+}
+
+/// Base of the existing vault of the mint. Otherwise, the default base, and the instruction to initialize the vault.
+pub fn get_vault_base_or_initialize_ix(
+    cluster: &Cluster,
+    client: &RpcClient,
+    payer: Pubkey,
+    mint: Pubkey,
+) -> (VaultBase, Option<Instruction>) {
+    match get_existing_vault(cluster, client, mint).unwrap() {
+        Some((_vault, vault_base)) => (vault_base, None),
+        None => (
+            VaultBase::Default,
+            Some(get_initialize_vault_ix(cluster, payer, mint)),
+        ),
+    }
+}
+
+/// Current slot, or timestamp of the cluster depending on the activation type
+pub fn get_current_point(
+    client: &RpcClient,
+    activation_type: ActivationType,
+) -> anyhow::Result<u64> {
+    let data = client.get_account_data(&clock::id())?;
+    let clock = bincode::deserialize::<Clock>(&data)?;
+    common::dynamic_amm::config::get_current_point(&clock, activation_type)
 }
 
 pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs) {
@@ -39,100 +144,91 @@ pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs
         trade_fee_bps,
         token_a_amount,
         token_b_amount,
+        amp,
+        depeg_type,
+        stake_pool,
+        preflight,
     } = sub_args;
 
+    let cluster = args.get_cluster();
     let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
     let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
 
+    let stable_curve = get_stable_curve(
+        &client,
+        *token_a_mint,
+        *token_b_mint,
+        *amp,
+        *depeg_type,
+        *stake_pool,
+    )
+    .unwrap();
+    let curve_type = stable_curve.map_or(CurveType::ConstantProduct, |stable_curve| {
+        stable_curve.curve_type()
+    });
+    let trade_fee_bps = get_trade_fee_bps(curve_type, *trade_fee_bps).unwrap();
+
     let mut ixs = vec![
         ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
         ComputeBudgetInstruction::set_compute_unit_limit(CREATE_POOL_COMPUTE_UNIT),
     ];
-    // check vault
-    let a_vault = derive_vault_key(*token_a_mint);
-    let a_token_vault = derive_token_vault_key(a_vault);
-    let a_vault_lp_mint = common::dynamic_vault::pda::derive_lp_mint_key(a_vault);
-    if client.get_account(&a_vault).is_err() {
-        ixs.push(Instruction {
-            program_id: prog_dynamic_vault::ID,
-            accounts: prog_dynamic_vault::accounts::Initialize {
-                vault: a_vault,
-                token_vault: a_token_vault,
-                token_mint: *token_a_mint,
-                token_program: spl_token::ID,
-                lp_mint: a_vault_lp_mint,
-                rent: anchor_client::solana_sdk::sysvar::rent::ID,
-                system_program: solana_program::system_program::ID,
-                payer: keypair.pubkey(),
-            }
-            .to_account_metas(None),
-            data: prog_dynamic_vault::instruction::Initialize {}.data(),
-        });
-    }
 
-    let b_vault = derive_vault_key(*token_b_mint);
-    let b_token_vault = derive_token_vault_key(b_vault);
-    let b_vault_lp_mint = common::dynamic_vault::pda::derive_lp_mint_key(b_vault);
-    if client.get_account(&b_vault).is_err() {
-        ixs.push(Instruction {
-            program_id: prog_dynamic_vault::ID,
-            accounts: prog_dynamic_vault::accounts::Initialize {
-                vault: b_vault,
-                token_vault: b_token_vault,
-                token_mint: *token_b_mint,
-                token_program: spl_token::ID,
-                lp_mint: b_vault_lp_mint,
-                rent: anchor_client::solana_sdk::sysvar::rent::ID,
-                system_program: solana_program::system_program::ID,
-                payer: keypair.pubkey(),
-            }
-            .to_account_metas(None),
-            data: prog_dynamic_vault::instruction::Initialize {}.data(),
-        });
-    }
+    let (token_a_vault_base, init_vault_a_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, keypair.pubkey(), *token_a_mint);
+    let (token_b_vault_base, init_vault_b_ix) =
+        get_vault_base_or_initialize_ix(&cluster, &client, keypair.pubkey(), *token_b_mint);
+    ixs.extend(init_vault_a_ix);
+    ixs.extend(init_vault_b_ix);
 
-    let pool = derive_permissionless_pool_key_with_fee_tier(
-        CurveType::ConstantProduct,
+    let curve_type_ix = stable_curve.map_or(CurveTypeIx::ConstantProduct, CurveTypeIx::Stable);
+    let accounts = IxAccountBuilder::initialize_permissionless_pool_with_fee_tier_accounts(
+        &cluster,
+        curve_type_ix,
+        trade_fee_bps,
         *token_a_mint,
         *token_b_mint,
-        *trade_fee_bps,
+        keypair.pubkey(),
+        VaultBases {
+            token_a: token_a_vault_base,
+            token_b: token_b_vault_base,
+        },
     );
-    let pool_lp_mint = common::dynamic_amm::pda::derive_lp_mint_key(pool);
-    let (mint_metadata, _bump) = mpl_token_metadata::accounts::Metadata::find_pda(&pool_lp_mint);
+    println!("pool {}", accounts.pool);
+    if *preflight
+        && !process_preflight(
+            &cluster,
+            &client,
+            &PoolCreationPlan {
+                pool: accounts.pool,
+                token_a_mint: *token_a_mint,
+                token_b_mint: *token_b_mint,
+                token_a_amount: *token_a_amount,
+                token_b_amount: *token_b_amount,
+                payer: keypair.pubkey(),
+                kind: PoolCreationKind::FeeTier {
+                    curve_type,
+                    trade_fee_bps,
+                },
+            },
+        )
+    {
+        return;
+    }
+
     ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::InitializePermissionlessPoolWithFeeTier {
-            pool,
-            rent: anchor_client::solana_sdk::sysvar::rent::ID,
-            system_program: solana_program::system_program::ID,
-            payer: keypair.pubkey(),
-            a_vault,
-            b_vault,
-            a_token_vault,
-            b_token_vault,
-            a_vault_lp_mint,
-            b_vault_lp_mint,
-            lp_mint: pool_lp_mint,
-            token_a_mint: *token_a_mint,
-            token_b_mint: *token_b_mint,
-            token_program: spl_token::ID,
-            associated_token_program: spl_associated_token_account::ID,
-            a_vault_lp: derive_vault_lp_key(a_vault, pool),
-            b_vault_lp: derive_vault_lp_key(b_vault, pool),
-            payer_token_a: get_associated_token_address(&keypair.pubkey(), token_a_mint),
-            payer_token_b: get_associated_token_address(&keypair.pubkey(), token_b_mint),
-            payer_pool_lp: get_associated_token_address(&keypair.pubkey(), &pool_lp_mint),
-            protocol_token_a_fee: derive_protocol_fee_key(*token_a_mint, pool),
-            protocol_token_b_fee: derive_protocol_fee_key(*token_b_mint, pool),
-            fee_owner: Pubkey::default(),
-            vault_program: prog_dynamic_vault::ID,
-            metadata_program: mpl_token_metadata::ID,
-            mint_metadata,
-        }
-        .to_account_metas(None),
+        program_id: cluster.amm_program_id,
+        accounts: accounts
+            .to_account_metas(None)
+            .into_iter()
+            .chain(
+                stable_curve
+                    .map(|stable_curve| stable_curve.remaining_accounts())
+                    .unwrap_or_default(),
+            )
+            .collect(),
         data: prog_dynamic_amm::instruction::InitializePermissionlessPoolWithFeeTier {
-            curve_type: CurveType::ConstantProduct,
-            trade_fee_bps: *trade_fee_bps,
+            curve_type,
+            trade_fee_bps,
             token_a_amount: *token_a_amount,
             token_b_amount: *token_b_amount,
         }
@@ -142,25 +238,5 @@ pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
         Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
-    let payload = args
-        .to_rpc_args()
-        .send_transaction_wrapper(
-            &tx,
-            MAX_RETRIES,
-            keypair.pubkey(),
-            "".to_string(),
-            sucess_cb,
-            failed_cb,
-        )
-        .unwrap();
-    let mut result = BTreeMap::new();
-    result.insert(0, Some(payload));
-    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
-}
-
-fn sucess_cb(_wallet_memo: String, sig: Signature) {
-    println!("done create pool {:?}", sig);
-}
-fn failed_cb(_wallet_memo: String) {
-    println!("cannot create pool");
+    process_transaction(args, &tx, keypair.pubkey(), "create pool").unwrap();
 }
@@ -0,0 +1,116 @@
+use self::fee_estimation::LOCK_COMPUTE_UNIT;
+use crate::transaction_utils::pack_instruction_groups;
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anyhow::{bail, ensure, Context};
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::lock_allocation::{get_lock_amounts, LockAllocation};
+use common::dynamic_amm::pda::derive_lp_mint_key;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateDynamicAmmPoolAndLockArgs {
+    #[clap(flatten)]
+    pub create_pool: CreateDynamicAmmPoolWithConfigArgs,
+    /// Lock escrow owner, and its weight of the initial LP, as <owner>:<weight>. The initial LP is split by weight relative to the sum of weights,
+    /// and the last owner get the rounding remainder. Default to the payer.
+    #[clap(long, multiple_values = true)]
+    pub allocations: Vec<LockAllocation>,
+}
+
+/// Create the pool with config, then lock all initial LP of the payer to the lock escrow of the owners.
+/// LP minted by the pool creation is only known after the pool is created, so the lock is sent in the following transaction.
+/// Return error when the preflight, or pool creation fails, the lock of all owners doesn't fit in one transaction, the lock fails,
+/// or the total locked LP of the pool doesn't match the locked amount. Simulation, and fee estimation only handle the pool creation.
+pub fn process_new_dynamic_pool_and_lock(
+    args: &Args,
+    sub_args: &CreateDynamicAmmPoolAndLockArgs,
+) -> anyhow::Result<()> {
+    let CreateDynamicAmmPoolAndLockArgs {
+        create_pool,
+        allocations,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let payer = keypair.pubkey();
+
+    let allocations = if allocations.is_empty() {
+        vec![LockAllocation {
+            owner: payer,
+            weight: 1,
+        }]
+    } else {
+        allocations.clone()
+    };
+    // Validate the allocations before creating the pool
+    get_lock_amounts(0, &allocations)?;
+
+    let (pool, ixs) = get_create_pool_with_config_ixs(args, &client, payer, create_pool)?
+        .context("Preflight failed, the pool is not created")?;
+
+    let blockhash = client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer), &[&keypair], blockhash);
+    process_transaction(args, &tx, payer, "create pool")?;
+
+    if args.tx_action != TX_ACTION_SENT_TX {
+        bail!(
+            "Lock was skipped, it requires the pool to be created with tx action {}",
+            TX_ACTION_SENT_TX
+        );
+    }
+
+    let cluster = args.get_cluster();
+    let payer_pool_lp = get_associated_token_address(&payer, &derive_lp_mint_key(&cluster, pool));
+    let lp_amount: u64 = client
+        .get_token_account_balance(&payer_pool_lp)?
+        .amount
+        .parse()?;
+    let lock_amounts = get_lock_amounts(lp_amount, &allocations)?;
+
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let groups = lock_amounts
+        .iter()
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(owner, amount)| {
+            ix_builder.create_lock_escrow_and_lock(payer, payer, *owner, *amount)
+        })
+        .collect();
+    let packed_ixs = pack_instruction_groups(&payer, groups, args.priority_fee, LOCK_COMPUTE_UNIT)?;
+    // Lock all owners atomically, so a failed lock never leave some owners locked
+    let ixs = match packed_ixs.as_slice() {
+        [ixs] => ixs,
+        [] => bail!("No LP to lock in {}", payer_pool_lp),
+        _ => bail!(
+            "Lock of {} owners doesn't fit in one transaction, the initial LP is left in {}",
+            lock_amounts.len(),
+            payer_pool_lp
+        ),
+    };
+
+    let blockhash = client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer), &[&keypair], blockhash);
+    process_transaction(args, &tx, payer, "lock")?;
+
+    let (pool_state, _, _) = get_pool_and_vaults(args, &cluster, pool);
+    let locked_amount: u64 = lock_amounts.iter().map(|(_, amount)| amount).sum();
+    for (owner, amount) in lock_amounts.iter() {
+        println!("owner {} locked {}", owner, amount);
+    }
+    ensure!(
+        pool_state.total_locked_lp == locked_amount,
+        "Total locked lp {} doesn't match the locked amount {}",
+        pool_state.total_locked_lp,
+        locked_amount
+    );
+    println!("total locked lp {}", pool_state.total_locked_lp);
+
+    Ok(())
+}
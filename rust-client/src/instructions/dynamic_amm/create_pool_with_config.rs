@@ -0,0 +1,162 @@
+use self::fee_estimation::CREATE_POOL_COMPUTE_UNIT;
+use crate::preflight::{process_preflight, PoolCreationKind, PoolCreationPlan};
+use crate::*;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use anyhow::bail;
+use common::dynamic_amm::config::get_activation_point_from_config;
+use common::dynamic_amm::ix_account_builder::IxAccountBuilder;
+use common::dynamic_vault::VaultBases;
+use prog_dynamic_amm::state::{ActivationType, Config};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateDynamicAmmPoolWithConfigArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    #[clap(long, env)]
+    pub config: Pubkey,
+    #[clap(long, env)]
+    pub token_a_amount: u64,
+    #[clap(long, env)]
+    pub token_b_amount: u64,
+    /// Slot, or timestamp depending on the config activation type. Default to the current point + activation duration of the config.
+    #[clap(long, env)]
+    pub activation_point: Option<u64>,
+    /// Check the pool creation before sending, and abort when it will fail
+    #[clap(long, env)]
+    pub preflight: bool,
+}
+
+/// Instructions to create the pool with config, including the missing vaults initialization.
+/// None when the preflight fails.
+pub fn get_create_pool_with_config_ixs(
+    args: &Args,
+    client: &RpcClient,
+    payer: Pubkey,
+    sub_args: &CreateDynamicAmmPoolWithConfigArgs,
+) -> anyhow::Result<Option<(Pubkey, Vec<Instruction>)>> {
+    let CreateDynamicAmmPoolWithConfigArgs {
+        token_a_mint,
+        token_b_mint,
+        config,
+        token_a_amount,
+        token_b_amount,
+        activation_point,
+        preflight,
+    } = sub_args;
+
+    let cluster = args.get_cluster();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(CREATE_POOL_COMPUTE_UNIT),
+    ];
+
+    let (token_a_vault_base, init_vault_a_ix) =
+        get_vault_base_or_initialize_ix(&cluster, client, payer, *token_a_mint);
+    let (token_b_vault_base, init_vault_b_ix) =
+        get_vault_base_or_initialize_ix(&cluster, client, payer, *token_b_mint);
+    ixs.extend(init_vault_a_ix);
+    ixs.extend(init_vault_b_ix);
+
+    let accounts =
+        IxAccountBuilder::initialize_permissionless_constant_product_pool_with_config_accounts(
+            &cluster,
+            *token_a_mint,
+            *token_b_mint,
+            *config,
+            payer,
+            VaultBases {
+                token_a: token_a_vault_base,
+                token_b: token_b_vault_base,
+            },
+        );
+    println!("pool {}", accounts.pool);
+    if *preflight
+        && !process_preflight(
+            &cluster,
+            client,
+            &PoolCreationPlan {
+                pool: accounts.pool,
+                token_a_mint: *token_a_mint,
+                token_b_mint: *token_b_mint,
+                token_a_amount: *token_a_amount,
+                token_b_amount: *token_b_amount,
+                payer,
+                kind: PoolCreationKind::Config {
+                    config: *config,
+                    activation_point: *activation_point,
+                },
+            },
+        )
+    {
+        return Ok(None);
+    }
+
+    let config_state: Config = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(*config)?;
+
+    let Ok(activation_type) = ActivationType::try_from(config_state.activation_type) else {
+        bail!(
+            "Invalid config activation type {}",
+            config_state.activation_type
+        );
+    };
+    let current_point = get_current_point(client, activation_type)?;
+    let expected_activation_point = get_activation_point_from_config(
+        &cluster,
+        &config_state,
+        payer,
+        *activation_point,
+        current_point,
+    )?;
+    println!(
+        "activation_type {:?} current_point {} activation_point {}",
+        activation_type, current_point, expected_activation_point
+    );
+
+    ixs.push(Instruction {
+        program_id: cluster.amm_program_id,
+        accounts: accounts.to_account_metas(None),
+        data:
+            prog_dynamic_amm::instruction::InitializePermissionlessConstantProductPoolWithConfig2 {
+                token_a_amount: *token_a_amount,
+                token_b_amount: *token_b_amount,
+                // Without activation point, the program derive the same activation point from the config
+                activation_point: activation_point.map(|_| expected_activation_point),
+            }
+            .data(),
+    });
+
+    Ok(Some((accounts.pool, ixs)))
+}
+
+pub fn process_new_dynamic_pool_with_config(
+    args: &Args,
+    sub_args: &CreateDynamicAmmPoolWithConfigArgs,
+) {
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let Some((_pool, ixs)) =
+        get_create_pool_with_config_ixs(args, &client, keypair.pubkey(), sub_args).unwrap()
+    else {
+        return;
+    };
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    process_transaction(args, &tx, keypair.pubkey(), "create pool with config").unwrap();
+}
@@ -4,6 +4,7 @@ use anchor_lang::InstructionData;
 use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::Mint;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
@@ -41,7 +42,10 @@ pub fn process_deposit_dynamic_pool(args: &Args, sub_args: &DepositDynamicAmmArg
         ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
     ];
 
-    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let cluster = args.get_cluster();
+    let program_amm_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
     let pool_state: Pool = program_amm_client.account(*pool).unwrap();
 
     let user_pool_lp = get_associated_token_address(&keypair.pubkey(), &pool_state.lp_mint);
@@ -55,7 +59,9 @@ pub fn process_deposit_dynamic_pool(args: &Args, sub_args: &DepositDynamicAmmArg
             ),
         );
     }
-    let program_vault_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.vault_program_id);
     let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
     let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
 
@@ -72,34 +78,14 @@ pub fn process_deposit_dynamic_pool(args: &Args, sub_args: &DepositDynamicAmmArg
     )
     .unwrap();
 
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
-            pool: *pool,
-            lp_mint: pool_state.lp_mint,
-            user_pool_lp,
-            a_vault_lp: pool_state.a_vault_lp,
-            b_vault_lp: pool_state.b_vault_lp,
-            a_vault: pool_state.a_vault,
-            b_vault: pool_state.b_vault,
-            a_vault_lp_mint: a_vault_state.lp_mint,
-            b_vault_lp_mint: b_vault_state.lp_mint,
-            a_token_vault: a_vault_state.token_vault,
-            b_token_vault: b_vault_state.token_vault,
-            user_a_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_a_mint),
-            user_b_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_b_mint),
-            user: keypair.pubkey(),
-            vault_program: prog_dynamic_vault::ID,
-            token_program: spl_token::ID,
-        }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::AddBalanceLiquidity {
-            pool_token_amount,
-            maximum_token_a_amount: *max_token_a_amount,
-            maximum_token_b_amount: *max_token_b_amount,
-        }
-        .data(),
-    });
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+    ixs.push(ix_builder.add_balance_liquidity(
+        keypair.pubkey(),
+        pool_token_amount,
+        *max_token_a_amount,
+        *max_token_b_amount,
+    ));
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
@@ -139,12 +125,15 @@ fn get_pool_token_amount(
     keypair: &Keypair,
 ) -> anyhow::Result<u64> {
     // get total pool amount
-    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let cluster = args.get_cluster();
+    let program_amm_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
     let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
     let blockhash = client.get_latest_blockhash()?;
     let tx = Transaction::new_signed_with_payer(
         &vec![Instruction {
-            program_id: prog_dynamic_amm::ID,
+            program_id: cluster.amm_program_id,
             accounts: prog_dynamic_amm::accounts::GetPoolInfo {
                 a_vault: pool_state.a_vault,
                 a_vault_lp: pool_state.a_vault_lp,
@@ -166,7 +155,7 @@ fn get_pool_token_amount(
 
     let logs = simulation.value.logs.expect("No log in simulation found");
     let pool_info: prog_dynamic_amm::event::PoolInfo =
-        transaction_utils::parse_event_log(&logs, prog_dynamic_amm::ID)
+        transaction_utils::parse_event_log(&logs, cluster.amm_program_id)
             .expect("Event log not found");
 
     let pool_lp_mint: Mint = program_amm_client.account(pool_state.lp_mint)?;
@@ -0,0 +1,97 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::Mint;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use dynamic_amm_quote::stable_liquidity::{apply_slippage, compute_imbalance_deposit_quote};
+use prog_dynamic_amm::state::CurveType;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct DepositImbalanceDynamicAmmArgs {
+    /// Stable swap pool
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    #[clap(long, env, default_value = "0")]
+    pub token_a_amount: u64,
+    #[clap(long, env, default_value = "0")]
+    pub token_b_amount: u64,
+    /// Slippage of the minimum LP out, in bps
+    #[clap(long, env, default_value = "50")]
+    pub slippage_bps: u64,
+}
+
+pub fn process_deposit_imbalance_dynamic_pool(
+    args: &Args,
+    sub_args: &DepositImbalanceDynamicAmmArgs,
+) {
+    let DepositImbalanceDynamicAmmArgs {
+        pool,
+        token_a_amount,
+        token_b_amount,
+        slippage_bps,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let quote_data = get_quote_data(args, *pool).unwrap();
+    if let CurveType::ConstantProduct = quote_data.pool.curve_type {
+        println!("imbalance deposit is only supported by stable pool, use deposit instead");
+        return;
+    }
+
+    let data = client.get_account_data(&quote_data.pool.lp_mint).unwrap();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+
+    let pool_token_amount = compute_imbalance_deposit_quote(
+        quote_data.clone(),
+        lp_mint.supply,
+        *token_a_amount,
+        *token_b_amount,
+    )
+    .unwrap();
+    let minimum_pool_token_amount = apply_slippage(pool_token_amount, *slippage_bps).unwrap();
+    println!(
+        "pool_token_amount {} minimum_pool_token_amount {}",
+        pool_token_amount, minimum_pool_token_amount
+    );
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let cluster = args.get_cluster();
+    let ix_builder = PoolIxBuilder::new(
+        &cluster,
+        *pool,
+        &quote_data.pool,
+        &quote_data.vault_a,
+        &quote_data.vault_b,
+    );
+    ixs.push(
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &keypair.pubkey(),
+            &keypair.pubkey(),
+            &quote_data.pool.lp_mint,
+            &spl_token::ID,
+        ),
+    );
+    ixs.push(ix_builder.add_imbalance_liquidity(
+        keypair.pubkey(),
+        minimum_pool_token_amount,
+        *token_a_amount,
+        *token_b_amount,
+    ));
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    process_transaction(args, &tx, keypair.pubkey(), "deposit imbalance").unwrap();
+}
@@ -0,0 +1,32 @@
+use crate::*;
+use dynamic_amm_quote::depth::{compute_liquidity_depth, DEFAULT_PRICE_CHANGE_BPS};
+
+#[derive(Parser, Debug, Clone)]
+pub struct DepthDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Price movement levels in bps, comma delimited
+    #[clap(long, env, use_value_delimiter = true, value_delimiter = ',')]
+    pub price_change_bps: Vec<u64>,
+    /// Number of samples of the price vs size curve, per side
+    #[clap(long, env, default_value = "20")]
+    pub sample_count: u64,
+}
+
+pub fn process_depth_dynamic_pool(args: &Args, sub_args: &DepthDynamicAmmArgs) {
+    let DepthDynamicAmmArgs {
+        pool,
+        price_change_bps,
+        sample_count,
+    } = sub_args;
+
+    let price_change_bps = if price_change_bps.is_empty() {
+        DEFAULT_PRICE_CHANGE_BPS.to_vec()
+    } else {
+        price_change_bps.clone()
+    };
+
+    let quote_data = get_quote_data(args, *pool).unwrap();
+    let depth = compute_liquidity_depth(quote_data, &price_change_bps, *sample_count).unwrap();
+    println!("{}", serde_json::to_string_pretty(&depth).unwrap());
+}
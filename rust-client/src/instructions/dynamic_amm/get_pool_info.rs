@@ -17,11 +17,14 @@ pub struct PoolInfoDynamicAmmArgs {
 pub fn process_get_dynamic_pool_info(args: &Args, sub_args: &PoolInfoDynamicAmmArgs) {
     let PoolInfoDynamicAmmArgs { pool } = sub_args;
 
-    let program_dynamic_amm = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let cluster = args.get_cluster();
+    let program_dynamic_amm = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
 
     let program_dynamic_vault = args
         .to_rpc_args()
-        .get_program_client(prog_dynamic_vault::ID);
+        .get_program_client(cluster.vault_program_id);
 
     let pool_state: Pool = program_dynamic_amm.account(*pool).unwrap();
     let vault_a: Vault = program_dynamic_vault.account(pool_state.a_vault).unwrap();
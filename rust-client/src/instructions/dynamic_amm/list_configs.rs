@@ -0,0 +1,58 @@
+use crate::*;
+use common::dynamic_amm::config::{
+    get_configs, get_protocol_trade_fee_bps, get_trade_fee_bps, ConfigFilter,
+};
+use prog_dynamic_amm::state::ActivationType;
+use serde_json::json;
+use solana_rpc_client::rpc_client::RpcClient;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ListConfigsDynamicAmmArgs {
+    /// Only list configs which can be used by the pool creator authority. Use 11111111111111111111111111111111 for public configs.
+    #[clap(long, env)]
+    pub pool_creator_authority: Option<Pubkey>,
+    /// 0 - Slot, 1 - Timestamp
+    #[clap(long, env)]
+    pub activation_type: Option<u8>,
+    #[clap(long, env)]
+    pub trade_fee_bps: Option<u64>,
+    #[clap(long, env)]
+    pub protocol_trade_fee_bps: Option<u64>,
+}
+
+pub fn process_list_configs_dynamic_amm(args: &Args, sub_args: &ListConfigsDynamicAmmArgs) {
+    let ListConfigsDynamicAmmArgs {
+        pool_creator_authority,
+        activation_type,
+        trade_fee_bps,
+        protocol_trade_fee_bps,
+    } = sub_args;
+
+    let activation_type = activation_type.map(|activation_type| {
+        ActivationType::try_from(activation_type).expect("Invalid activation type")
+    });
+
+    let filter = ConfigFilter {
+        pool_creator_authority: *pool_creator_authority,
+        activation_type,
+        trade_fee_bps: *trade_fee_bps,
+        protocol_trade_fee_bps: *protocol_trade_fee_bps,
+    };
+
+    let client = RpcClient::new(args.rpc_url.clone());
+    let configs = get_configs(&args.get_cluster(), &client, &filter).unwrap();
+
+    let configs = configs
+        .iter()
+        .map(|(key, config)| {
+            json!({
+                "pubkey": key.to_string(),
+                "trade_fee_bps": get_trade_fee_bps(&config.pool_fees),
+                "protocol_trade_fee_bps": get_protocol_trade_fee_bps(&config.pool_fees),
+                "config": config_to_json(config),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    println!("{}", serde_json::to_string_pretty(&configs).unwrap());
+}
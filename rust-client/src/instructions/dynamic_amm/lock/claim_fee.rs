@@ -0,0 +1,56 @@
+use super::{get_pool_and_vaults, process_lock_ixs};
+use crate::*;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ClaimLockEscrowFeeArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Max LP amount to claim as fee. Claim all fee when not provided.
+    #[clap(long, env)]
+    pub max_amount: Option<u64>,
+    /// Create the token A, token B, and LP associated token account of the payer, which receive the fee
+    #[clap(long, env)]
+    pub create_ata: bool,
+}
+
+pub fn process_claim_lock_escrow_fee(args: &Args, sub_args: &ClaimLockEscrowFeeArgs) {
+    let ClaimLockEscrowFeeArgs {
+        pool,
+        max_amount,
+        create_ata,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let cluster = args.get_cluster();
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, *pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let mut ixs = vec![];
+    if *create_ata {
+        // Claimed LP is transferred to the LP token account, before withdrawn as token A, and B
+        for mint in [
+            pool_state.token_a_mint,
+            pool_state.token_b_mint,
+            pool_state.lp_mint,
+        ] {
+            ixs.push(create_associated_token_account_idempotent(
+                &keypair.pubkey(),
+                &keypair.pubkey(),
+                &mint,
+                &spl_token::ID,
+            ));
+        }
+    }
+    ixs.push(ix_builder.claim_fee(keypair.pubkey(), max_amount.unwrap_or(u64::MAX)));
+
+    process_lock_ixs(args, &client, &keypair, &ixs, "claim fee").unwrap();
+}
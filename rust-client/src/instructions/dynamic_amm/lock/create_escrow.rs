@@ -0,0 +1,37 @@
+use super::{get_create_lock_escrow_ixs, get_pool_and_vaults, process_lock_ixs};
+use crate::*;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateLockEscrowArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Owner of the lock escrow. Default to the payer.
+    #[clap(long, env)]
+    pub owner: Option<Pubkey>,
+}
+
+pub fn process_create_lock_escrow(args: &Args, sub_args: &CreateLockEscrowArgs) {
+    let CreateLockEscrowArgs { pool, owner } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let owner = owner.unwrap_or(keypair.pubkey());
+
+    let cluster = args.get_cluster();
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, *pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let ixs = get_create_lock_escrow_ixs(&client, &ix_builder, keypair.pubkey(), owner);
+    if ixs.is_empty() {
+        println!("lock escrow of {} already exists", owner);
+        return;
+    }
+
+    process_lock_ixs(args, &client, &keypair, &ixs, "create lock escrow").unwrap();
+}
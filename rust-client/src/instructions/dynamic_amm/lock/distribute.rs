@@ -0,0 +1,364 @@
+use super::get_pool_and_vaults;
+use crate::fee_estimation::LOCK_COMPUTE_UNIT;
+use crate::transaction_utils::pack_instruction_groups_with_count;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use anyhow::{bail, ensure, Context};
+use common::cluster::Cluster;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::lock_manifest::{get_manifest_lock_amounts, load_lock_manifest_file};
+use common::dynamic_amm::pda::derive_lock_escrow_key;
+use common::MAX_MULTIPLE_ACCOUNTS;
+use prog_dynamic_amm::state::LockEscrow;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use std::fs::File;
+use std::str::FromStr;
+
+/// Max number of signatures of getSignatureStatuses
+const MAX_SIGNATURE_STATUSES: usize = 256;
+
+#[derive(Parser, Debug, Clone)]
+pub struct DistributeLockArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// CSV, or JSON manifest of the recipients. CSV has `owner`, and `amount` and/or `share` header, and each row set either amount, or share.
+    /// JSON is an array of `{ "owner": "<base58>", "amount": <u64> }`, or `{ "owner": "<base58>", "share": <u64> }`. Shares split the LP remaining after the amounts.
+    #[clap(long, env)]
+    pub manifest_path: PathBuf,
+    /// State file of the distribution. Created on the first run, rerun with the same state file to resume after partial failure.
+    #[clap(long, env)]
+    pub state_path: PathBuf,
+    /// LP amount split by the manifest. Default to all LP of the payer.
+    #[clap(long, env)]
+    pub lp_amount: Option<u64>,
+    /// JSON file to dump the signature of each transaction, keyed by the recipients of the transaction
+    #[clap(long, env)]
+    pub dump_signature_path: Option<PathBuf>,
+}
+
+/// Progress of the distribution, persisted to resume after partial failure
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LockDistributionState {
+    pub pool: String,
+    pub recipients: Vec<LockDistributionRecipient>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LockDistributionRecipient {
+    pub owner: String,
+    pub amount: u64,
+    pub locked: bool,
+    /// Signature of the last transaction locking to the recipient, saved before the transaction is sent
+    pub signature: Option<String>,
+    /// Blockhash of the last transaction, which can't land after the blockhash expired
+    pub blockhash: Option<String>,
+}
+
+impl LockDistributionState {
+    fn read(path: &PathBuf) -> anyhow::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Every recipient must lock non zero amount, zero amount is never locked
+    fn validate(&self) -> anyhow::Result<()> {
+        for recipient in self.recipients.iter() {
+            ensure!(
+                recipient.amount > 0,
+                "Lock amount of owner {} is zero",
+                recipient.owner
+            );
+        }
+        Ok(())
+    }
+
+    fn write(&self, path: &PathBuf) -> anyhow::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+}
+
+/// Lock escrow of the owners, None when the lock escrow doesn't exist
+fn get_lock_escrows(
+    client: &RpcClient,
+    cluster: &Cluster,
+    pool: Pubkey,
+    owners: &[Pubkey],
+) -> anyhow::Result<Vec<Option<LockEscrow>>> {
+    let keys: Vec<Pubkey> = owners
+        .iter()
+        .map(|owner| derive_lock_escrow_key(cluster, pool, *owner))
+        .collect();
+
+    let mut lock_escrows = Vec::with_capacity(keys.len());
+    for keys in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for account in client.get_multiple_accounts(keys)? {
+            let lock_escrow = match account {
+                Some(account) => Some(LockEscrow::try_deserialize(&mut account.data.as_slice())?),
+                None => None,
+            };
+            lock_escrows.push(lock_escrow);
+        }
+    }
+    Ok(lock_escrows)
+}
+
+/// Resolve the lock amount of the recipients from the manifest
+fn new_lock_distribution_state(
+    pool: Pubkey,
+    manifest_path: &PathBuf,
+    lp_amount: u64,
+) -> anyhow::Result<LockDistributionState> {
+    let entries = load_lock_manifest_file(manifest_path)?;
+    let lock_amounts = get_manifest_lock_amounts(lp_amount, &entries)?;
+
+    let recipients = lock_amounts
+        .iter()
+        .map(|(owner, amount)| LockDistributionRecipient {
+            owner: owner.to_string(),
+            amount: *amount,
+            locked: false,
+            signature: None,
+            blockhash: None,
+        })
+        .collect();
+
+    let state = LockDistributionState {
+        pool: pool.to_string(),
+        recipients,
+    };
+    state.validate()?;
+    Ok(state)
+}
+
+/// Resolve the recipients with the transaction sent by the previous run from the signature status.
+/// Recipients of the landed transaction are locked, the failed, or expired transaction is sent again.
+fn resolve_sent_recipients(
+    client: &RpcClient,
+    state: &mut LockDistributionState,
+) -> anyhow::Result<()> {
+    let sent: Vec<usize> = (0..state.recipients.len())
+        .filter(|i| !state.recipients[*i].locked && state.recipients[*i].signature.is_some())
+        .collect();
+    let signatures = sent
+        .iter()
+        .map(|i| {
+            Signature::from_str(
+                state.recipients[*i]
+                    .signature
+                    .as_deref()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut statuses = Vec::with_capacity(signatures.len());
+    for signatures in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+        statuses.extend(
+            client
+                .get_signature_statuses_with_history(signatures)?
+                .value,
+        );
+    }
+
+    for ((i, signature), status) in sent.into_iter().zip(signatures).zip(statuses) {
+        let recipient = &mut state.recipients[i];
+        match status {
+            Some(status) if !status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                bail!(
+                    "Transaction {} of owner {} is not confirmed yet, rerun later",
+                    signature,
+                    recipient.owner
+                );
+            }
+            Some(status) if status.err.is_none() => {
+                println!(
+                    "owner {} already locked {} in {}",
+                    recipient.owner, recipient.amount, signature
+                );
+                recipient.locked = true;
+            }
+            Some(status) => {
+                println!(
+                    "transaction {} of owner {} failed {:?}, lock again",
+                    signature, recipient.owner, status.err
+                );
+                recipient.signature = None;
+                recipient.blockhash = None;
+            }
+            None => {
+                let blockhash = recipient
+                    .blockhash
+                    .as_deref()
+                    .context("Missing blockhash of the sent transaction")?;
+                ensure!(
+                    !client.is_blockhash_valid(
+                        &Hash::from_str(blockhash)?,
+                        CommitmentConfig::processed()
+                    )?,
+                    "Transaction {} of owner {} might still land, rerun after its blockhash expired",
+                    signature,
+                    recipient.owner
+                );
+                recipient.signature = None;
+                recipient.blockhash = None;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Lock LP of the payer to the lock escrow of every recipient of the manifest. Missing lock escrows are created.
+/// Signature of every transaction is saved to the state file before it is sent. On resume, the recipients of the landed transaction are skipped,
+/// so transaction landed without confirmation is not locked twice.
+pub fn process_distribute_lock(args: &Args, sub_args: &DistributeLockArgs) {
+    let DistributeLockArgs {
+        pool,
+        manifest_path,
+        state_path,
+        lp_amount,
+        dump_signature_path,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let payer = keypair.pubkey();
+
+    let cluster = args.get_cluster();
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, *pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let payer_pool_lp = get_associated_token_address(&payer, &pool_state.lp_mint);
+    let payer_lp_amount: u64 = client
+        .get_token_account_balance(&payer_pool_lp)
+        .with_context(|| format!("Fail to get the LP balance of {}", payer_pool_lp))
+        .unwrap()
+        .amount
+        .parse()
+        .unwrap();
+
+    let mut state = if state_path.exists() {
+        println!("resume from {}", state_path.display());
+        let state = LockDistributionState::read(state_path).unwrap();
+        assert_eq!(
+            state.pool,
+            pool.to_string(),
+            "State file is for another pool"
+        );
+        state.validate().unwrap();
+        state
+    } else {
+        let lp_amount = lp_amount.unwrap_or(payer_lp_amount);
+        new_lock_distribution_state(*pool, manifest_path, lp_amount).unwrap()
+    };
+    resolve_sent_recipients(&client, &mut state).unwrap();
+    if args.tx_action == TX_ACTION_SENT_TX {
+        state.write(state_path).unwrap();
+    }
+
+    let pending: Vec<usize> = (0..state.recipients.len())
+        .filter(|i| !state.recipients[*i].locked)
+        .collect();
+    let owners: Vec<Pubkey> = pending
+        .iter()
+        .map(|i| Pubkey::from_str(&state.recipients[*i].owner).unwrap())
+        .collect();
+    let lock_escrows = get_lock_escrows(&client, &cluster, *pool, &owners).unwrap();
+
+    let mut groups = vec![];
+    let mut group_recipients = vec![];
+    for ((i, owner), lock_escrow) in pending.iter().zip(owners).zip(lock_escrows) {
+        let recipient = &state.recipients[*i];
+        match lock_escrow {
+            Some(_) => {
+                groups.push(vec![ix_builder.lock(payer, owner, recipient.amount)]);
+                group_recipients.push(*i);
+            }
+            None => {
+                groups.push(ix_builder.create_lock_escrow_and_lock(
+                    payer,
+                    payer,
+                    owner,
+                    recipient.amount,
+                ));
+                group_recipients.push(*i);
+            }
+        }
+    }
+
+    let required_amount: u64 = group_recipients
+        .iter()
+        .map(|i| state.recipients[*i].amount)
+        .sum();
+    assert!(
+        payer_lp_amount >= required_amount,
+        "Payer has {} LP, {} LP is required",
+        payer_lp_amount,
+        required_amount
+    );
+
+    let packed =
+        pack_instruction_groups_with_count(&payer, groups, args.priority_fee, LOCK_COMPUTE_UNIT)
+            .unwrap();
+
+    let mut result = BTreeMap::new();
+    let mut group_recipients = group_recipients.into_iter();
+    for (i, (ixs, count)) in packed.iter().enumerate() {
+        let recipients: Vec<usize> = group_recipients.by_ref().take(*count).collect();
+        let wallet_memo = recipients
+            .iter()
+            .map(|j| state.recipients[*j].owner.clone())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let blockhash = client.get_latest_blockhash().unwrap();
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&payer), &[&keypair], blockhash);
+        if args.tx_action == TX_ACTION_SENT_TX {
+            for j in recipients.iter() {
+                let recipient = &mut state.recipients[*j];
+                recipient.signature = Some(tx.signatures[0].to_string());
+                recipient.blockhash = Some(blockhash.to_string());
+            }
+            state.write(state_path).unwrap();
+        }
+        let payload = args
+            .to_rpc_args()
+            .send_transaction_wrapper(&tx, MAX_RETRIES, payer, wallet_memo, sucess_cb, failed_cb)
+            .ok();
+
+        if args.tx_action == TX_ACTION_SENT_TX && payload.is_some() {
+            for j in recipients {
+                state.recipients[j].locked = true;
+            }
+            state.write(state_path).unwrap();
+        }
+        result.insert(i as u64, payload);
+    }
+    handle_collect_cb_by_tx_action(args.tx_action, dump_signature_path.clone(), &result);
+
+    let locked_count = state
+        .recipients
+        .iter()
+        .filter(|recipient| recipient.locked)
+        .count();
+    println!(
+        "locked {} of {} recipients",
+        locked_count,
+        state.recipients.len()
+    );
+    if args.tx_action == TX_ACTION_SENT_TX && locked_count < state.recipients.len() {
+        println!(
+            "rerun with the same state file {} to resume",
+            state_path.display()
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use super::{get_create_lock_escrow_ixs, get_pool_and_vaults, process_lock_ixs};
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct LockLiquidityArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// LP amount to lock. Lock all LP of the payer when not provided.
+    #[clap(long, env)]
+    pub amount: Option<u64>,
+    /// Owner of the lock escrow which receive the locked LP. Default to the payer. The lock escrow is created when it doesn't exist.
+    #[clap(long, env)]
+    pub escrow_owner: Option<Pubkey>,
+}
+
+pub fn process_lock_liquidity(args: &Args, sub_args: &LockLiquidityArgs) {
+    let LockLiquidityArgs {
+        pool,
+        amount,
+        escrow_owner,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let escrow_owner = escrow_owner.unwrap_or(keypair.pubkey());
+
+    let cluster = args.get_cluster();
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, *pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let amount = match amount {
+        Some(amount) => *amount,
+        None => {
+            let user_pool_lp = get_associated_token_address(&keypair.pubkey(), &pool_state.lp_mint);
+            client
+                .get_token_account_balance(&user_pool_lp)
+                .unwrap()
+                .amount
+                .parse()
+                .unwrap()
+        }
+    };
+    if amount == 0 {
+        println!("nothing to lock");
+        return;
+    }
+
+    let mut ixs = get_create_lock_escrow_ixs(&client, &ix_builder, keypair.pubkey(), escrow_owner);
+    ixs.push(ix_builder.lock(keypair.pubkey(), escrow_owner, amount));
+
+    process_lock_ixs(args, &client, &keypair, &ixs, "lock").unwrap();
+}
@@ -0,0 +1,110 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use common::cluster::Cluster;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::pda::derive_lock_escrow_key;
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+pub mod claim_fee;
+pub use claim_fee::*;
+
+pub mod create_escrow;
+pub use create_escrow::*;
+
+pub mod distribute;
+pub use distribute::*;
+
+pub mod lock_liquidity;
+pub use lock_liquidity::*;
+
+pub mod move_locked_lp;
+pub use move_locked_lp::*;
+
+pub mod show_escrow;
+pub use show_escrow::*;
+
+#[derive(Debug, Parser, Clone)]
+pub enum LockCommands {
+    /// Create lock escrow
+    CreateEscrow(CreateLockEscrowArgs),
+    /// Lock LP to the lock escrow
+    Lock(LockLiquidityArgs),
+    /// Lock escrow, and its claimable fee as JSON
+    ShowEscrow(ShowLockEscrowArgs),
+    /// Claim fee of the lock escrow
+    ClaimFee(ClaimLockEscrowFeeArgs),
+    /// Move locked LP to another lock escrow
+    MoveLockedLp(MoveLockedLpArgs),
+    /// Lock LP to the lock escrow of every recipient of a CSV, or JSON manifest. Resumable from the state file.
+    Distribute(DistributeLockArgs),
+}
+
+/// Fetch the pool, and its vaults
+pub fn get_pool_and_vaults(args: &Args, cluster: &Cluster, pool: Pubkey) -> (Pool, Vault, Vault) {
+    let program_amm_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
+    let pool_state: Pool = program_amm_client.account(pool).unwrap();
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.vault_program_id);
+    let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
+    let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
+
+    (pool_state, a_vault_state, b_vault_state)
+}
+
+/// Create the lock escrow of `owner`, and its escrow vault, if the lock escrow doesn't exist
+pub fn get_create_lock_escrow_ixs(
+    client: &RpcClient,
+    ix_builder: &PoolIxBuilder,
+    payer: Pubkey,
+    owner: Pubkey,
+) -> Vec<Instruction> {
+    let lock_escrow = derive_lock_escrow_key(ix_builder.cluster, ix_builder.pool_key, owner);
+    if client.get_account(&lock_escrow).is_ok() {
+        return vec![];
+    }
+
+    vec![
+        ix_builder.create_lock_escrow(payer, owner),
+        create_associated_token_account_idempotent(
+            &payer,
+            &lock_escrow,
+            &ix_builder.pool.lp_mint,
+            &spl_token::ID,
+        ),
+    ]
+}
+
+/// Sign the instructions, and handle the transaction by `tx_action`. `action` describe the transaction in the printed result.
+pub fn process_lock_ixs(
+    args: &Args,
+    client: &RpcClient,
+    keypair: &Keypair,
+    ixs: &[Instruction],
+    action: &str,
+) -> anyhow::Result<()> {
+    let mut all_ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+    all_ixs.extend_from_slice(ixs);
+
+    let blockhash = client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(
+        &all_ixs,
+        Some(&keypair.pubkey()),
+        &[keypair],
+        blockhash,
+    );
+    process_transaction(args, &tx, keypair.pubkey(), action)
+}
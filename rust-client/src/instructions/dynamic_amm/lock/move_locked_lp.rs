@@ -0,0 +1,44 @@
+use super::{get_create_lock_escrow_ixs, get_pool_and_vaults, process_lock_ixs};
+use crate::*;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct MoveLockedLpArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Owner of the destination lock escrow. The lock escrow is created when it doesn't exist.
+    #[clap(long, env)]
+    pub to_owner: Pubkey,
+    /// Max locked LP amount to move. Move all locked LP when not provided.
+    #[clap(long, env)]
+    pub max_amount: Option<u64>,
+}
+
+pub fn process_move_locked_lp(args: &Args, sub_args: &MoveLockedLpArgs) {
+    let MoveLockedLpArgs {
+        pool,
+        to_owner,
+        max_amount,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let cluster = args.get_cluster();
+    let (pool_state, a_vault_state, b_vault_state) = get_pool_and_vaults(args, &cluster, *pool);
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+
+    let mut ixs = get_create_lock_escrow_ixs(&client, &ix_builder, keypair.pubkey(), *to_owner);
+    ixs.push(ix_builder.move_locked_lp(
+        keypair.pubkey(),
+        *to_owner,
+        max_amount.unwrap_or(u64::MAX),
+    ));
+
+    process_lock_ixs(args, &client, &keypair, &ixs, "move locked lp").unwrap();
+}
@@ -0,0 +1,73 @@
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::Mint;
+use common::account_decoder::DecodedAccount;
+use common::dynamic_amm::pda::derive_lock_escrow_key;
+use dynamic_amm_quote::lock_escrow::compute_lock_escrow_fee;
+use prog_dynamic_amm::state::LockEscrow;
+use serde_json::json;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ShowLockEscrowArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Owner of the lock escrow. Default to the payer.
+    #[clap(long, env)]
+    pub owner: Option<Pubkey>,
+}
+
+pub fn process_show_lock_escrow(args: &Args, sub_args: &ShowLockEscrowArgs) {
+    let ShowLockEscrowArgs { pool, owner } = sub_args;
+
+    let owner = match owner {
+        Some(owner) => *owner,
+        None => read_keypair_file(args.keypair_path.clone().unwrap())
+            .unwrap()
+            .pubkey(),
+    };
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let cluster = args.get_cluster();
+
+    let lock_escrow_key = derive_lock_escrow_key(&cluster, *pool, owner);
+    let lock_escrow: LockEscrow = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id)
+        .account(lock_escrow_key)
+        .unwrap();
+
+    let quote_data = get_quote_data(args, *pool).unwrap();
+
+    let data = client.get_account_data(&quote_data.pool.lp_mint).unwrap();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+
+    let escrow_vault_amount: u64 = client
+        .get_token_account_balance(&lock_escrow.escrow_vault)
+        .unwrap()
+        .amount
+        .parse()
+        .unwrap();
+
+    let claimable_fee = compute_lock_escrow_fee(
+        quote_data,
+        lp_mint.supply,
+        &lock_escrow,
+        escrow_vault_amount,
+    )
+    .unwrap();
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&json!({
+            "address": lock_escrow_key.to_string(),
+            "lock_escrow": decoded_account_to_json(&DecodedAccount::LockEscrow(lock_escrow))["data"],
+            "escrow_vault_amount": escrow_vault_amount,
+            "claimable_fee": claimable_fee,
+        }))
+        .unwrap()
+    );
+}
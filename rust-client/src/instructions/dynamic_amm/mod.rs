@@ -1,10 +1,20 @@
 use crate::*;
 pub mod create_pool;
 pub use create_pool::*;
+pub mod create_pool_with_config;
+pub use create_pool_with_config::*;
+pub mod create_customizable_pool;
+pub use create_customizable_pool::*;
+pub mod create_pool_and_lock;
+pub use create_pool_and_lock::*;
 pub mod deposit;
 pub use deposit::*;
 pub mod withdraw;
 pub use withdraw::*;
+pub mod deposit_imbalance;
+pub use deposit_imbalance::*;
+pub mod withdraw_single_side;
+pub use withdraw_single_side::*;
 
 pub mod swap;
 pub use swap::*;
@@ -15,18 +25,60 @@ pub use quote::*;
 pub mod get_pool_info;
 pub use get_pool_info::*;
 
+pub mod arbitrage;
+pub use arbitrage::*;
+
+pub mod depth;
+pub use depth::*;
+
+pub mod list_configs;
+pub use list_configs::*;
+
+pub mod lock;
+pub use lock::*;
+
+pub mod partner;
+pub use partner::*;
+
+pub mod admin;
+pub use admin::*;
+
 #[derive(Debug, Parser, Clone)]
 pub enum DynamicAmmCommands {
     /// Create pool
     CreatePool(CreateDynamicAmmPoolArgs),
+    /// Create constant product pool with config
+    CreatePoolWithConfig(CreateDynamicAmmPoolWithConfigArgs),
+    /// Create customizable constant product pool, with custom fee, activation, and alpha vault support
+    CreateCustomizablePool(CreateCustomizableDynamicAmmPoolArgs),
+    /// Create constant product pool with config, and lock the initial LP to the lock escrow of the owners
+    CreatePoolAndLock(CreateDynamicAmmPoolAndLockArgs),
     /// Deposit
     Deposit(DepositDynamicAmmArgs),
     /// Withdraw
     Withdraw(WithdrawDynamicAmmArgs),
+    /// Deposit token A, and token B in any ratio. Stable pool only.
+    DepositImbalance(DepositImbalanceDynamicAmmArgs),
+    /// Withdraw only token A, or token B. Stable pool only.
+    WithdrawSingleSide(WithdrawSingleSideDynamicAmmArgs),
     /// Swap
     Swap(SwapDynamicAmmArgs),
     /// Quote
     Quote(QuoteDynamicAmmArgs),
     /// Get pool info
     GetPoolInfo(PoolInfoDynamicAmmArgs),
+    /// Find, and execute cross pool arbitrage
+    Arbitrage(ArbitrageDynamicAmmArgs),
+    /// Liquidity depth, and price curve of the pool as JSON
+    Depth(DepthDynamicAmmArgs),
+    /// List configs matching the filters as JSON
+    ListConfigs(ListConfigsDynamicAmmArgs),
+    /// Lock escrow, and locked liquidity
+    #[clap(subcommand)]
+    Lock(LockCommands),
+    /// Partner fee of pools created from config with partner fee
+    #[clap(subcommand)]
+    Partner(PartnerCommands),
+    /// Admin instructions, signed by the payer, or output as unsigned transaction
+    Admin(AdminArgs),
 }
@@ -0,0 +1,110 @@
+use self::fee_estimation::PARTNER_CLAIM_FEE_COMPUTE_UNIT;
+use super::get_vaults;
+use crate::transaction_utils::pack_instruction_groups;
+use crate::*;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use common::dynamic_amm::partner::{get_partner_pools, has_pending_partner_fee};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ClaimPartnerFeeArgs {
+    /// Only claim from these pools. Claim from all pools of the payer when not provided.
+    #[clap(long, multiple_values = true)]
+    pub pools: Vec<Pubkey>,
+}
+
+pub fn process_claim_partner_fee(args: &Args, sub_args: &ClaimPartnerFeeArgs) {
+    let ClaimPartnerFeeArgs { pools } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let partner_authority = keypair.pubkey();
+
+    let cluster = args.get_cluster();
+    let partner_pools: Vec<_> = get_partner_pools(&cluster, &client, partner_authority)
+        .unwrap()
+        .into_iter()
+        .filter(|(key, pool)| {
+            has_pending_partner_fee(pool) && (pools.is_empty() || pools.contains(key))
+        })
+        .collect();
+
+    if partner_pools.is_empty() {
+        println!("no pending partner fee");
+        return;
+    }
+
+    let vaults = get_vaults(
+        &client,
+        partner_pools
+            .iter()
+            .flat_map(|(_, pool)| [pool.a_vault, pool.b_vault]),
+    )
+    .unwrap();
+
+    let groups = partner_pools
+        .iter()
+        .map(|(key, pool)| {
+            let ix_builder = PoolIxBuilder::new(
+                &cluster,
+                *key,
+                pool,
+                &vaults[&pool.a_vault],
+                &vaults[&pool.b_vault],
+            );
+
+            vec![
+                create_associated_token_account_idempotent(
+                    &partner_authority,
+                    &partner_authority,
+                    &pool.token_a_mint,
+                    &spl_token::ID,
+                ),
+                create_associated_token_account_idempotent(
+                    &partner_authority,
+                    &partner_authority,
+                    &pool.token_b_mint,
+                    &spl_token::ID,
+                ),
+                ix_builder.partner_claim_fee(u64::MAX, u64::MAX),
+            ]
+        })
+        .collect();
+
+    let packed_ixs = pack_instruction_groups(
+        &partner_authority,
+        groups,
+        args.priority_fee,
+        PARTNER_CLAIM_FEE_COMPUTE_UNIT,
+    )
+    .unwrap();
+
+    let mut result = BTreeMap::new();
+    for (i, ixs) in packed_ixs.iter().enumerate() {
+        let blockhash = client.get_latest_blockhash().unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&partner_authority),
+            &[&keypair],
+            blockhash,
+        );
+        let payload = args
+            .to_rpc_args()
+            .send_transaction_wrapper(
+                &tx,
+                MAX_RETRIES,
+                partner_authority,
+                format!("claim partner fee tx {}", i),
+                sucess_cb,
+                failed_cb,
+            )
+            .ok();
+        result.insert(i as u64, payload);
+    }
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
@@ -0,0 +1,43 @@
+use crate::*;
+use common::dynamic_amm::partner::get_partner_pools;
+use serde_json::json;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ListPartnerPoolsArgs {
+    /// Partner authority of the pools. Default to the payer.
+    #[clap(long, env)]
+    pub partner_authority: Option<Pubkey>,
+}
+
+pub fn process_list_partner_pools(args: &Args, sub_args: &ListPartnerPoolsArgs) {
+    let ListPartnerPoolsArgs { partner_authority } = sub_args;
+
+    let partner_authority = match partner_authority {
+        Some(partner_authority) => *partner_authority,
+        None => read_keypair_file(args.keypair_path.clone().unwrap())
+            .unwrap()
+            .pubkey(),
+    };
+
+    let client = RpcClient::new(args.rpc_url.clone());
+    let pools = get_partner_pools(&args.get_cluster(), &client, partner_authority).unwrap();
+
+    let pools = pools
+        .iter()
+        .map(|(key, pool)| {
+            json!({
+                "pubkey": key.to_string(),
+                "token_a_mint": pool.token_a_mint.to_string(),
+                "token_b_mint": pool.token_b_mint.to_string(),
+                "fee_numerator": pool.partner_info.fee_numerator,
+                "pending_fee_a": pool.partner_info.pending_fee_a,
+                "pending_fee_b": pool.partner_info.pending_fee_b,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    println!("{}", serde_json::to_string_pretty(&pools).unwrap());
+}
@@ -0,0 +1,44 @@
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use common::MAX_MULTIPLE_ACCOUNTS;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use std::collections::{BTreeSet, HashMap};
+
+pub mod claim_partner_fee;
+pub use claim_partner_fee::*;
+
+pub mod list_partner_pools;
+pub use list_partner_pools::*;
+
+#[derive(Debug, Parser, Clone)]
+pub enum PartnerCommands {
+    /// List pools of the partner authority, and their pending partner fee as JSON
+    ListPools(ListPartnerPoolsArgs),
+    /// Claim pending partner fee of all pools of the partner authority
+    ClaimFee(ClaimPartnerFeeArgs),
+}
+
+/// Fetch the vaults in batch
+pub fn get_vaults(
+    client: &RpcClient,
+    vaults: impl IntoIterator<Item = Pubkey>,
+) -> anyhow::Result<HashMap<Pubkey, Vault>> {
+    let keys: Vec<Pubkey> = vaults
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut vault_states = HashMap::with_capacity(keys.len());
+    for keys in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = client.get_multiple_accounts(keys)?;
+        for (key, account) in keys.iter().zip(accounts) {
+            let account = account.ok_or_else(|| anyhow::anyhow!("Vault {key} not found"))?;
+            let vault = Vault::try_deserialize(&mut account.data.as_ref())?;
+            vault_states.insert(*key, vault);
+        }
+    }
+
+    Ok(vault_states)
+}
@@ -2,6 +2,7 @@ use crate::*;
 use anchor_lang::AccountDeserialize;
 use anchor_spl::token::Mint;
 use anchor_spl::token::TokenAccount;
+use anyhow::Context;
 use bincode::deserialize;
 use dynamic_amm_quote::compute_quote;
 use dynamic_amm_quote::QuoteData;
@@ -27,59 +28,64 @@ pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
         source_token,
     } = sub_args;
 
-    let program_dynamic_amm = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let quote_data = get_quote_data(args, *pool).unwrap();
+    let quote = compute_quote(*source_token, *in_amount, quote_data);
+    println!("{:?}", quote);
+}
+
+/// Fetch all the accounts required to quote the pool
+pub fn get_quote_data(args: &Args, pool: Pubkey) -> anyhow::Result<QuoteData> {
+    let cluster = args.get_cluster();
+    let program_dynamic_amm = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
     let program_dynamic_vault = args
         .to_rpc_args()
-        .get_program_client(prog_dynamic_vault::ID);
+        .get_program_client(cluster.vault_program_id);
 
-    let pool_state: Pool = program_dynamic_amm.account(*pool).unwrap();
-    let vault_a: Vault = program_dynamic_vault.account(pool_state.a_vault).unwrap();
-    let vault_b: Vault = program_dynamic_vault.account(pool_state.b_vault).unwrap();
+    let pool_state: Pool = program_dynamic_amm.account(pool)?;
+    let vault_a: Vault = program_dynamic_vault.account(pool_state.a_vault)?;
+    let vault_b: Vault = program_dynamic_vault.account(pool_state.b_vault)?;
 
-    let accounts = program_dynamic_amm
-        .rpc()
-        .get_multiple_accounts(&[
-            pool_state.a_vault_lp,
-            pool_state.b_vault_lp,
-            vault_a.lp_mint,
-            vault_b.lp_mint,
-            vault_a.token_vault,
-            vault_b.token_vault,
-            clock::id(),
-        ])
-        .unwrap();
+    let accounts = program_dynamic_amm.rpc().get_multiple_accounts(&[
+        pool_state.a_vault_lp,
+        pool_state.b_vault_lp,
+        vault_a.lp_mint,
+        vault_b.lp_mint,
+        vault_a.token_vault,
+        vault_b.token_vault,
+        clock::id(),
+    ])?;
 
     let accounts = accounts
         .into_iter()
-        .map(|account| account.unwrap().data)
-        .collect::<Vec<Vec<u8>>>();
+        .map(|account| account.map(|account| account.data))
+        .collect::<Option<Vec<Vec<u8>>>>()
+        .context("Pool related account not found")?;
 
     let mut data = accounts[0].as_slice();
-    let pool_vault_a_lp_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let pool_vault_a_lp_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[1].as_slice();
-    let pool_vault_b_lp_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let pool_vault_b_lp_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[2].as_slice();
-    let vault_a_lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_a_lp_mint = Mint::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[3].as_slice();
-    let vault_b_lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_b_lp_mint = Mint::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[4].as_slice();
-    let vault_a_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_a_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[5].as_slice();
-    let vault_b_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_b_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let data = accounts[6].as_slice();
-    let clock = deserialize::<Clock>(&data).unwrap();
+    let clock = deserialize::<Clock>(data)?;
 
     let stake_data = if pool_state.stake != Pubkey::default() {
-        let account = program_dynamic_amm
-            .rpc()
-            .get_account(&pool_state.stake)
-            .unwrap();
+        let account = program_dynamic_amm.rpc().get_account(&pool_state.stake)?;
         let mut stake_data = HashMap::new();
         stake_data.insert(pool_state.stake, account.data);
         stake_data
@@ -87,7 +93,7 @@ pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
         HashMap::new()
     };
 
-    let quote_data = QuoteData {
+    Ok(QuoteData {
         pool: pool_state,
         vault_a,
         vault_b,
@@ -99,7 +105,5 @@ pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
         vault_b_token,
         clock,
         stake_data,
-    };
-    let quote = compute_quote(*source_token, *in_amount, quote_data);
-    println!("{:?}", quote);
+    })
 }
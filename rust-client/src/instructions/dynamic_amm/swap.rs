@@ -1,14 +1,12 @@
 use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
 use crate::*;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
-use solana_sdk::instruction::Instruction;
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
@@ -40,27 +38,24 @@ pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
         ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
     ];
 
-    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let cluster = args.get_cluster();
+    let program_amm_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
     let pool_state: Pool = program_amm_client.account(*pool).unwrap();
 
-    let program_vault_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.vault_program_id);
     let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
     let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
 
-    let (user_destination_token, protocol_token_fee, destination_mint) =
-        if *source_token == pool_state.token_a_mint {
-            (
-                get_associated_token_address(&keypair.pubkey(), &pool_state.token_b_mint),
-                pool_state.protocol_token_a_fee,
-                pool_state.token_b_mint,
-            )
-        } else {
-            (
-                get_associated_token_address(&keypair.pubkey(), &pool_state.token_a_mint),
-                pool_state.protocol_token_b_fee,
-                pool_state.token_a_mint,
-            )
-        };
+    let destination_mint = if *source_token == pool_state.token_a_mint {
+        pool_state.token_b_mint
+    } else {
+        pool_state.token_a_mint
+    };
+    let user_destination_token = get_associated_token_address(&keypair.pubkey(), &destination_mint);
 
     if client.get_account(&user_destination_token).is_err() {
         ixs.push(
@@ -73,32 +68,14 @@ pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
         );
     }
 
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::Swap {
-            pool: *pool,
-            user_source_token: get_associated_token_address(&keypair.pubkey(), source_token),
-            user_destination_token,
-            a_vault_lp: pool_state.a_vault_lp,
-            b_vault_lp: pool_state.b_vault_lp,
-            a_vault: pool_state.a_vault,
-            b_vault: pool_state.b_vault,
-            a_vault_lp_mint: a_vault_state.lp_mint,
-            b_vault_lp_mint: b_vault_state.lp_mint,
-            a_token_vault: a_vault_state.token_vault,
-            b_token_vault: b_vault_state.token_vault,
-            user: keypair.pubkey(),
-            vault_program: prog_dynamic_vault::ID,
-            token_program: spl_token::ID,
-            protocol_token_fee,
-        }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::Swap {
-            in_amount: *in_amount,
-            minimum_out_amount: *minimum_out_amount,
-        }
-        .data(),
-    });
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+    ixs.push(ix_builder.swap(
+        keypair.pubkey(),
+        *source_token,
+        *in_amount,
+        *minimum_out_amount,
+    ));
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
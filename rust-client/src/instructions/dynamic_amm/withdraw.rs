@@ -1,14 +1,11 @@
 use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
 use crate::*;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
-use anchor_spl::associated_token::get_associated_token_address;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
-use solana_sdk::instruction::Instruction;
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
@@ -40,42 +37,26 @@ pub fn process_withdraw_dynamic_pool(args: &Args, sub_args: &WithdrawDynamicAmmA
         ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
     ];
 
-    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let cluster = args.get_cluster();
+    let program_amm_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.amm_program_id);
     let pool_state: Pool = program_amm_client.account(*pool).unwrap();
 
-    let user_pool_lp = get_associated_token_address(&keypair.pubkey(), &pool_state.lp_mint);
-    let program_vault_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(cluster.vault_program_id);
     let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
     let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
 
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
-            pool: *pool,
-            lp_mint: pool_state.lp_mint,
-            user_pool_lp,
-            a_vault_lp: pool_state.a_vault_lp,
-            b_vault_lp: pool_state.b_vault_lp,
-            a_vault: pool_state.a_vault,
-            b_vault: pool_state.b_vault,
-            a_vault_lp_mint: a_vault_state.lp_mint,
-            b_vault_lp_mint: b_vault_state.lp_mint,
-            a_token_vault: a_vault_state.token_vault,
-            b_token_vault: b_vault_state.token_vault,
-            user_a_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_a_mint),
-            user_b_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_b_mint),
-            user: keypair.pubkey(),
-            vault_program: prog_dynamic_vault::ID,
-            token_program: spl_token::ID,
-        }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::RemoveBalanceLiquidity {
-            pool_token_amount: *pool_token_amount,
-            minimum_a_token_out: *minimum_a_token_out,
-            minimum_b_token_out: *minimum_b_token_out,
-        }
-        .data(),
-    });
+    let ix_builder =
+        PoolIxBuilder::new(&cluster, *pool, &pool_state, &a_vault_state, &b_vault_state);
+    ixs.push(ix_builder.remove_balance_liquidity(
+        keypair.pubkey(),
+        *pool_token_amount,
+        *minimum_a_token_out,
+        *minimum_b_token_out,
+    ));
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
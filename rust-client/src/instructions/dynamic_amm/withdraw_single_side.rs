@@ -0,0 +1,98 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::Mint;
+use common::dynamic_amm::ix_builder::PoolIxBuilder;
+use dynamic_amm_quote::stable_liquidity::{apply_slippage, compute_single_side_withdraw_quote};
+use prog_dynamic_amm::state::CurveType;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct WithdrawSingleSideDynamicAmmArgs {
+    /// Stable swap pool
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    #[clap(long, env)]
+    pub pool_token_amount: u64,
+    /// Token mint to receive, either token A, or token B of the pool
+    #[clap(long, env)]
+    pub out_token_mint: Pubkey,
+    /// Slippage of the minimum token out, in bps
+    #[clap(long, env, default_value = "50")]
+    pub slippage_bps: u64,
+}
+
+pub fn process_withdraw_single_side_dynamic_pool(
+    args: &Args,
+    sub_args: &WithdrawSingleSideDynamicAmmArgs,
+) {
+    let WithdrawSingleSideDynamicAmmArgs {
+        pool,
+        pool_token_amount,
+        out_token_mint,
+        slippage_bps,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+
+    let quote_data = get_quote_data(args, *pool).unwrap();
+    if let CurveType::ConstantProduct = quote_data.pool.curve_type {
+        println!("single side withdraw is only supported by stable pool, use withdraw instead");
+        return;
+    }
+
+    let data = client.get_account_data(&quote_data.pool.lp_mint).unwrap();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+
+    let out_amount = compute_single_side_withdraw_quote(
+        quote_data.clone(),
+        lp_mint.supply,
+        *out_token_mint,
+        *pool_token_amount,
+    )
+    .unwrap();
+    let minimum_out_amount = apply_slippage(out_amount, *slippage_bps).unwrap();
+    println!(
+        "out_amount {} minimum_out_amount {}",
+        out_amount, minimum_out_amount
+    );
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let cluster = args.get_cluster();
+    let ix_builder = PoolIxBuilder::new(
+        &cluster,
+        *pool,
+        &quote_data.pool,
+        &quote_data.vault_a,
+        &quote_data.vault_b,
+    );
+    ixs.push(
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &keypair.pubkey(),
+            &keypair.pubkey(),
+            out_token_mint,
+            &spl_token::ID,
+        ),
+    );
+    ixs.push(ix_builder.remove_liquidity_single_side(
+        keypair.pubkey(),
+        *out_token_mint,
+        *pool_token_amount,
+        minimum_out_amount,
+    ));
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    process_transaction(args, &tx, keypair.pubkey(), "withdraw single side").unwrap();
+}
@@ -1,2 +1,8 @@
 pub mod dynamic_amm;
 pub use dynamic_amm::*;
+
+pub mod decode_account;
+pub use decode_account::*;
+
+pub mod verify_lp_mint;
+pub use verify_lp_mint::*;
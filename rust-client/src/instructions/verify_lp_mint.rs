@@ -0,0 +1,48 @@
+use crate::*;
+use common::dynamic_amm::verify_pool_lp_mints;
+use common::dynamic_vault::verify_vault_lp_mints;
+use common::lp_mint_registry::LpMintMismatch;
+use serde_json::{json, Value};
+use solana_rpc_client::rpc_client::RpcClient;
+
+#[derive(Parser, Debug, Clone)]
+pub struct VerifyLpMintArgs {
+    /// Pools to verify
+    #[clap(long, multiple_values = true)]
+    pub pools: Vec<Pubkey>,
+    /// Vaults to verify
+    #[clap(long, multiple_values = true)]
+    pub vaults: Vec<Pubkey>,
+}
+
+fn mismatches_to_json(mismatches: &[LpMintMismatch]) -> Value {
+    mismatches
+        .iter()
+        .map(|mismatch| {
+            json!({
+                "key": mismatch.key.to_string(),
+                "lp_mint": mismatch.lp_mint.to_string(),
+                "derived_lp_mint": mismatch.derived_lp_mint.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn process_verify_lp_mint(args: &Args, sub_args: &VerifyLpMintArgs) {
+    let VerifyLpMintArgs { pools, vaults } = sub_args;
+
+    let cluster = args.get_cluster();
+    let client = RpcClient::new(args.rpc_url.clone());
+
+    let pool_mismatches = verify_pool_lp_mints(&cluster, &client, pools).unwrap();
+    let vault_mismatches = verify_vault_lp_mints(&cluster, &client, vaults).unwrap();
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&json!({
+            "pools": mismatches_to_json(&pool_mismatches),
+            "vaults": mismatches_to_json(&vault_mismatches),
+        }))
+        .unwrap()
+    );
+}
@@ -1,6 +1,7 @@
 pub mod fee_estimation;
 pub mod file;
 pub mod instructions;
+pub mod preflight;
 pub mod rpc;
 pub mod transaction_utils;
 use crate::instructions::*;
@@ -10,6 +11,7 @@ use clap::{Parser, Subcommand};
 use file::write_signature_to_file;
 pub use rpc::*;
 use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
 use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
@@ -37,6 +39,26 @@ pub struct Args {
     /// Is simulation
     #[clap(long, env, default_value = "0")]
     pub tx_action: u8,
+
+    /// Cluster of the programs. mainnet, staging, devnet, or localnet
+    #[clap(long, env, default_value = "mainnet")]
+    pub cluster: common::cluster::Cluster,
+
+    /// Override dynamic amm program id, for redeployed program
+    #[clap(long, env)]
+    pub amm_program_id: Option<Pubkey>,
+
+    /// Override dynamic vault program id, for redeployed program
+    #[clap(long, env)]
+    pub vault_program_id: Option<Pubkey>,
+
+    /// JSON file of pool to lp mint, for pools with non PDA based lp mint not built in
+    #[clap(long, env)]
+    pub pool_lp_mints_path: Option<String>,
+
+    /// JSON file of vault to lp mint, for vaults with non PDA based lp mint not built in
+    #[clap(long, env)]
+    pub vault_lp_mints_path: Option<String>,
 }
 
 impl Args {
@@ -48,6 +70,31 @@ impl Args {
             keypair_path: self.keypair_path.clone(),
         }
     }
+
+    /// Cluster with the program id overrides applied
+    pub fn get_cluster(&self) -> common::cluster::Cluster {
+        let mut cluster = self.cluster;
+        if let Some(amm_program_id) = self.amm_program_id {
+            cluster = cluster.with_amm_program_id(amm_program_id);
+        }
+        if let Some(vault_program_id) = self.vault_program_id {
+            cluster = cluster.with_vault_program_id(vault_program_id);
+        }
+        cluster
+    }
+
+    /// Extend the non PDA based lp mint registries with the supplied files
+    pub fn load_lp_mint_registries(&self) -> anyhow::Result<()> {
+        let cluster = self.get_cluster();
+        if let Some(path) = &self.pool_lp_mints_path {
+            common::dynamic_amm::get_pool_lp_mint_registry(&cluster).load_from_json_file(path)?;
+        }
+        if let Some(path) = &self.vault_lp_mints_path {
+            common::dynamic_vault::get_vault_lp_mint_registry(&cluster)
+                .load_from_json_file(path)?;
+        }
+        Ok(())
+    }
 }
 
 pub fn handle_collect_cb_by_tx_action(
@@ -133,28 +180,76 @@ pub fn handle_collect_cb_by_tx_action(
     }
 }
 
+fn sucess_cb(wallet_memo: String, sig: Signature) {
+    println!("done {} {:?}", wallet_memo, sig);
+}
+fn failed_cb(wallet_memo: String) {
+    println!("cannot {}", wallet_memo);
+}
+
+/// Handle the signed transaction by `tx_action`, and print the result. `action` describe the transaction, such as "lock", and is the wallet memo of the result.
+pub fn process_transaction(
+    args: &Args,
+    tx: &Transaction,
+    payer: Pubkey,
+    action: &str,
+) -> anyhow::Result<()> {
+    let payload = args.to_rpc_args().send_transaction_wrapper(
+        tx,
+        MAX_RETRIES,
+        payer,
+        action.to_string(),
+        sucess_cb,
+        failed_cb,
+    )?;
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+    Ok(())
+}
+
 // Subcommands
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     /// Dynamic amm
     #[clap(subcommand)]
     DynamicAmm(DynamicAmmCommands),
+    /// Decode dynamic amm, dynamic vault, or token account as JSON
+    DecodeAccount(DecodeAccountArgs),
+    /// Verify the lp mint of pools, and vaults against the derived lp mint
+    VerifyLpMint(VerifyLpMintArgs),
 }
 
 fn main() {
     let args = Args::parse();
+    args.load_lp_mint_registries().unwrap();
 
     match &args.command {
         Commands::DynamicAmm(sub_command) => match sub_command {
             DynamicAmmCommands::CreatePool(sub_args) => {
                 dynamic_amm::process_new_dynamic_pool(&args, sub_args)
             }
+            DynamicAmmCommands::CreatePoolWithConfig(sub_args) => {
+                dynamic_amm::process_new_dynamic_pool_with_config(&args, sub_args)
+            }
+            DynamicAmmCommands::CreateCustomizablePool(sub_args) => {
+                dynamic_amm::process_new_customizable_dynamic_pool(&args, sub_args).unwrap()
+            }
+            DynamicAmmCommands::CreatePoolAndLock(sub_args) => {
+                dynamic_amm::process_new_dynamic_pool_and_lock(&args, sub_args).unwrap()
+            }
             DynamicAmmCommands::Deposit(sub_args) => {
                 dynamic_amm::process_deposit_dynamic_pool(&args, sub_args)
             }
             DynamicAmmCommands::Withdraw(sub_args) => {
                 dynamic_amm::process_withdraw_dynamic_pool(&args, sub_args)
             }
+            DynamicAmmCommands::DepositImbalance(sub_args) => {
+                dynamic_amm::process_deposit_imbalance_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::WithdrawSingleSide(sub_args) => {
+                dynamic_amm::process_withdraw_single_side_dynamic_pool(&args, sub_args)
+            }
             DynamicAmmCommands::Swap(sub_args) => {
                 dynamic_amm::process_swap_dynamic_pool(&args, sub_args)
             }
@@ -164,6 +259,71 @@ fn main() {
             DynamicAmmCommands::GetPoolInfo(sub_args) => {
                 dynamic_amm::process_get_dynamic_pool_info(&args, sub_args)
             }
+            DynamicAmmCommands::Arbitrage(sub_args) => {
+                dynamic_amm::process_arbitrage_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::Depth(sub_args) => {
+                dynamic_amm::process_depth_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::ListConfigs(sub_args) => {
+                dynamic_amm::process_list_configs_dynamic_amm(&args, sub_args)
+            }
+            DynamicAmmCommands::Lock(sub_command) => match sub_command {
+                LockCommands::CreateEscrow(sub_args) => {
+                    dynamic_amm::process_create_lock_escrow(&args, sub_args)
+                }
+                LockCommands::Lock(sub_args) => {
+                    dynamic_amm::process_lock_liquidity(&args, sub_args)
+                }
+                LockCommands::ShowEscrow(sub_args) => {
+                    dynamic_amm::process_show_lock_escrow(&args, sub_args)
+                }
+                LockCommands::ClaimFee(sub_args) => {
+                    dynamic_amm::process_claim_lock_escrow_fee(&args, sub_args)
+                }
+                LockCommands::MoveLockedLp(sub_args) => {
+                    dynamic_amm::process_move_locked_lp(&args, sub_args)
+                }
+                LockCommands::Distribute(sub_args) => {
+                    dynamic_amm::process_distribute_lock(&args, sub_args)
+                }
+            },
+            DynamicAmmCommands::Partner(sub_command) => match sub_command {
+                PartnerCommands::ListPools(sub_args) => {
+                    dynamic_amm::process_list_partner_pools(&args, sub_args)
+                }
+                PartnerCommands::ClaimFee(sub_args) => {
+                    dynamic_amm::process_claim_partner_fee(&args, sub_args)
+                }
+            },
+            DynamicAmmCommands::Admin(admin_args) => match &admin_args.command {
+                AdminCommands::EnableOrDisablePool(sub_args) => {
+                    dynamic_amm::process_enable_or_disable_pool(&args, admin_args, sub_args)
+                }
+                AdminCommands::SetPoolFees(sub_args) => {
+                    dynamic_amm::process_set_pool_fees(&args, admin_args, sub_args)
+                }
+                AdminCommands::OverrideCurveParam(sub_args) => {
+                    dynamic_amm::process_override_curve_param(&args, admin_args, sub_args)
+                }
+                AdminCommands::UpdateActivationPoint(sub_args) => {
+                    dynamic_amm::process_update_activation_point(&args, admin_args, sub_args)
+                }
+                AdminCommands::CreateConfig(sub_args) => {
+                    dynamic_amm::process_create_config(&args, admin_args, sub_args)
+                }
+                AdminCommands::CloseConfig(sub_args) => {
+                    dynamic_amm::process_close_config(&args, admin_args, sub_args)
+                }
+                AdminCommands::InitializePermissionedPool(sub_args) => {
+                    dynamic_amm::process_initialize_permissioned_pool(&args, admin_args, sub_args)
+                }
+                AdminCommands::CreateMintMetadata(sub_args) => {
+                    dynamic_amm::process_create_mint_metadata(&args, admin_args, sub_args)
+                }
+            },
         },
+        Commands::DecodeAccount(sub_args) => process_decode_account(&args, sub_args),
+        Commands::VerifyLpMint(sub_args) => process_verify_lp_mint(&args, sub_args),
     }
 }
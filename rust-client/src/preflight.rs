@@ -0,0 +1,516 @@
+use crate::fee_estimation::DEFAULT_SIGNATURE_FEE;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, TokenAccount};
+use anyhow::Context;
+use common::cluster::Cluster;
+use common::dynamic_amm::config::{
+    get_activation_point_from_config, get_current_point, get_trade_fee_bps,
+};
+use common::dynamic_amm::customizable_params::CustomizablePoolParams;
+use common::dynamic_amm::pda::{derive_lp_mint_key, derive_metadata_key};
+use common::dynamic_vault::get_existing_vault;
+use dynamic_amm_quote::curve::curve_type::CurveType as QuoteCurveType;
+use prog_dynamic_amm::constants::fee::MAX_FEE_BPS;
+use prog_dynamic_amm::state::{ActivationType, Config, CurveType, Pool};
+use prog_dynamic_vault::state::Vault;
+use serde_json::{json, Value};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sysvar::clock::{self, Clock};
+
+/// Size of the metaplex metadata account of the pool LP mint
+const METADATA_ACCOUNT_SIZE: usize = 679;
+/// Token accounts created by the pool creation. 2 vault LP, 2 protocol fee, and payer LP.
+const POOL_TOKEN_ACCOUNT_COUNT: u64 = 5;
+
+/// Allowed trade fee bps of the pool with fee tier
+pub fn get_allowed_trade_fee_bps(curve_type: CurveType) -> Vec<u64> {
+    let curve_type = match curve_type {
+        CurveType::ConstantProduct => QuoteCurveType::ConstantProduct,
+        CurveType::Stable { .. } => QuoteCurveType::default(),
+    };
+    curve_type.get_allowed_trade_fee_bps().to_vec()
+}
+
+/// Pool creation instruction to be checked
+#[derive(Debug, Clone, Copy)]
+pub enum PoolCreationKind {
+    /// `initialize_permissionless_pool_with_fee_tier`
+    FeeTier {
+        curve_type: CurveType,
+        trade_fee_bps: u64,
+    },
+    /// `initialize_permissionless_constant_product_pool_with_config2`
+    Config {
+        config: Pubkey,
+        activation_point: Option<u64>,
+    },
+    /// `initialize_customizable_permissionless_constant_product_pool`
+    Customizable {
+        trade_fee_bps: u64,
+        activation_type: ActivationType,
+        activation_point: Option<u64>,
+    },
+}
+
+/// Planned pool creation
+#[derive(Debug, Clone, Copy)]
+pub struct PoolCreationPlan {
+    pub pool: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub payer: Pubkey,
+    pub kind: PoolCreationKind,
+}
+
+/// Check which failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreflightCheck {
+    PoolExists,
+    TradeFee,
+    Config,
+    Activation,
+    CustomizableParams,
+    Mint,
+    FreezeAuthority,
+    MetadataExists,
+    PayerLamports,
+    PayerTokenBalance,
+}
+
+impl PreflightCheck {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PoolExists => "pool_exists",
+            Self::TradeFee => "trade_fee",
+            Self::Config => "config",
+            Self::Activation => "activation",
+            Self::CustomizableParams => "customizable_params",
+            Self::Mint => "mint",
+            Self::FreezeAuthority => "freeze_authority",
+            Self::MetadataExists => "metadata_exists",
+            Self::PayerLamports => "payer_lamports",
+            Self::PayerTokenBalance => "payer_token_balance",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Pool creation will fail
+    Error,
+    /// Pool creation will succeed, but the pool might not be usable as expected
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreflightViolation {
+    pub check: PreflightCheck,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Result of the preflight checks
+#[derive(Debug, Clone, Default)]
+pub struct PreflightReport {
+    pub pool: Pubkey,
+    /// Existing vault of token A, which is initialized by the pool creation when None
+    pub vault_a: Option<Pubkey>,
+    /// Existing vault of token B, which is initialized by the pool creation when None
+    pub vault_b: Option<Pubkey>,
+    /// Estimated lamports required by the payer
+    pub required_lamports: u64,
+    pub violations: Vec<PreflightViolation>,
+}
+
+impl PreflightReport {
+    /// No violation which fail the pool creation
+    pub fn is_ok(&self) -> bool {
+        !self
+            .violations
+            .iter()
+            .any(|violation| violation.severity == Severity::Error)
+    }
+
+    fn error(&mut self, check: PreflightCheck, message: String) {
+        self.violations.push(PreflightViolation {
+            check,
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    fn warning(&mut self, check: PreflightCheck, message: String) {
+        self.violations.push(PreflightViolation {
+            check,
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "ok": self.is_ok(),
+            "pool": self.pool.to_string(),
+            "vault_a": self.vault_a.map(|vault| vault.to_string()),
+            "vault_b": self.vault_b.map(|vault| vault.to_string()),
+            "required_lamports": self.required_lamports,
+            "violations": self.violations.iter().map(|violation| json!({
+                "check": violation.check.name(),
+                "severity": match violation.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": violation.message,
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn check_trade_fee(report: &mut PreflightReport, curve_type: CurveType, trade_fee_bps: u64) {
+    let allowed_trade_fee_bps = get_allowed_trade_fee_bps(curve_type);
+    if !allowed_trade_fee_bps.contains(&trade_fee_bps) {
+        report.error(
+            PreflightCheck::TradeFee,
+            format!(
+                "Trade fee bps {} is not one of {:?}",
+                trade_fee_bps, allowed_trade_fee_bps
+            ),
+        );
+    }
+    if trade_fee_bps > MAX_FEE_BPS {
+        report.error(
+            PreflightCheck::TradeFee,
+            format!("Trade fee bps {} > {}", trade_fee_bps, MAX_FEE_BPS),
+        );
+    }
+}
+
+fn check_config(
+    cluster: &Cluster,
+    report: &mut PreflightReport,
+    plan: &PoolCreationPlan,
+    config: Option<Config>,
+    activation_point: Option<u64>,
+    clock: &Clock,
+) {
+    let Some(config) = config else {
+        report.error(PreflightCheck::Config, "Config not found".to_string());
+        return;
+    };
+
+    match get_trade_fee_bps(&config.pool_fees) {
+        Some(trade_fee_bps) if trade_fee_bps <= MAX_FEE_BPS => {}
+        trade_fee_bps => report.error(
+            PreflightCheck::TradeFee,
+            format!("Config trade fee bps {:?} > {}", trade_fee_bps, MAX_FEE_BPS),
+        ),
+    }
+
+    let Ok(activation_type) = ActivationType::try_from(config.activation_type) else {
+        report.error(
+            PreflightCheck::Config,
+            format!("Invalid config activation type {}", config.activation_type),
+        );
+        return;
+    };
+
+    if let Err(e) = get_current_point(clock, activation_type).and_then(|current_point| {
+        get_activation_point_from_config(
+            cluster,
+            &config,
+            plan.payer,
+            activation_point,
+            current_point,
+        )
+    }) {
+        report.error(PreflightCheck::Activation, e.to_string());
+    }
+}
+
+fn check_customizable(
+    cluster: &Cluster,
+    report: &mut PreflightReport,
+    plan: &PoolCreationPlan,
+    trade_fee_bps: u64,
+    activation_type: ActivationType,
+    activation_point: Option<u64>,
+    clock: &Clock,
+) {
+    let current_point = match get_current_point(clock, activation_type) {
+        Ok(current_point) => current_point,
+        Err(e) => {
+            report.error(PreflightCheck::Activation, e.to_string());
+            return;
+        }
+    };
+
+    let params = CustomizablePoolParams {
+        trade_fee_bps,
+        activation_type,
+        activation_point,
+        // Not validated
+        has_alpha_vault: false,
+    };
+    if let Err(e) =
+        params.to_customizable_params(cluster, plan.token_a_mint, plan.token_b_mint, current_point)
+    {
+        report.error(PreflightCheck::CustomizableParams, e.to_string());
+    }
+}
+
+fn check_mint(report: &mut PreflightReport, mint_key: Pubkey, data: Option<&[u8]>) {
+    let Some(mut data) = data else {
+        report.error(PreflightCheck::Mint, format!("Mint {} not found", mint_key));
+        return;
+    };
+    match Mint::try_deserialize_unchecked(&mut data) {
+        Ok(mint) => {
+            if mint.freeze_authority.is_some() {
+                report.warning(
+                    PreflightCheck::FreezeAuthority,
+                    format!("Mint {} has freeze authority", mint_key),
+                );
+            }
+        }
+        Err(e) => report.error(
+            PreflightCheck::Mint,
+            format!("Fail to decode mint {}: {}", mint_key, e),
+        ),
+    }
+}
+
+fn check_token_balance(
+    report: &mut PreflightReport,
+    mint_key: Pubkey,
+    amount: u64,
+    data: Option<&[u8]>,
+) {
+    let balance = data
+        .and_then(|mut data| TokenAccount::try_deserialize_unchecked(&mut data).ok())
+        .map_or(0, |token_account| token_account.amount);
+    if balance < amount {
+        report.error(
+            PreflightCheck::PayerTokenBalance,
+            format!(
+                "Payer balance of {} is {}, require {}",
+                mint_key, balance, amount
+            ),
+        );
+    }
+}
+
+/// Check the planned pool creation against the program constraints, and the on chain state
+pub fn run_preflight(
+    cluster: &Cluster,
+    client: &RpcClient,
+    plan: &PoolCreationPlan,
+) -> anyhow::Result<PreflightReport> {
+    let mut report = PreflightReport {
+        pool: plan.pool,
+        vault_a: get_existing_vault(cluster, client, plan.token_a_mint)?.map(|(vault, _)| vault),
+        vault_b: get_existing_vault(cluster, client, plan.token_b_mint)?.map(|(vault, _)| vault),
+        ..Default::default()
+    };
+
+    let lp_mint = derive_lp_mint_key(cluster, plan.pool);
+    let config_key = match plan.kind {
+        PoolCreationKind::Config { config, .. } => config,
+        _ => Pubkey::default(),
+    };
+
+    let accounts = client.get_multiple_accounts(&[
+        plan.pool,
+        derive_metadata_key(cluster, lp_mint),
+        plan.token_a_mint,
+        plan.token_b_mint,
+        get_associated_token_address(&plan.payer, &plan.token_a_mint),
+        get_associated_token_address(&plan.payer, &plan.token_b_mint),
+        config_key,
+        clock::id(),
+    ])?;
+    let data = accounts
+        .iter()
+        .map(|account| account.as_ref().map(|account| account.data.as_slice()))
+        .collect::<Vec<_>>();
+
+    if data[0].is_some() {
+        report.error(
+            PreflightCheck::PoolExists,
+            format!("Pool {} already exists", plan.pool),
+        );
+    }
+    if data[1].is_some() {
+        report.error(
+            PreflightCheck::MetadataExists,
+            format!("Metadata of the pool LP mint {} already exists", lp_mint),
+        );
+    }
+
+    check_mint(&mut report, plan.token_a_mint, data[2]);
+    check_mint(&mut report, plan.token_b_mint, data[3]);
+    check_token_balance(&mut report, plan.token_a_mint, plan.token_a_amount, data[4]);
+    check_token_balance(&mut report, plan.token_b_mint, plan.token_b_amount, data[5]);
+
+    let clock = bincode::deserialize::<Clock>(data[7].context("Clock not found")?)?;
+
+    match plan.kind {
+        PoolCreationKind::FeeTier {
+            curve_type,
+            trade_fee_bps,
+        } => check_trade_fee(&mut report, curve_type, trade_fee_bps),
+        PoolCreationKind::Config {
+            activation_point, ..
+        } => {
+            let config = data[6].and_then(|mut data| Config::try_deserialize(&mut data).ok());
+            check_config(cluster, &mut report, plan, config, activation_point, &clock);
+        }
+        PoolCreationKind::Customizable {
+            trade_fee_bps,
+            activation_type,
+            activation_point,
+        } => check_customizable(
+            cluster,
+            &mut report,
+            plan,
+            trade_fee_bps,
+            activation_type,
+            activation_point,
+            &clock,
+        ),
+    }
+
+    // Rent of the accounts created by the pool creation, and the missing vaults
+    let missing_vault_count = [report.vault_a, report.vault_b]
+        .iter()
+        .filter(|vault| vault.is_none())
+        .count() as u64;
+    let token_account_rent = client.get_minimum_balance_for_rent_exemption(TokenAccount::LEN)?;
+    let mint_rent = client.get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+    let pool_rent =
+        client.get_minimum_balance_for_rent_exemption(8 + std::mem::size_of::<Pool>())?;
+    let vault_rent =
+        client.get_minimum_balance_for_rent_exemption(8 + std::mem::size_of::<Vault>())?;
+    let metadata_rent = client.get_minimum_balance_for_rent_exemption(METADATA_ACCOUNT_SIZE)?;
+
+    report.required_lamports = pool_rent
+        + mint_rent
+        + metadata_rent
+        + token_account_rent * POOL_TOKEN_ACCOUNT_COUNT
+        + (vault_rent + token_account_rent + mint_rent) * missing_vault_count
+        + DEFAULT_SIGNATURE_FEE;
+
+    let payer_lamports = client.get_balance(&plan.payer)?;
+    if payer_lamports < report.required_lamports {
+        report.error(
+            PreflightCheck::PayerLamports,
+            format!(
+                "Payer lamports {} < estimated {}",
+                payer_lamports, report.required_lamports
+            ),
+        );
+    }
+
+    Ok(report)
+}
+
+/// Run the preflight checks, and print the report as JSON. Return false when the pool creation will fail.
+pub fn process_preflight(cluster: &Cluster, client: &RpcClient, plan: &PoolCreationPlan) -> bool {
+    let report = run_preflight(cluster, client, plan).unwrap();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report.to_json()).unwrap()
+    );
+    report.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prog_dynamic_amm::constants::fee::{
+        FEE_DENOMINATOR, MAX_BASIS_POINT, MEME_MAX_FEE_NUMERATOR, MEME_MIN_FEE_NUMERATOR,
+    };
+    use prog_dynamic_amm::constants::QUOTE_MINTS;
+
+    fn get_customizable_plan(token_a_mint: Pubkey, trade_fee_bps: u64) -> PoolCreationPlan {
+        PoolCreationPlan {
+            pool: Pubkey::new_unique(),
+            token_a_mint,
+            token_b_mint: Pubkey::new_unique(),
+            token_a_amount: 1_000_000,
+            token_b_amount: 1_000_000,
+            payer: Pubkey::new_unique(),
+            kind: PoolCreationKind::Customizable {
+                trade_fee_bps,
+                activation_type: ActivationType::Slot,
+                activation_point: None,
+            },
+        }
+    }
+
+    fn check_customizable_fee(token_a_mint: Pubkey, trade_fee_bps: u64) -> PreflightReport {
+        let plan = get_customizable_plan(token_a_mint, trade_fee_bps);
+        let mut report = PreflightReport::default();
+        check_customizable(
+            &Cluster::default(),
+            &mut report,
+            &plan,
+            trade_fee_bps,
+            ActivationType::Slot,
+            None,
+            &Clock::default(),
+        );
+        report
+    }
+
+    #[test]
+    fn test_report_is_ok_with_warning_only() {
+        let mut report = PreflightReport::default();
+        assert!(report.is_ok());
+
+        report.warning(PreflightCheck::FreezeAuthority, "freeze".to_string());
+        assert!(report.is_ok());
+        assert_eq!(report.to_json()["ok"], true);
+
+        report.error(PreflightCheck::PayerLamports, "lamports".to_string());
+        assert!(!report.is_ok());
+        assert_eq!(report.to_json()["ok"], false);
+        assert_eq!(report.to_json()["violations"][1]["severity"], "error");
+    }
+
+    #[test]
+    fn test_customizable_trade_fee_range() {
+        let quote_mint = QUOTE_MINTS[0];
+        let min_bps = MEME_MIN_FEE_NUMERATOR * MAX_BASIS_POINT / FEE_DENOMINATOR;
+        let max_bps = MEME_MAX_FEE_NUMERATOR * MAX_BASIS_POINT / FEE_DENOMINATOR;
+
+        for trade_fee_bps in [min_bps, max_bps] {
+            let report = check_customizable_fee(quote_mint, trade_fee_bps);
+            assert!(report.violations.is_empty(), "{}", trade_fee_bps);
+        }
+
+        for trade_fee_bps in [0, min_bps - 1, max_bps + 1, MAX_FEE_BPS + 1, u64::MAX] {
+            let report = check_customizable_fee(quote_mint, trade_fee_bps);
+            assert!(!report.is_ok(), "{}", trade_fee_bps);
+            assert_eq!(report.violations.len(), 1);
+            assert_eq!(
+                report.violations[0].check,
+                PreflightCheck::CustomizableParams
+            );
+        }
+    }
+
+    #[test]
+    fn test_customizable_without_quote_mint() {
+        let report = check_customizable_fee(Pubkey::new_unique(), 100);
+        assert!(!report.is_ok());
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(
+            report.violations[0].check,
+            PreflightCheck::CustomizableParams
+        );
+    }
+}
@@ -1,5 +1,15 @@
+use crate::fee_estimation::MAX_COMPUTE_UNIT;
+use anyhow::{bail, Context};
+use common::dynamic_amm::event_decoder::{decode_event_cpi, parse_event_logs, DecodedEvent};
 use regex::Regex;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiInstruction, UiTransactionStatusMeta};
+use std::str::FromStr;
 
 pub fn parse_event_log<
     T: anchor_lang::AnchorDeserialize + anchor_lang::AnchorSerialize + anchor_lang::Discriminator,
@@ -7,7 +17,7 @@ pub fn parse_event_log<
     logs: &Vec<String>,
     program_id: Pubkey,
 ) -> Option<T> {
-    let program_start_pattern = Regex::new(r"Program .* invoke \[\d{1}\]").ok()?;
+    let program_start_pattern = Regex::new(r"Program .* invoke \[\d+\]").ok()?;
     let program_end_pattern = Regex::new(r"Program .* success").ok()?;
     let mut execution_stack: Vec<String> = vec![];
     for log in logs.into_iter() {
@@ -37,3 +47,334 @@ pub fn parse_event_log<
     }
     None
 }
+
+/// Decode every event emitted by `program_id` in the transaction, including events emitted through CPI, such as swap routed by aggregator.
+/// Events are decoded from `Program data:` logs, and self CPI inner instructions, and merged in execution order by the inner instruction position.
+/// The order is only kept when the logs are complete, since the position of the logged events is counted from the invoke logs.
+/// `account_keys` are the static account keys of the transaction message. Addresses loaded from lookup tables are read from the meta.
+pub fn parse_transaction_events(
+    meta: &UiTransactionStatusMeta,
+    account_keys: &[Pubkey],
+    program_id: Pubkey,
+) -> anyhow::Result<Vec<DecodedEvent>> {
+    let log_events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => parse_event_logs(logs, program_id),
+        _ => vec![],
+    };
+
+    let mut cpi_events = vec![];
+    let mut account_keys = account_keys.to_vec();
+    if let OptionSerializer::Some(loaded_addresses) = &meta.loaded_addresses {
+        for address in loaded_addresses
+            .writable
+            .iter()
+            .chain(loaded_addresses.readonly.iter())
+        {
+            account_keys.push(Pubkey::from_str(address)?);
+        }
+    }
+
+    if let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions {
+        for inner_instruction in inner_instructions {
+            for (position, instruction) in inner_instruction.instructions.iter().enumerate() {
+                let UiInstruction::Compiled(instruction) = instruction else {
+                    continue;
+                };
+                let instruction_program_id = account_keys
+                    .get(usize::from(instruction.program_id_index))
+                    .context("Program id index out of range")?;
+                if *instruction_program_id != program_id {
+                    continue;
+                }
+                let data = solana_sdk::bs58::decode(&instruction.data).into_vec()?;
+                // Skip event CPI which fail to decode, like the logs
+                if let Some(Ok(event)) = decode_event_cpi(&data) {
+                    // Event CPI is invoked by the program itself, one level deeper than the emitter
+                    let invoke_depth = instruction
+                        .stack_height
+                        .map(|stack_height| (stack_height as usize).saturating_sub(1))
+                        .unwrap_or(1);
+                    cpi_events.push(DecodedEvent {
+                        instruction_index: inner_instruction.index.into(),
+                        invoke_depth,
+                        inner_instruction_position: position,
+                        program_id,
+                        event,
+                    });
+                }
+            }
+        }
+    }
+
+    // Stable sort keep the order of each source. Logged event at the same position is emitted before the event CPI is invoked.
+    let mut events = log_events;
+    events.extend(cpi_events);
+    events.sort_by_key(|event| (event.instruction_index, event.inner_instruction_position));
+
+    Ok(events)
+}
+
+fn with_compute_budget(
+    groups: &[Vec<Instruction>],
+    priority_fee: u64,
+    compute_unit_per_group: u32,
+) -> Vec<Instruction> {
+    let compute_unit = u32::try_from(groups.len())
+        .unwrap_or(u32::MAX)
+        .saturating_mul(compute_unit_per_group)
+        .min(MAX_COMPUTE_UNIT);
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_unit),
+    ];
+    ixs.extend(groups.iter().flatten().cloned());
+    ixs
+}
+
+fn fit_in_transaction(payer: &Pubkey, ixs: &[Instruction]) -> anyhow::Result<bool> {
+    let tx = Transaction::new_with_payer(ixs, Some(payer));
+    Ok(bincode::serialized_size(&tx)? <= PACKET_DATA_SIZE as u64)
+}
+
+/// Pack instruction groups into as few transactions as possible. Instructions of a group are never split across transactions.
+/// Every transaction start with compute budget instructions, with `compute_unit_per_group` of compute unit for each group.
+pub fn pack_instruction_groups(
+    payer: &Pubkey,
+    groups: Vec<Vec<Instruction>>,
+    priority_fee: u64,
+    compute_unit_per_group: u32,
+) -> anyhow::Result<Vec<Vec<Instruction>>> {
+    let packed =
+        pack_instruction_groups_with_count(payer, groups, priority_fee, compute_unit_per_group)?;
+    Ok(packed.into_iter().map(|(ixs, _)| ixs).collect())
+}
+
+/// Same as `pack_instruction_groups`, with the number of groups packed in each transaction
+pub fn pack_instruction_groups_with_count(
+    payer: &Pubkey,
+    groups: Vec<Vec<Instruction>>,
+    priority_fee: u64,
+    compute_unit_per_group: u32,
+) -> anyhow::Result<Vec<(Vec<Instruction>, usize)>> {
+    let max_groups_by_compute_unit =
+        (MAX_COMPUTE_UNIT / compute_unit_per_group.max(1)).max(1) as usize;
+
+    let mut packed = vec![];
+    let mut current: Vec<Vec<Instruction>> = vec![];
+
+    for group in groups {
+        current.push(group);
+
+        let ixs = with_compute_budget(&current, priority_fee, compute_unit_per_group);
+        if fit_in_transaction(payer, &ixs)? && current.len() <= max_groups_by_compute_unit {
+            continue;
+        }
+
+        if current.len() == 1 {
+            bail!("Instruction group doesn't fit in a transaction");
+        }
+
+        // Unwrap is safe, current has more than 1 group
+        let group = current.pop().unwrap();
+        packed.push((
+            with_compute_budget(&current, priority_fee, compute_unit_per_group),
+            current.len(),
+        ));
+        current = vec![group];
+
+        let ixs = with_compute_budget(&current, priority_fee, compute_unit_per_group);
+        if !fit_in_transaction(payer, &ixs)? {
+            bail!("Instruction group doesn't fit in a transaction");
+        }
+    }
+
+    if !current.is_empty() {
+        packed.push((
+            with_compute_budget(&current, priority_fee, compute_unit_per_group),
+            current.len(),
+        ));
+    }
+
+    Ok(packed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::base64;
+    use anchor_lang::event::EVENT_IX_TAG_LE;
+    use anchor_lang::Event;
+    use common::dynamic_amm::event_decoder::DynamicAmmEvent;
+    use prog_dynamic_amm::event::{CloseConfig, PoolEnabled};
+    use serde_json::json;
+    use solana_sdk::instruction::AccountMeta;
+
+    fn get_groups(count: usize, data_len: usize) -> Vec<Vec<Instruction>> {
+        let program_id = Pubkey::new_unique();
+        (0..count)
+            .map(|_| {
+                vec![Instruction::new_with_bytes(
+                    program_id,
+                    &vec![1; data_len],
+                    vec![AccountMeta::new(Pubkey::new_unique(), false)],
+                )]
+            })
+            .collect()
+    }
+
+    fn get_counts(packed: &[(Vec<Instruction>, usize)]) -> Vec<usize> {
+        packed.iter().map(|(_, count)| *count).collect()
+    }
+
+    #[test]
+    fn test_pack_empty_groups() {
+        let packed =
+            pack_instruction_groups_with_count(&Pubkey::new_unique(), vec![], 0, 1).unwrap();
+        assert!(packed.is_empty());
+    }
+
+    #[test]
+    fn test_pack_bounded_by_compute_unit() {
+        let payer = Pubkey::new_unique();
+        let compute_unit_per_group = MAX_COMPUTE_UNIT / 2;
+
+        let packed =
+            pack_instruction_groups_with_count(&payer, get_groups(5, 1), 1, compute_unit_per_group)
+                .unwrap();
+        assert_eq!(get_counts(&packed), vec![2, 2, 1]);
+        for (ixs, count) in packed.iter() {
+            assert_eq!(ixs.len(), count + 2);
+            assert_eq!(
+                ixs[1],
+                ComputeBudgetInstruction::set_compute_unit_limit(
+                    compute_unit_per_group * *count as u32
+                )
+            );
+        }
+
+        // Group over the max compute unit is packed alone, with the compute unit capped
+        let packed =
+            pack_instruction_groups_with_count(&payer, get_groups(2, 1), 1, u32::MAX).unwrap();
+        assert_eq!(get_counts(&packed), vec![1, 1]);
+        assert_eq!(
+            packed[0].0[1],
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT)
+        );
+
+        // Zero compute unit is not divided by zero
+        let packed = pack_instruction_groups_with_count(&payer, get_groups(3, 1), 1, 0).unwrap();
+        assert_eq!(get_counts(&packed), vec![3]);
+    }
+
+    #[test]
+    fn test_pack_bounded_by_transaction_size() {
+        let payer = Pubkey::new_unique();
+        let groups = get_groups(20, 200);
+
+        let packed = pack_instruction_groups_with_count(&payer, groups.clone(), 1, 1).unwrap();
+        assert!(packed.len() > 1);
+        assert_eq!(get_counts(&packed).iter().sum::<usize>(), groups.len());
+
+        let mut groups = groups.into_iter();
+        for (i, (ixs, count)) in packed.iter().enumerate() {
+            assert!(fit_in_transaction(&payer, ixs).unwrap());
+
+            let packed_groups: Vec<Vec<Instruction>> = groups.by_ref().take(*count).collect();
+            assert_eq!(ixs[2..], packed_groups.concat()[..]);
+
+            // Greedy, the first group of the next transaction doesn't fit
+            if let Some((next_ixs, _)) = packed.get(i + 1) {
+                let mut ixs = ixs.clone();
+                ixs.push(next_ixs[2].clone());
+                assert!(!fit_in_transaction(&payer, &ixs).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_oversized_group() {
+        let payer = Pubkey::new_unique();
+
+        let result =
+            pack_instruction_groups_with_count(&payer, get_groups(1, PACKET_DATA_SIZE), 1, 1);
+        assert!(result.is_err());
+
+        let mut groups = get_groups(2, 1);
+        groups.extend(get_groups(1, PACKET_DATA_SIZE));
+        let result = pack_instruction_groups_with_count(&payer, groups, 1, 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_transaction_events_execution_order() {
+        let program_id = Pubkey::new_unique();
+        let aggregator = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+
+        let logged_data = base64::encode(
+            PoolEnabled {
+                pool,
+                enabled: true,
+            }
+            .data(),
+        );
+        let mut event_cpi_data = EVENT_IX_TAG_LE.to_vec();
+        event_cpi_data.extend(CloseConfig { config }.data());
+
+        let meta: UiTransactionStatusMeta = serde_json::from_value(json!({
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+            "innerInstructions": [{
+                "index": 0,
+                "instructions": [
+                    {
+                        "programIdIndex": 1,
+                        "accounts": [],
+                        "data": solana_sdk::bs58::encode([1, 2, 3]).into_string(),
+                        "stackHeight": 2,
+                    },
+                    {
+                        "programIdIndex": 1,
+                        "accounts": [],
+                        "data": solana_sdk::bs58::encode(event_cpi_data).into_string(),
+                        "stackHeight": 3,
+                    },
+                ],
+            }],
+            "logMessages": [
+                format!("Program {} invoke [1]", aggregator),
+                format!("Program {} invoke [2]", program_id),
+                format!("Program data: {}", logged_data),
+                format!("Program {} invoke [3]", program_id),
+                format!("Program {} success", program_id),
+                format!("Program data: {}", logged_data),
+                format!("Program {} success", program_id),
+                format!("Program {} success", aggregator),
+            ],
+            "preTokenBalances": [],
+            "postTokenBalances": [],
+            "rewards": [],
+            "loadedAddresses": { "writable": [], "readonly": [] },
+            "returnData": null,
+            "computeUnitsConsumed": null,
+        }))
+        .unwrap();
+
+        let events =
+            parse_transaction_events(&meta, &[aggregator, program_id], program_id).unwrap();
+        let names: Vec<&str> = events.iter().map(|event| event.event.name()).collect();
+        assert_eq!(names, vec!["PoolEnabled", "CloseConfig", "PoolEnabled"]);
+
+        let DynamicAmmEvent::CloseConfig(event) = &events[1].event else {
+            panic!("Unexpected event {}", events[1].event.name());
+        };
+        assert_eq!(event.config, config);
+        assert_eq!(events[1].invoke_depth, 2);
+        assert_eq!(events[1].inner_instruction_position, 1);
+    }
+}